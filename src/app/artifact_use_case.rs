@@ -1,5 +1,7 @@
 use std::sync::Arc;
 use crate::domain::artifact::Artifact;
+use crate::domain::billing::QuotaOverrides;
+use crate::domain::storage::StorageUsageReport;
 use crate::domain::github::GithubRepository;
 use crate::domain::error::Result;
 
@@ -49,8 +51,14 @@ impl ArtifactUseCase {
         self.repo.delete_cache(owner, repo, cache_id).await
     }
 
-    pub async fn get_storage_usage(&self) -> Result<crate::domain::storage::StorageUsageReport> {
-        self.repo.get_storage_usage().await
+    pub async fn delete_release_asset(&self, owner: &str, repo: &str, asset_id: u64) -> Result<()> {
+        self.repo.delete_release_asset(owner, repo, asset_id).await
+    }
+
+    pub async fn get_storage_usage(&self, overrides: &QuotaOverrides, include_release_assets: bool) -> Result<StorageUsageReport> {
+        let mut report = self.repo.get_storage_usage(include_release_assets).await?;
+        report.apply_quota_overrides(overrides);
+        Ok(report)
     }
 }
 
@@ -106,4 +114,32 @@ mod tests {
         assert_eq!(deleted.len(), 1);
         assert_eq!(deleted[0].id, 1);
     }
+
+    #[tokio::test]
+    async fn test_get_storage_usage_applies_overrides() {
+        use crate::domain::billing::{AccountStorage, AccountType};
+
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_storage_usage()
+            .with(mockall::predicate::eq(true))
+            .times(1)
+            .returning(|_| Ok(StorageUsageReport::new(
+                vec![
+                    AccountStorage::new("octocat", AccountType::User),
+                    AccountStorage::new("acme", AccountType::Organization),
+                ],
+                vec![],
+            )));
+
+        let overrides = QuotaOverrides {
+            default: None,
+            accounts: [("acme".to_string(), 2048)].into_iter().collect(),
+        };
+
+        let use_case = ArtifactUseCase::new(Arc::new(mock));
+        let report = use_case.get_storage_usage(&overrides, true).await.unwrap();
+        assert_eq!(report.accounts[0].quota_bytes(), None);
+        assert_eq!(report.accounts[1].quota_bytes(), Some(2048));
+        assert_eq!(report.max_allowed(), 2048);
+    }
 }
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+use futures::stream::{self, StreamExt};
+use crate::domain::audit::{AuditReport, AuditRule, AuditRules, RepoFacts};
+use crate::domain::error::Result;
+use crate::domain::github::GithubRepository;
+use crate::domain::repo::{OwnerRepoType, RepoQuery, Repository};
+
+/// Repositories audited at the same time
+const AUDIT_CONCURRENCY: usize = 8;
+
+pub struct AuditUseCase {
+    github: Arc<dyn GithubRepository>,
+}
+
+impl AuditUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>) -> Self {
+        Self { github }
+    }
+
+    /// Check every repository of `owner` matching `query` against `rules`.
+    pub async fn audit_repos(&self, owner: &str, rules: &AuditRules, query: &RepoQuery) -> Result<AuditReport> {
+        let repos = self.github.list_repos_for(owner, OwnerRepoType::All).await?;
+        let repos: Vec<Repository> = query.apply(&repos).into_iter().cloned().collect();
+
+        let mut audits: Vec<_> = stream::iter(repos)
+            .map(|repo| async move { rules.evaluate(&self.facts(repo, rules).await) })
+            .buffer_unordered(AUDIT_CONCURRENCY)
+            .collect()
+            .await;
+        audits.sort_by(|a, b| a.repo.cmp(&b.repo));
+
+        Ok(AuditReport::new(owner.to_string(), rules.enabled(), audits))
+    }
+
+    /// Only what the enabled rules need is fetched. Anything that cannot be
+    /// read (usually for lack of admin rights) is left unknown, which counts
+    /// against the score.
+    async fn facts(&self, repo: Repository, rules: &AuditRules) -> RepoFacts {
+        let (owner, name) = (repo.owner.as_str(), repo.name.as_str());
+        let (protected, settings) = futures::join!(
+            async {
+                if !rules.is_enabled(AuditRule::BranchProtection) {
+                    return None;
+                }
+                self.github.is_branch_protected(owner, name, &repo.default_branch).await.ok()
+            },
+            async {
+                if !rules.is_enabled(AuditRule::SecretScanning) && !rules.is_enabled(AuditRule::DeleteBranchOnMerge) {
+                    return None;
+                }
+                self.github.get_repo_settings(owner, name).await.ok()
+            },
+        );
+
+        RepoFacts {
+            default_branch_protected: protected,
+            secret_scanning: settings.as_ref().and_then(|s| s.secret_scanning),
+            delete_branch_on_merge: settings.and_then(|s| s.delete_branch_on_merge),
+            repo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::audit::CheckOutcome;
+    use crate::domain::error::GhTuiError;
+    use crate::domain::github::MockGithubRepository;
+    use crate::domain::settings::RepoSettings;
+    use mockall::predicate::eq;
+
+    fn repo(name: &str, branch: &str, archived: bool) -> Repository {
+        Repository {
+            name: name.into(),
+            owner: "acme".into(),
+            full_name: format!("acme/{}", name),
+            description: Some(format!("The {} service", name)),
+            license: Some("MIT".into()),
+            default_branch: branch.into(),
+            archived,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_repos() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_repos_for()
+            .with(eq("acme"), eq(OwnerRepoType::All))
+            .returning(|_, _| Ok(vec![repo("web", "master", false), repo("api", "main", false), repo("old", "main", true)]));
+        mock.expect_is_branch_protected()
+            .returning(|_, name, branch| Ok(name == "api" && branch == "main"));
+        mock.expect_get_repo_settings().times(2).returning(|_, name| match name {
+            "api" => Ok(RepoSettings { delete_branch_on_merge: Some(true), secret_scanning: Some(true), ..Default::default() }),
+            // security_and_analysis is not visible
+            _ => Ok(RepoSettings { delete_branch_on_merge: Some(true), ..Default::default() }),
+        });
+
+        let use_case = AuditUseCase::new(Arc::new(mock));
+        let report = use_case.audit_repos("acme", &AuditRules::default(), &RepoQuery::default()).await.unwrap();
+
+        // Archived repositories are left out like in `ghr ls`
+        assert_eq!(report.repos.len(), 2);
+        let (api, web) = (&report.repos[0], &report.repos[1]);
+        assert_eq!(api.repo, "acme/api");
+        assert_eq!(api.count(CheckOutcome::Fail), 0);
+        assert_eq!(web.outcome(AuditRule::BranchProtection), Some(CheckOutcome::Fail));
+        assert_eq!(web.outcome(AuditRule::DefaultBranchName), Some(CheckOutcome::Fail));
+        assert_eq!(web.outcome(AuditRule::SecretScanning), Some(CheckOutcome::Unknown));
+        assert_eq!(report.violations, 2);
+        assert_eq!(report.unknown, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_checks_count_against_the_score() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_repos_for().returning(|_, _| Ok(vec![repo("api", "main", false)]));
+        mock.expect_is_branch_protected()
+            .returning(|_, _, _| Err(GhTuiError::Auth("Resource not accessible by integration".into())));
+        mock.expect_get_repo_settings()
+            .returning(|_, _| Err(GhTuiError::Auth("Resource not accessible by integration".into())));
+
+        let use_case = AuditUseCase::new(Arc::new(mock));
+        let report = use_case.audit_repos("acme", &AuditRules::default(), &RepoQuery::default()).await.unwrap();
+        assert_eq!(report.violations, 0);
+        assert_eq!(report.unknown, 3);
+        assert_eq!(report.score, 50.0);
+    }
+
+    #[tokio::test]
+    async fn test_audit_skips_calls_for_disabled_rules() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_repos_for().returning(|_, _| Ok(vec![repo("api", "main", false)]));
+        mock.expect_is_branch_protected().never();
+        mock.expect_get_repo_settings().never();
+
+        let rules = AuditRules {
+            branch_protection: false,
+            secret_scanning: false,
+            delete_branch_on_merge: false,
+            ..Default::default()
+        };
+        let use_case = AuditUseCase::new(Arc::new(mock));
+        let report = use_case.audit_repos("acme", &rules, &RepoQuery::default()).await.unwrap();
+        assert_eq!(report.rules.len(), 3);
+        assert_eq!(report.score, 100.0);
+    }
+}
@@ -1,4 +1,3 @@
-use std::sync::Arc;
 use crate::infra::github::auth::AuthManager;
 use crate::domain::error::Result;
 use crate::domain::user::User;
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use chrono::Utc;
+use crate::domain::branch::{Branch, BranchFilter, PruneCandidate};
+use crate::domain::error::Result;
+use crate::domain::github::GithubRepository;
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::repo::RepoRef;
+
+pub struct BranchUseCase {
+    github: Arc<dyn GithubRepository>,
+}
+
+impl BranchUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>) -> Self {
+        Self { github }
+    }
+
+    /// Branches matching `filter`, the default branch first and then the most
+    /// recently committed to.
+    pub async fn list(&self, target: &RepoRef, filter: &BranchFilter) -> Result<Vec<Branch>> {
+        let now = Utc::now();
+        let mut branches: Vec<Branch> = self
+            .github
+            .list_branches(&target.owner, &target.name)
+            .await?
+            .into_iter()
+            .filter(|b| filter.matches(b, now))
+            .collect();
+        branches.sort_by(|a, b| b.is_default.cmp(&a.is_default).then(b.last_commit_at.cmp(&a.last_commit_at)));
+        Ok(branches)
+    }
+
+    /// Branches that are merged (when `merged`) or without commits for
+    /// `stale_days`, oldest first.
+    pub async fn prune_candidates(&self, target: &RepoRef, merged: bool, stale_days: Option<i64>) -> Result<Vec<PruneCandidate>> {
+        let now = Utc::now();
+        let mut candidates: Vec<PruneCandidate> = self
+            .github
+            .list_branches(&target.owner, &target.name)
+            .await?
+            .into_iter()
+            .filter_map(|branch| {
+                branch
+                    .prune_reason(merged, stale_days, now)
+                    .map(|reason| PruneCandidate { branch, reason })
+            })
+            .collect();
+        candidates.sort_by_key(|c| c.branch.last_commit_at);
+        Ok(candidates)
+    }
+
+    /// Delete each branch, one at a time.
+    pub async fn delete(&self, target: &RepoRef, branches: &[String]) -> Vec<(String, RepoActionStatus)> {
+        let mut results = Vec::with_capacity(branches.len());
+        for branch in branches {
+            let status = match self.github.delete_branch(&target.owner, &target.name, branch).await {
+                Ok(()) => RepoActionStatus::Done,
+                Err(e) => RepoActionStatus::Failed(e.to_string()),
+            };
+            results.push((branch.clone(), status));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::branch::{BranchPullRequest, PruneReason, PullRequestState};
+    use crate::domain::error::GhTuiError;
+    use crate::domain::github::MockGithubRepository;
+    use chrono::Duration;
+    use mockall::predicate::eq;
+
+    fn branch(name: &str, ahead: u32, days_ago: i64) -> Branch {
+        Branch {
+            name: name.into(),
+            is_default: name == "main",
+            ahead,
+            last_commit_at: Some(Utc::now() - Duration::days(days_ago)),
+            ..Default::default()
+        }
+    }
+
+    fn branches() -> Vec<Branch> {
+        let mut open = branch("wip", 0, 300);
+        open.pull_request = Some(BranchPullRequest { number: 12, state: PullRequestState::Open, head_sha: None });
+        vec![
+            branch("feature", 4, 2),
+            branch("main", 0, 1),
+            branch("fix-typo", 0, 20),
+            branch("spike", 9, 200),
+            Branch { protected: true, ..branch("release-1", 0, 400) },
+            open,
+        ]
+    }
+
+    fn use_case() -> BranchUseCase {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_branches()
+            .with(eq("acme"), eq("api"))
+            .returning(|_, _| Ok(branches()));
+        BranchUseCase::new(Arc::new(mock))
+    }
+
+    #[tokio::test]
+    async fn test_list_orders_and_filters() {
+        let target: RepoRef = "acme/api".parse().unwrap();
+        let all = use_case().list(&target, &BranchFilter::default()).await.unwrap();
+        let names: Vec<_> = all.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "feature", "fix-typo", "spike", "wip", "release-1"]);
+
+        let merged = use_case().list(&target, &BranchFilter { merged: true, stale_days: Some(90) }).await.unwrap();
+        let names: Vec<_> = merged.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["wip", "release-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_prune_candidates() {
+        let target: RepoRef = "acme/api".parse().unwrap();
+        let candidates = use_case().prune_candidates(&target, true, Some(90)).await.unwrap();
+        let picked: Vec<_> = candidates.iter().map(|c| (c.branch.name.as_str(), c.reason)).collect();
+        assert_eq!(picked, vec![("spike", PruneReason::Stale), ("fix-typo", PruneReason::Merged)]);
+
+        let merged_only = use_case().prune_candidates(&target, true, None).await.unwrap();
+        assert_eq!(merged_only.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_reports_failures() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_delete_branch().returning(|_, _, branch| match branch {
+            "spike" => Ok(()),
+            _ => Err(GhTuiError::NotFound(format!("branch '{}'", branch))),
+        });
+        let use_case = BranchUseCase::new(Arc::new(mock));
+
+        let results = use_case.delete(&"acme/api".parse().unwrap(), &["spike".into(), "gone".into()]).await;
+        assert_eq!(results[0].1, RepoActionStatus::Done);
+        assert!(matches!(results[1].1, RepoActionStatus::Failed(_)));
+    }
+}
@@ -0,0 +1,187 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use futures::stream::{self, StreamExt};
+use crate::domain::error::Result;
+use crate::domain::git::{CloneOutcome, CloneStatus, CloneUrl, GitOperations};
+use crate::domain::github::GithubRepository;
+use crate::domain::repo::{OwnerRepoType, RepoQuery, RepoRef};
+
+pub struct CloneUseCase {
+    github: Arc<dyn GithubRepository>,
+    git: Arc<dyn GitOperations>,
+    url: CloneUrl,
+}
+
+impl CloneUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>, git: Arc<dyn GitOperations>, url: CloneUrl) -> Self {
+        Self { github, git, url }
+    }
+
+    /// Clone `target` into `dest`, or fetch it if `dest` is already a clone.
+    pub async fn clone_repo(&self, target: &RepoRef, dest: &Path) -> CloneOutcome {
+        let git = self.git.clone();
+        let url = self.url.for_repo(target);
+        let path = dest.to_path_buf();
+        let repo = target.to_string();
+
+        let status = tokio::task::spawn_blocking(move || {
+            let result = if path.join(".git").exists() {
+                git.fetch(&path).map(|_| CloneStatus::Updated)
+            } else {
+                git.clone_repo(&url, &path).map(|_| CloneStatus::Cloned)
+            };
+            result.unwrap_or_else(|e| CloneStatus::Failed(e.to_string()))
+        })
+        .await
+        .unwrap_or_else(|e| CloneStatus::Failed(e.to_string()));
+
+        CloneOutcome {
+            repo,
+            path: dest.to_path_buf(),
+            status,
+        }
+    }
+
+    /// Clone or update every repository of `owner` matching `query` into
+    /// `into/<name>`, running up to `parallelism` git processes at once.
+    pub async fn clone_all(
+        &self,
+        owner: &str,
+        repo_type: OwnerRepoType,
+        query: &RepoQuery,
+        into: &Path,
+        parallelism: usize,
+    ) -> Result<Vec<CloneOutcome>> {
+        let repos = self.github.list_repos_for(owner, repo_type).await?;
+        let targets: Vec<(RepoRef, PathBuf)> = query
+            .apply(&repos)
+            .into_iter()
+            .map(|r| {
+                let target = RepoRef { owner: r.owner.clone(), name: r.name.clone() };
+                (target, into.join(&r.name))
+            })
+            .collect();
+
+        let mut outcomes: Vec<CloneOutcome> = stream::iter(targets)
+            .map(|(target, dest)| async move { self.clone_repo(&target, &dest).await })
+            .buffer_unordered(parallelism.max(1))
+            .collect()
+            .await;
+        outcomes.sort_by(|a, b| a.repo.cmp(&b.repo));
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::GhTuiError;
+    use crate::domain::git::MockGitOperations;
+    use crate::domain::github::MockGithubRepository;
+    use crate::domain::repo::Repository;
+    use crate::infra::git::tests::bare_repo;
+    use crate::infra::git::GitCli;
+    use mockall::predicate::eq;
+
+    fn repo(name: &str) -> Repository {
+        Repository {
+            name: name.into(),
+            owner: "acme".into(),
+            full_name: format!("acme/{}", name),
+            ..Default::default()
+        }
+    }
+
+    fn github_with(repos: Vec<Repository>) -> MockGithubRepository {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_repos_for()
+            .with(eq("acme"), eq(OwnerRepoType::All))
+            .times(1)
+            .returning(move |_, _| Ok(repos.clone()));
+        mock
+    }
+
+    #[tokio::test]
+    async fn test_clone_all_clones_new_and_fetches_existing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("web").join(".git")).unwrap();
+
+        let mut git = MockGitOperations::new();
+        git.expect_clone_repo()
+            .withf(|url, _| url == "https://github.com/acme/api.git")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        git.expect_clone_repo()
+            .withf(|url, _| url == "https://github.com/acme/docs.git")
+            .times(1)
+            .returning(|_, _| Err(GhTuiError::Git("Repository not found.".into())));
+        git.expect_fetch().times(1).returning(|_| Ok(()));
+
+        let use_case = CloneUseCase::new(
+            Arc::new(github_with(vec![repo("web"), repo("api"), repo("docs")])),
+            Arc::new(git),
+            CloneUrl::Protocol(Default::default()),
+        );
+        let outcomes = use_case
+            .clone_all("acme", OwnerRepoType::All, &RepoQuery::default(), dir.path(), 2)
+            .await
+            .unwrap();
+
+        let statuses: Vec<(&str, &CloneStatus)> = outcomes.iter().map(|o| (o.repo.as_str(), &o.status)).collect();
+        assert_eq!(statuses[0], ("acme/api", &CloneStatus::Cloned));
+        assert_eq!(statuses[1], ("acme/docs", &CloneStatus::Failed("Git error: Repository not found.".into())));
+        assert_eq!(statuses[2], ("acme/web", &CloneStatus::Updated));
+        assert_eq!(outcomes[0].path, dir.path().join("api"));
+    }
+
+    #[tokio::test]
+    async fn test_clone_all_applies_query() {
+        let mut archived = repo("legacy");
+        archived.archived = true;
+        let mut git = MockGitOperations::new();
+        git.expect_clone_repo().times(1).returning(|_, _| Ok(()));
+
+        let use_case = CloneUseCase::new(
+            Arc::new(github_with(vec![repo("api"), archived])),
+            Arc::new(git),
+            CloneUrl::Protocol(Default::default()),
+        );
+        let outcomes = use_case
+            .clone_all("acme", OwnerRepoType::All, &RepoQuery::default(), Path::new("/tmp/ghr-never-used"), 4)
+            .await
+            .unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].repo, "acme/api");
+    }
+
+    #[tokio::test]
+    async fn test_clone_all_from_local_bare_repos() {
+        let remote = tempfile::tempdir().unwrap();
+        bare_repo(remote.path(), "acme", "api");
+        bare_repo(remote.path(), "acme", "web");
+        let into = tempfile::tempdir().unwrap();
+
+        let url = CloneUrl::Template(format!("file://{}/{{owner}}/{{name}}.git", remote.path().display()));
+        let mut github = MockGithubRepository::new();
+        github.expect_list_repos_for()
+            .times(2)
+            .returning(|_, _| Ok(vec![repo("api"), repo("web"), repo("missing")]));
+        let use_case = CloneUseCase::new(Arc::new(github), Arc::new(GitCli::new()), url);
+
+        let first = use_case
+            .clone_all("acme", OwnerRepoType::All, &RepoQuery::default(), into.path(), 2)
+            .await
+            .unwrap();
+        assert_eq!(first[0].status, CloneStatus::Cloned);
+        assert!(matches!(first[1].status, CloneStatus::Failed(_)));
+        assert_eq!(first[2].status, CloneStatus::Cloned);
+        assert!(into.path().join("web").join("README.md").exists());
+
+        let second = use_case
+            .clone_all("acme", OwnerRepoType::All, &RepoQuery::default(), into.path(), 2)
+            .await
+            .unwrap();
+        assert_eq!(second[0].status, CloneStatus::Updated);
+        assert_eq!(second[2].status, CloneStatus::Updated);
+    }
+}
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::github::GithubRepository;
+use crate::domain::issue::{CloseReason, Issue, IssueComment, IssueDetails, IssueQuery, IssueState, NewIssue};
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::repo::RepoRef;
+
+pub struct IssueUseCase {
+    github: Arc<dyn GithubRepository>,
+}
+
+impl IssueUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>) -> Self {
+        Self { github }
+    }
+
+    pub async fn list(&self, target: &RepoRef, query: &IssueQuery) -> Result<Vec<Issue>> {
+        let mut issues = self.github.list_issues(&target.owner, &target.name, query).await?;
+        if let Some(limit) = query.limit {
+            issues.truncate(limit);
+        }
+        Ok(issues)
+    }
+
+    /// The issue, with its comments when `with_comments` is set.
+    pub async fn view(&self, target: &RepoRef, number: u64, with_comments: bool) -> Result<IssueDetails> {
+        let issue = self.github.get_issue(&target.owner, &target.name, number).await?;
+        let comments = if with_comments && issue.comments > 0 {
+            self.github.list_issue_comments(&target.owner, &target.name, number).await?
+        } else {
+            Vec::new()
+        };
+        Ok(IssueDetails { issue, comments })
+    }
+
+    pub async fn create(&self, target: &RepoRef, new_issue: &NewIssue) -> Result<Issue> {
+        new_issue.validate().map_err(GhTuiError::InvalidInput)?;
+        self.github.create_issue(&target.owner, &target.name, new_issue).await
+    }
+
+    /// Close or reopen an issue, commenting first when `comment` is given.
+    /// `Unchanged` (and no comment) when the issue already is in that state.
+    pub async fn set_state(
+        &self,
+        target: &RepoRef,
+        number: u64,
+        state: IssueState,
+        reason: Option<CloseReason>,
+        comment: Option<String>,
+    ) -> Result<(Issue, RepoActionStatus)> {
+        let issue = self.github.get_issue(&target.owner, &target.name, number).await?;
+        if issue.state == state {
+            return Ok((issue, RepoActionStatus::Unchanged));
+        }
+        if let Some(comment) = comment.filter(|c| !c.trim().is_empty()) {
+            self.github.add_issue_comment(&target.owner, &target.name, number, &comment).await?;
+        }
+        let issue = self
+            .github
+            .set_issue_state(&target.owner, &target.name, number, state, reason)
+            .await?;
+        Ok((issue, RepoActionStatus::Done))
+    }
+
+    pub async fn comment(&self, target: &RepoRef, number: u64, body: &str) -> Result<IssueComment> {
+        if body.trim().is_empty() {
+            return Err(GhTuiError::InvalidInput("the comment cannot be empty".into()));
+        }
+        self.github.add_issue_comment(&target.owner, &target.name, number, body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::github::MockGithubRepository;
+    use crate::domain::issue::IssueStateFilter;
+    use chrono::Utc;
+    use mockall::predicate::{always, eq};
+
+    fn issue(number: u64, state: IssueState, comments: u32) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            state,
+            state_reason: None,
+            author: "octocat".into(),
+            assignees: Vec::new(),
+            labels: Vec::new(),
+            milestone: None,
+            comments,
+            body: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            closed_at: None,
+            html_url: format!("https://github.com/acme/api/issues/{}", number),
+        }
+    }
+
+    fn target() -> RepoRef {
+        "acme/api".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_passes_query_and_limits() {
+        let query = IssueQuery { state: IssueStateFilter::All, labels: vec!["bug".into()], limit: Some(2), ..Default::default() };
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_issues()
+            .with(eq("acme"), eq("api"), eq(query.clone()))
+            .returning(|_, _, _| Ok((1..=3).map(|n| issue(n, IssueState::Open, 0)).collect()));
+        let use_case = IssueUseCase::new(Arc::new(mock));
+
+        let issues = use_case.list(&target(), &query).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_view_fetches_comments_only_when_needed() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_issue()
+            .returning(|_, _, number| Ok(issue(number, IssueState::Open, if number == 1 { 2 } else { 0 })));
+        mock.expect_list_issue_comments()
+            .with(eq("acme"), eq("api"), eq(1))
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![IssueComment {
+                    author: "hubot".into(),
+                    body: "Same here".into(),
+                    created_at: Utc::now(),
+                    html_url: String::new(),
+                }])
+            });
+        let use_case = IssueUseCase::new(Arc::new(mock));
+
+        assert_eq!(use_case.view(&target(), 1, true).await.unwrap().comments.len(), 1);
+        assert!(use_case.view(&target(), 1, false).await.unwrap().comments.is_empty());
+        assert!(use_case.view(&target(), 2, true).await.unwrap().comments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_close_comments_first_and_skips_closed_issues() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_issue()
+            .returning(|_, _, number| Ok(issue(number, if number == 1 { IssueState::Open } else { IssueState::Closed }, 0)));
+        mock.expect_add_issue_comment()
+            .with(eq("acme"), eq("api"), eq(1), eq("Fixed in #3"))
+            .times(1)
+            .returning(|_, _, _, body| {
+                Ok(IssueComment { author: "octocat".into(), body: body.into(), created_at: Utc::now(), html_url: String::new() })
+            });
+        mock.expect_set_issue_state()
+            .with(eq("acme"), eq("api"), eq(1), eq(IssueState::Closed), eq(Some(CloseReason::Completed)))
+            .times(1)
+            .returning(|_, _, number, state, _| Ok(issue(number, state, 1)));
+        let use_case = IssueUseCase::new(Arc::new(mock));
+
+        let (closed, status) = use_case
+            .set_state(&target(), 1, IssueState::Closed, Some(CloseReason::Completed), Some("Fixed in #3".into()))
+            .await
+            .unwrap();
+        assert_eq!((closed.state, status), (IssueState::Closed, RepoActionStatus::Done));
+
+        let (_, status) = use_case
+            .set_state(&target(), 2, IssueState::Closed, None, Some("Again".into()))
+            .await
+            .unwrap();
+        assert_eq!(status, RepoActionStatus::Unchanged);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_empty_input() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_create_issue().never();
+        mock.expect_add_issue_comment().with(always(), always(), always(), always()).never();
+        let use_case = IssueUseCase::new(Arc::new(mock));
+
+        let err = use_case.create(&target(), &NewIssue { title: " ".into(), ..Default::default() }).await.unwrap_err();
+        assert!(matches!(err, GhTuiError::InvalidInput(_)));
+        let err = use_case.comment(&target(), 1, "\n").await.unwrap_err();
+        assert!(matches!(err, GhTuiError::InvalidInput(_)));
+    }
+}
@@ -1,3 +1,13 @@
 pub mod artifact_use_case;
 pub mod repo_use_case;
 pub mod auth_use_case;
+pub mod storage_history_use_case;
+pub mod retention_use_case;
+pub mod clone_use_case;
+pub mod audit_use_case;
+pub mod protection_use_case;
+pub mod branch_use_case;
+pub mod issue_use_case;
+pub mod search_use_case;
+pub mod pull_request_use_case;
+pub mod release_use_case;
@@ -0,0 +1,219 @@
+use std::sync::Arc;
+use futures::stream::{self, StreamExt};
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::github::GithubRepository;
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::protection::{BranchProtection, ProtectionPlan, ProtectionReport, Ruleset};
+use crate::domain::repo::{RepoRef, Repository};
+
+/// Repositories read at the same time when planning
+const PLAN_CONCURRENCY: usize = 8;
+
+pub struct ProtectionUseCase {
+    github: Arc<dyn GithubRepository>,
+}
+
+impl ProtectionUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>) -> Self {
+        Self { github }
+    }
+
+    /// Branch protection and rulesets of `branch`, the default branch when `None`.
+    /// The report still comes back when only the rulesets cannot be read.
+    pub async fn show(&self, target: &RepoRef, branch: Option<String>) -> Result<ProtectionReport> {
+        let branch = match branch {
+            Some(branch) => branch,
+            None => self.github.get_repo(&target.owner, &target.name).await?.default_branch,
+        };
+        let (protection, rulesets) = futures::join!(
+            self.github.get_branch_protection(&target.owner, &target.name, &branch),
+            self.github.list_rulesets(&target.owner, &target.name),
+        );
+        let (rulesets, rulesets_error) = match rulesets {
+            Ok(rulesets) => (rulesets, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        Ok(ProtectionReport {
+            repo: target.to_string(),
+            protection: protection?,
+            rulesets,
+            rulesets_error,
+            branch,
+        })
+    }
+
+    /// Compare each repository with `desired`, as branch protection of
+    /// `branch` (each default branch when `None`) or as the ruleset named
+    /// `ruleset`. Plans come back in the order of `repos`.
+    pub async fn plan(
+        &self,
+        repos: &[Repository],
+        branch: Option<&str>,
+        ruleset: Option<&str>,
+        desired: &BranchProtection,
+    ) -> Result<Vec<ProtectionPlan>> {
+        desired.validate().map_err(GhTuiError::InvalidInput)?;
+
+        let plans = stream::iter(repos)
+            .map(|repo| async move {
+                let (owner, name) = (repo.owner.as_str(), repo.name.as_str());
+                let mut plan = ProtectionPlan {
+                    owner: repo.owner.clone(),
+                    name: repo.name.clone(),
+                    branch: branch.unwrap_or(&repo.default_branch).to_string(),
+                    ruleset: None,
+                    changes: Vec::new(),
+                    status: RepoActionStatus::Pending,
+                };
+
+                let changes = match ruleset {
+                    Some(ruleset_name) => self.github.list_rulesets(owner, name).await.map(|rulesets| {
+                        let current = rulesets.into_iter().find(|r| r.name == ruleset_name);
+                        let wanted = Ruleset {
+                            id: current.as_ref().and_then(|r| r.id),
+                            name: ruleset_name.to_string(),
+                            enforcement: "active".into(),
+                            branches: vec![Ruleset::branch_pattern(branch)],
+                            protection: desired.clone(),
+                        };
+                        let changes = wanted.diff(current.as_ref());
+                        plan.ruleset = Some(wanted);
+                        changes
+                    }),
+                    None => self
+                        .github
+                        .get_branch_protection(owner, name, &plan.branch)
+                        .await
+                        .map(|current| desired.diff(current.as_ref())),
+                };
+
+                match changes {
+                    Ok(changes) if changes.is_empty() => plan.status = RepoActionStatus::Unchanged,
+                    Ok(changes) => plan.changes = changes,
+                    Err(e) => plan.status = RepoActionStatus::Failed(e.to_string()),
+                }
+                plan
+            })
+            .buffered(PLAN_CONCURRENCY)
+            .collect()
+            .await;
+        Ok(plans)
+    }
+
+    /// Apply the pending plans one at a time, recording `Done` or `Failed`.
+    pub async fn apply(&self, plans: &mut [ProtectionPlan], desired: &BranchProtection) {
+        for plan in plans.iter_mut().filter(|p| p.status == RepoActionStatus::Pending) {
+            let result = match plan.ruleset {
+                Some(ref ruleset) => self.github.save_ruleset(&plan.owner, &plan.name, ruleset).await,
+                None => self.github.set_branch_protection(&plan.owner, &plan.name, &plan.branch, desired).await,
+            };
+            plan.status = match result {
+                Ok(()) => RepoActionStatus::Done,
+                Err(e) => RepoActionStatus::Failed(e.to_string()),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::github::MockGithubRepository;
+    use mockall::predicate::eq;
+
+    fn repo(name: &str, branch: &str) -> Repository {
+        Repository {
+            name: name.into(),
+            owner: "acme".into(),
+            full_name: format!("acme/{}", name),
+            default_branch: branch.into(),
+            ..Default::default()
+        }
+    }
+
+    fn desired() -> BranchProtection {
+        BranchProtection {
+            required_approving_reviews: Some(1),
+            required_linear_history: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_and_apply_branch_protection() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_branch_protection().returning(|_, name, branch| match (name, branch) {
+            ("api", "main") => Ok(Some(desired())),
+            ("web", "master") => Ok(None),
+            _ => Err(GhTuiError::NotFound("branch".into())),
+        });
+        mock.expect_set_branch_protection()
+            .withf(|_, name, branch, p| name == "web" && branch == "master" && *p == desired())
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+        let use_case = ProtectionUseCase::new(Arc::new(mock));
+
+        let repos = vec![repo("api", "main"), repo("web", "master"), repo("docs", "gh-pages")];
+        let mut plans = use_case.plan(&repos, None, None, &desired()).await.unwrap();
+        assert_eq!(plans[0].status, RepoActionStatus::Unchanged);
+        assert_eq!(plans[1].changes[0].setting, "protected");
+        assert!(matches!(plans[2].status, RepoActionStatus::Failed(_)));
+
+        use_case.apply(&mut plans, &desired()).await;
+        assert_eq!(plans[1].status, RepoActionStatus::Done);
+    }
+
+    #[tokio::test]
+    async fn test_plan_updates_existing_ruleset() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_rulesets().with(eq("acme"), eq("api")).returning(|_, _| {
+            Ok(vec![Ruleset {
+                id: Some(9),
+                name: "main".into(),
+                enforcement: "active".into(),
+                branches: vec!["refs/heads/main".into()],
+                protection: BranchProtection::unprotected(),
+            }])
+        });
+        mock.expect_get_branch_protection().never();
+        mock.expect_save_ruleset()
+            .withf(|_, _, r| r.id == Some(9) && r.protection == desired())
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let use_case = ProtectionUseCase::new(Arc::new(mock));
+
+        let mut plans = use_case.plan(&[repo("api", "main")], Some("main"), Some("main"), &desired()).await.unwrap();
+        assert!(!plans[0].changes.iter().any(|c| c.setting == "ruleset" || c.setting == "branches"));
+        use_case.apply(&mut plans, &desired()).await;
+        assert_eq!(plans[0].status, RepoActionStatus::Done);
+    }
+
+    #[tokio::test]
+    async fn test_show_uses_default_branch() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo().returning(|_, name| Ok(repo(name, "trunk")));
+        mock.expect_get_branch_protection()
+            .with(eq("acme"), eq("api"), eq("trunk"))
+            .returning(|_, _, _| Ok(None));
+        mock.expect_list_rulesets().returning(|_, _| Ok(Vec::new()));
+        let use_case = ProtectionUseCase::new(Arc::new(mock));
+
+        let report = use_case.show(&"acme/api".parse().unwrap(), None).await.unwrap();
+        assert_eq!(report.branch, "trunk");
+        assert!(report.protection.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_show_keeps_protection_when_rulesets_fail() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_branch_protection().returning(|_, _, _| Ok(Some(BranchProtection::default())));
+        mock.expect_list_rulesets()
+            .returning(|_, _| Err(GhTuiError::Auth("Resource not accessible by integration".into())));
+        let use_case = ProtectionUseCase::new(Arc::new(mock));
+
+        let report = use_case.show(&"acme/api".parse().unwrap(), Some("main".into())).await.unwrap();
+        assert!(report.protection.is_some());
+        assert!(report.rulesets.is_empty());
+        assert!(report.rulesets_error.unwrap().contains("not accessible"));
+    }
+}
@@ -0,0 +1,291 @@
+use std::path::Path;
+use std::sync::Arc;
+use crate::domain::branch::PullRequestState;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::git::{repo_from_remote_url, GitOperations};
+use crate::domain::github::GithubRepository;
+use crate::domain::pull_request::{
+    MergeMethod, MergeOutcome, NewReview, PrListQuery, PrStatus, PrStatusQuery, PullRequest, PullRequestDetails,
+};
+use crate::domain::repo::RepoRef;
+
+pub struct PullRequestUseCase {
+    github: Arc<dyn GithubRepository>,
+    git: Arc<dyn GitOperations>,
+}
+
+impl PullRequestUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>, git: Arc<dyn GitOperations>) -> Self {
+        Self { github, git }
+    }
+
+    /// The GitHub repository `remote` of the clone in `dir` points to.
+    pub fn repo_from_remote(&self, dir: &Path, remote: &str) -> Result<RepoRef> {
+        let url = self.git.remote_url(dir, remote).map_err(|_| {
+            GhTuiError::InvalidInput(format!("no git remote '{}' here, pass --repo owner/repo", remote))
+        })?;
+        repo_from_remote_url(&url).ok_or_else(|| {
+            GhTuiError::InvalidInput(format!("remote '{}' ({}) is not a GitHub repository, pass --repo owner/repo", remote, url))
+        })
+    }
+
+    pub async fn list(&self, query: &PrListQuery) -> Result<Vec<PullRequest>> {
+        if query.author.as_deref().is_some_and(|a| a.trim().is_empty() || a.contains(char::is_whitespace)) {
+            return Err(GhTuiError::InvalidInput("the author must be a single login".into()));
+        }
+        self.github.list_pull_requests(query).await
+    }
+
+    pub async fn get(&self, target: &RepoRef, number: u64) -> Result<PullRequest> {
+        self.github.get_pull_request(&target.owner, &target.name, number).await
+    }
+
+    pub async fn details(&self, target: &RepoRef, number: u64) -> Result<PullRequestDetails> {
+        self.github.get_pull_request_details(&target.owner, &target.name, number).await
+    }
+
+    /// Fetch the head of pull request `number` from `remote` and switch the
+    /// clone in `dir` to `branch`, its local branch by default.
+    pub async fn checkout(&self, dir: &Path, remote: &str, number: u64, branch: Option<String>) -> Result<(PullRequest, String)> {
+        let target = self.repo_from_remote(dir, remote)?;
+        let pr = self.get(&target, number).await?;
+        // refs/pull/N/head lives in the base repository, forks included
+        self.git.fetch_ref(dir, remote, &format!("refs/pull/{}/head", number))?;
+        let branch = branch.unwrap_or_else(|| pr.local_branch());
+        self.git.checkout_branch(dir, &branch, "FETCH_HEAD")?;
+        Ok((pr, branch))
+    }
+
+    pub async fn diff(&self, target: &RepoRef, number: u64) -> Result<String> {
+        self.github.get_pull_request_diff(&target.owner, &target.name, number).await
+    }
+
+    /// Submit a review, returning its URL.
+    pub async fn review(&self, target: &RepoRef, number: u64, review: &NewReview) -> Result<String> {
+        review.validate().map_err(GhTuiError::InvalidInput)?;
+        self.github.submit_review(&target.owner, &target.name, number, review).await
+    }
+
+    /// Merge the pull request, or with `auto` have GitHub merge it once
+    /// checks and reviews allow. Known blockers fail before calling the merge
+    /// API so the error can list them all.
+    pub async fn merge(&self, target: &RepoRef, number: u64, method: MergeMethod, delete_branch: bool, auto: bool) -> Result<(PullRequest, MergeOutcome)> {
+        let pr = self.get(target, number).await?;
+        match pr.state {
+            PullRequestState::Merged => return Err(GhTuiError::InvalidInput(format!("#{} is already merged", number))),
+            PullRequestState::Closed => return Err(GhTuiError::InvalidInput(format!("#{} is closed", number))),
+            PullRequestState::Open | PullRequestState::Draft => {}
+        }
+
+        let blockers = pr.merge_blockers();
+        if !blockers.is_empty() {
+            if auto {
+                self.github.enable_auto_merge(&pr.id, method).await?;
+                return Ok((pr, MergeOutcome::AutoMergeEnabled));
+            }
+            return Err(GhTuiError::InvalidInput(format!(
+                "cannot merge #{}: {} (use --auto to merge it once possible)",
+                number,
+                blockers.join(", ")
+            )));
+        }
+
+        self.github.merge_pull_request(&target.owner, &target.name, number, method, &pr.head_sha).await?;
+        // The head branch of a fork belongs to someone else. The merge is done
+        // by now, so failing to delete the branch is only reported.
+        let (branch_deleted, branch_error) = if delete_branch && !pr.is_from_fork() {
+            match self.github.delete_branch(&target.owner, &target.name, &pr.head_ref).await {
+                Ok(()) => (true, None),
+                Err(e) => (false, Some(e.to_string())),
+            }
+        } else {
+            (false, None)
+        };
+        Ok((pr, MergeOutcome::Merged { branch_deleted, branch_error }))
+    }
+
+    /// Pull requests involving the authenticated user. Authors often assign
+    /// their own pull requests, so those are only listed as authored.
+    pub async fn status(&self, query: &PrStatusQuery) -> Result<PrStatus> {
+        let mut status = self.github.pull_request_status(query).await?;
+        let authored: Vec<(String, u64)> = status.authored.iter().map(|pr| (pr.repository.clone(), pr.number)).collect();
+        status.assigned.retain(|pr| !authored.contains(&(pr.repository.clone(), pr.number)));
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::domain::git::MockGitOperations;
+    use crate::domain::github::MockGithubRepository;
+    use crate::domain::pull_request::{CheckStatus, MergeStateStatus, MergeableState, ReviewDecision, ReviewEvent};
+    use mockall::predicate::eq;
+
+    fn pr(repository: &str, number: u64) -> PullRequest {
+        PullRequest {
+            id: format!("PR_{}", number),
+            repository: repository.into(),
+            number,
+            title: format!("PR {}", number),
+            author: "octocat".into(),
+            state: PullRequestState::Open,
+            draft: false,
+            head_ref: "feature".into(),
+            head_sha: "abc123".into(),
+            head_repository: Some(repository.into()),
+            base_ref: "main".into(),
+            checks: CheckStatus::Success,
+            review_decision: None,
+            mergeable: MergeableState::Mergeable,
+            merge_state: MergeStateStatus::Clean,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            html_url: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_drops_self_assigned_from_assigned() {
+        let query = PrStatusQuery { repo: None, org: Some("acme".into()), limit: 20 };
+        let mut mock = MockGithubRepository::new();
+        mock.expect_pull_request_status()
+            .with(eq(query.clone()))
+            .times(1)
+            .returning(|_| {
+                Ok(PrStatus {
+                    authored: vec![pr("acme/api", 1)],
+                    review_requested: vec![pr("acme/web", 1)],
+                    assigned: vec![pr("acme/api", 1), pr("acme/web", 1), pr("acme/api", 2)],
+                })
+            });
+        let use_case = PullRequestUseCase::new(Arc::new(mock), Arc::new(MockGitOperations::new()));
+
+        let status = use_case.status(&query).await.unwrap();
+        assert_eq!(status.authored.len(), 1);
+        assert_eq!(status.review_requested.len(), 1);
+        let assigned: Vec<_> = status.assigned.iter().map(|pr| (pr.repository.as_str(), pr.number)).collect();
+        assert_eq!(assigned, vec![("acme/web", 1), ("acme/api", 2)]);
+    }
+
+    fn api() -> RepoRef {
+        "acme/api".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_checkout_uses_remote_and_head_ref() {
+        let mut git = MockGitOperations::new();
+        git.expect_remote_url().returning(|_, _| Ok("git@github.com:acme/api.git".into()));
+        git.expect_fetch_ref()
+            .withf(|_, remote, refspec| remote == "origin" && refspec == "refs/pull/7/head")
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        git.expect_checkout_branch()
+            .withf(|_, branch, start| branch == "feature" && start == "FETCH_HEAD")
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let mut github = MockGithubRepository::new();
+        github.expect_get_pull_request()
+            .with(eq("acme"), eq("api"), eq(7))
+            .returning(|_, _, n| Ok(pr("acme/api", n)));
+        let use_case = PullRequestUseCase::new(Arc::new(github), Arc::new(git));
+
+        let (_, branch) = use_case.checkout(Path::new("."), "origin", 7, None).await.unwrap();
+        assert_eq!(branch, "feature");
+    }
+
+    #[tokio::test]
+    async fn test_blocked_merge_lists_blockers() {
+        let mut github = MockGithubRepository::new();
+        github.expect_get_pull_request().returning(|_, _, n| {
+            Ok(PullRequest {
+                merge_state: MergeStateStatus::Blocked,
+                checks: CheckStatus::Failure,
+                review_decision: Some(ReviewDecision::ReviewRequired),
+                ..pr("acme/api", n)
+            })
+        });
+        github.expect_merge_pull_request().never();
+        github.expect_enable_auto_merge()
+            .with(eq("PR_7"), eq(MergeMethod::Squash))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let use_case = PullRequestUseCase::new(Arc::new(github), Arc::new(MockGitOperations::new()));
+
+        let err = use_case.merge(&api(), 7, MergeMethod::Squash, false, false).await.unwrap_err().to_string();
+        assert!(err.contains("required checks are failing, it needs an approving review"), "{}", err);
+
+        let (_, outcome) = use_case.merge(&api(), 7, MergeMethod::Squash, false, true).await.unwrap();
+        assert_eq!(outcome, MergeOutcome::AutoMergeEnabled);
+    }
+
+    #[tokio::test]
+    async fn test_merge_deletes_branch_unless_fork() {
+        let mut github = MockGithubRepository::new();
+        github.expect_get_pull_request().returning(|_, _, n| {
+            let pr = pr("acme/api", n);
+            Ok(if n == 8 { PullRequest { head_repository: Some("hubot/api".into()), ..pr } } else { pr })
+        });
+        github.expect_merge_pull_request()
+            .withf(|_, _, _, method, sha| *method == MergeMethod::Rebase && sha == "abc123")
+            .times(2)
+            .returning(|_, _, _, _, _| Ok(()));
+        github.expect_delete_branch()
+            .with(eq("acme"), eq("api"), eq("feature"))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let use_case = PullRequestUseCase::new(Arc::new(github), Arc::new(MockGitOperations::new()));
+
+        let (_, outcome) = use_case.merge(&api(), 7, MergeMethod::Rebase, true, false).await.unwrap();
+        assert_eq!(outcome, MergeOutcome::Merged { branch_deleted: true, branch_error: None });
+        let (_, outcome) = use_case.merge(&api(), 8, MergeMethod::Rebase, true, false).await.unwrap();
+        assert_eq!(outcome, MergeOutcome::Merged { branch_deleted: false, branch_error: None });
+    }
+
+    #[tokio::test]
+    async fn test_merge_succeeds_when_branch_deletion_fails() {
+        let mut github = MockGithubRepository::new();
+        github.expect_get_pull_request().returning(|_, _, n| Ok(pr("acme/api", n)));
+        github.expect_merge_pull_request().times(1).returning(|_, _, _, _, _| Ok(()));
+        // Already deleted by GitHub when the repository deletes head branches on merge
+        github.expect_delete_branch()
+            .times(1)
+            .returning(|_, _, _| Err(GhTuiError::InvalidInput("Reference does not exist".into())));
+        let use_case = PullRequestUseCase::new(Arc::new(github), Arc::new(MockGitOperations::new()));
+
+        let (_, outcome) = use_case.merge(&api(), 7, MergeMethod::Squash, true, false).await.unwrap();
+        match outcome {
+            MergeOutcome::Merged { branch_deleted, branch_error } => {
+                assert!(!branch_deleted);
+                assert!(branch_error.unwrap().contains("Reference does not exist"));
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_review_makes_no_call() {
+        let mut github = MockGithubRepository::new();
+        github.expect_submit_review().never();
+        let use_case = PullRequestUseCase::new(Arc::new(github), Arc::new(MockGitOperations::new()));
+
+        let review = NewReview { event: ReviewEvent::RequestChanges, body: None };
+        assert!(matches!(use_case.review(&api(), 7, &review).await, Err(GhTuiError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_rejects_blank_author() {
+        let mut github = MockGithubRepository::new();
+        github.expect_list_pull_requests()
+            .withf(|q| q.author.as_deref() == Some("hubot"))
+            .times(1)
+            .returning(|_| Ok(vec![pr("acme/api", 1)]));
+        let use_case = PullRequestUseCase::new(Arc::new(github), Arc::new(MockGitOperations::new()));
+
+        let query = PrListQuery { author: Some("hubot".into()), limit: 100, ..Default::default() };
+        assert_eq!(use_case.list(&query).await.unwrap().len(), 1);
+        let query = PrListQuery { author: Some("hu bot".into()), ..query };
+        assert!(matches!(use_case.list(&query).await, Err(GhTuiError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use chrono::Utc;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::github::GithubRepository;
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::release::{AssetUpload, NewRelease, PrereleaseCleanup, Release, ReleaseAsset, TransferProgress};
+use crate::domain::repo::RepoRef;
+
+pub struct ReleaseUseCase {
+    github: Arc<dyn GithubRepository>,
+}
+
+impl ReleaseUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>) -> Self {
+        Self { github }
+    }
+
+    pub async fn list(&self, target: &RepoRef, limit: usize) -> Result<Vec<Release>> {
+        let mut releases = self.github.list_releases(&target.owner, &target.name).await?;
+        releases.truncate(limit);
+        Ok(releases)
+    }
+
+    /// The release of `tag`. The tag endpoint ignores drafts, so those are
+    /// looked up in the release list.
+    pub async fn view(&self, target: &RepoRef, tag: &str) -> Result<Release> {
+        match self.github.get_release_by_tag(&target.owner, &target.name, tag).await {
+            Err(GhTuiError::NotFound(message)) => self
+                .github
+                .list_releases(&target.owner, &target.name)
+                .await?
+                .into_iter()
+                .find(|r| r.draft && r.tag_name == tag)
+                .ok_or(GhTuiError::NotFound(message)),
+            result => result,
+        }
+    }
+
+    pub async fn create(&self, target: &RepoRef, new_release: &NewRelease) -> Result<Release> {
+        new_release.validate().map_err(GhTuiError::InvalidInput)?;
+        self.github.create_release(&target.owner, &target.name, new_release).await
+    }
+
+    /// Check every file before anything is uploaded: it must exist, names must
+    /// be unique, and an existing asset is only replaced with `clobber`.
+    pub async fn upload_plan(&self, target: &RepoRef, tag: &str, files: &[PathBuf], clobber: bool) -> Result<(Release, Vec<AssetUpload>)> {
+        let release = self.view(target, tag).await?;
+        let mut names = HashSet::new();
+        let mut uploads = Vec::with_capacity(files.len());
+
+        for path in files {
+            let metadata = std::fs::metadata(path)
+                .map_err(|e| GhTuiError::InvalidInput(format!("cannot read {}: {}", path.display(), e)))?;
+            if !metadata.is_file() {
+                return Err(GhTuiError::InvalidInput(format!("{} is not a file", path.display())));
+            }
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if !names.insert(name.clone()) {
+                return Err(GhTuiError::InvalidInput(format!("two files are named '{}'", name)));
+            }
+            let replaces = release.assets.iter().find(|a| a.name == name).map(|a| a.id);
+            if replaces.is_some() && !clobber {
+                return Err(GhTuiError::InvalidInput(format!(
+                    "{} already has an asset named '{}', pass --clobber to replace it",
+                    tag, name
+                )));
+            }
+            uploads.push(AssetUpload { path: path.clone(), name, size_in_bytes: metadata.len(), replaces });
+        }
+        Ok((release, uploads))
+    }
+
+    /// Upload one file. An asset it replaces is only deleted once the file is
+    /// uploaded, under a temporary name that then becomes the asset's name.
+    pub async fn upload(&self, target: &RepoRef, release: &Release, upload: &AssetUpload, progress: TransferProgress) -> Result<ReleaseAsset> {
+        let (owner, name) = (target.owner.as_str(), target.name.as_str());
+        let Some(replaced) = upload.replaces else {
+            return self.github.upload_release_asset(owner, name, release.id, upload, progress).await;
+        };
+
+        // GitHub refuses two assets with the same name
+        let staged = AssetUpload { name: format!("{}.uploading", upload.name), ..upload.clone() };
+        let asset = self.github.upload_release_asset(owner, name, release.id, &staged, progress).await?;
+        if let Err(e) = self.github.delete_release_asset(owner, name, replaced).await {
+            // Leave the release as it was
+            let _ = self.github.delete_release_asset(owner, name, asset.id).await;
+            return Err(e);
+        }
+        self.github.rename_release_asset(owner, name, asset.id, &upload.name).await
+    }
+
+    /// The assets of `tag` matching any of the glob `patterns` (all of them
+    /// without patterns). Patterns matching nothing are an error, as a typo
+    /// would otherwise download nothing silently.
+    pub async fn download_plan(&self, target: &RepoRef, tag: &str, patterns: &[String]) -> Result<(Release, Vec<ReleaseAsset>)> {
+        let release = self.view(target, tag).await?;
+        if !patterns.is_empty() && release.matching_assets(patterns).is_empty() {
+            return Err(GhTuiError::NotFound(format!("no asset of {} matches '{}'", tag, patterns.join("', '"))));
+        }
+        let assets = release.matching_assets(patterns).into_iter().cloned().collect();
+        Ok((release, assets))
+    }
+
+    /// Save `asset` into `dir`, returning its path. Existing files are only
+    /// overwritten with `clobber`, and only once the download is complete.
+    pub async fn download(&self, target: &RepoRef, asset: &ReleaseAsset, dir: &Path, clobber: bool, progress: TransferProgress) -> Result<PathBuf> {
+        let path = dir.join(&asset.name);
+        if path.exists() && !clobber {
+            return Err(GhTuiError::InvalidInput(format!("{} already exists, pass --clobber to overwrite it", path.display())));
+        }
+        std::fs::create_dir_all(dir)?;
+        let partial = dir.join(format!("{}.part", asset.name));
+        if let Err(e) = self.github.download_release_asset(&target.owner, &target.name, asset.id, &partial, progress).await {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e);
+        }
+        std::fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+
+    /// Delete the release of `tag`, keeping the tag.
+    pub async fn delete(&self, target: &RepoRef, tag: &str) -> Result<Release> {
+        let release = self.view(target, tag).await?;
+        self.github.delete_release(&target.owner, &target.name, release.id).await?;
+        Ok(release)
+    }
+
+    /// Pre-releases `cleanup` selects, oldest first.
+    pub async fn prerelease_cleanup(&self, target: &RepoRef, cleanup: &PrereleaseCleanup) -> Result<Vec<Release>> {
+        let releases = self.github.list_releases(&target.owner, &target.name).await?;
+        Ok(cleanup.select(&releases, Utc::now()))
+    }
+
+    /// Delete each release, one at a time.
+    pub async fn delete_all(&self, target: &RepoRef, releases: &[Release]) -> Vec<(String, RepoActionStatus)> {
+        let mut results = Vec::with_capacity(releases.len());
+        for release in releases {
+            let status = match self.github.delete_release(&target.owner, &target.name, release.id).await {
+                Ok(()) => RepoActionStatus::Done,
+                Err(e) => RepoActionStatus::Failed(e.to_string()),
+            };
+            results.push((release.tag_name.clone(), status));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::github::MockGithubRepository;
+    use chrono::Duration;
+    use mockall::predicate::eq;
+    use mockall::Sequence;
+
+    fn cli() -> RepoRef {
+        "acme/cli".parse().unwrap()
+    }
+
+    fn asset(id: u64, name: &str) -> ReleaseAsset {
+        ReleaseAsset {
+            id,
+            name: name.into(),
+            size_in_bytes: 4,
+            download_count: 0,
+            content_type: "application/octet-stream".into(),
+            created_at: Utc::now(),
+            browser_download_url: String::new(),
+        }
+    }
+
+    fn release(id: u64, tag: &str, prerelease: bool, draft: bool) -> Release {
+        Release {
+            id,
+            tag_name: tag.into(),
+            name: None,
+            body: None,
+            draft,
+            prerelease,
+            author: "octocat".into(),
+            created_at: Some(Utc::now() - Duration::days(id as i64)),
+            published_at: if draft { None } else { Some(Utc::now() - Duration::days(id as i64)) },
+            html_url: String::new(),
+            assets: vec![asset(10, "ghr.zip"), asset(11, "ghr.tar.gz")],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_view_falls_back_to_drafts() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_release_by_tag()
+            .returning(|_, _, tag| Err(GhTuiError::NotFound(format!("release '{}'", tag))));
+        mock.expect_list_releases()
+            .with(eq("acme"), eq("cli"))
+            .returning(|_, _| Ok(vec![release(1, "v2.0.0", false, true), release(2, "v1.0.0", false, false)]));
+        let use_case = ReleaseUseCase::new(Arc::new(mock));
+
+        assert_eq!(use_case.view(&cli(), "v2.0.0").await.unwrap().id, 1);
+        assert!(matches!(use_case.view(&cli(), "v3.0.0").await, Err(GhTuiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_plan_requires_clobber_to_replace() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip = dir.path().join("ghr.zip");
+        let deb = dir.path().join("ghr.deb");
+        std::fs::write(&zip, b"new!").unwrap();
+        std::fs::write(&deb, b"deb").unwrap();
+
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_release_by_tag().returning(|_, _, tag| Ok(release(1, tag, false, false)));
+        mock.expect_upload_release_asset().never();
+        let use_case = ReleaseUseCase::new(Arc::new(mock));
+
+        let err = use_case.upload_plan(&cli(), "v1.0.0", &[deb.clone(), zip.clone()], false).await.unwrap_err();
+        assert!(err.to_string().contains("--clobber"), "{}", err);
+
+        let (_, uploads) = use_case.upload_plan(&cli(), "v1.0.0", &[deb.clone(), zip], true).await.unwrap();
+        let plan: Vec<_> = uploads.iter().map(|u| (u.name.as_str(), u.size_in_bytes, u.replaces)).collect();
+        assert_eq!(plan, vec![("ghr.deb", 3, None), ("ghr.zip", 4, Some(10))]);
+
+        let missing = dir.path().join("missing.txt");
+        let err = use_case.upload_plan(&cli(), "v1.0.0", &[missing], false).await.unwrap_err();
+        assert!(matches!(err, GhTuiError::InvalidInput(_)));
+        let err = use_case.upload_plan(&cli(), "v1.0.0", &[deb.clone(), deb], false).await.unwrap_err();
+        assert!(err.to_string().contains("two files"), "{}", err);
+    }
+
+    fn no_progress() -> TransferProgress {
+        Arc::new(|_| {})
+    }
+
+    #[tokio::test]
+    async fn test_upload_replaces_existing_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip = dir.path().join("ghr.zip");
+        std::fs::write(&zip, b"new!").unwrap();
+
+        // The old asset goes only once the new one is up
+        let mut seq = Sequence::new();
+        let mut mock = MockGithubRepository::new();
+        mock.expect_upload_release_asset()
+            .withf(|_, _, release_id, upload, _| *release_id == 1 && upload.name == "ghr.zip.uploading")
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, upload, _| Ok(asset(12, &upload.name)));
+        mock.expect_delete_release_asset()
+            .with(eq("acme"), eq("cli"), eq(10))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(()));
+        mock.expect_rename_release_asset()
+            .with(eq("acme"), eq("cli"), eq(12), eq("ghr.zip"))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, id, name| Ok(asset(id, name)));
+        let use_case = ReleaseUseCase::new(Arc::new(mock));
+
+        let upload = AssetUpload { path: zip, name: "ghr.zip".into(), size_in_bytes: 4, replaces: Some(10) };
+        let uploaded = use_case.upload(&cli(), &release(1, "v1.0.0", false, false), &upload, no_progress()).await.unwrap();
+        assert_eq!((uploaded.id, uploaded.name.as_str()), (12, "ghr.zip"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_upload_keeps_replaced_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip = dir.path().join("ghr.zip");
+        std::fs::write(&zip, b"new!").unwrap();
+
+        let mut mock = MockGithubRepository::new();
+        mock.expect_upload_release_asset()
+            .returning(|_, _, _, _, _| Err(GhTuiError::InvalidInput("connection reset".into())));
+        mock.expect_delete_release_asset().never();
+        mock.expect_rename_release_asset().never();
+        let use_case = ReleaseUseCase::new(Arc::new(mock));
+
+        let upload = AssetUpload { path: zip, name: "ghr.zip".into(), size_in_bytes: 4, replaces: Some(10) };
+        assert!(use_case.upload(&cli(), &release(1, "v1.0.0", false, false), &upload, no_progress()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_download_matching_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_release_by_tag().returning(|_, _, tag| Ok(release(1, tag, false, false)));
+        mock.expect_download_release_asset()
+            .withf(|owner, repo, asset_id, _, _| owner == "acme" && repo == "cli" && *asset_id == 11)
+            .times(1)
+            .returning(|_, _, _, path, progress| {
+                std::fs::write(path, b"tarball")?;
+                progress(7);
+                Ok(())
+            });
+        let use_case = ReleaseUseCase::new(Arc::new(mock));
+
+        let err = use_case.download_plan(&cli(), "v1.0.0", &["*.deb".into()]).await.unwrap_err();
+        assert!(matches!(err, GhTuiError::NotFound(_)));
+
+        let (_, assets) = use_case.download_plan(&cli(), "v1.0.0", &["*.tar.gz".into()]).await.unwrap();
+        assert_eq!(assets.len(), 1);
+        let received = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let counter = received.clone();
+        let progress: TransferProgress = Arc::new(move |n| counter.store(n, std::sync::atomic::Ordering::Relaxed));
+        let path = use_case.download(&cli(), &assets[0], &dir.path().join("dist"), false, progress).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"tarball");
+        assert_eq!(received.load(std::sync::atomic::Ordering::Relaxed), 7);
+        assert!(!dir.path().join("dist/ghr.tar.gz.part").exists());
+
+        // The file now exists
+        let err = use_case.download(&cli(), &assets[0], &dir.path().join("dist"), false, no_progress()).await.unwrap_err();
+        assert!(err.to_string().contains("--clobber"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_tag() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_create_release().never();
+        let use_case = ReleaseUseCase::new(Arc::new(mock));
+
+        let new_release = NewRelease { tag: "v1 final".into(), generate_notes: true, ..Default::default() };
+        assert!(matches!(use_case.create(&cli(), &new_release).await, Err(GhTuiError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_old_prereleases() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_releases().returning(|_, _| {
+            Ok(vec![
+                release(1, "v2.0.0-rc.2", true, false),
+                release(5, "v1.0.0", false, false),
+                release(9, "v2.0.0-rc.1", true, false),
+                release(12, "v1.0.0-rc.1", true, false),
+            ])
+        });
+        mock.expect_delete_release()
+            .returning(|_, _, id| if id == 9 { Err(GhTuiError::NotFound("release".into())) } else { Ok(()) });
+        let use_case = ReleaseUseCase::new(Arc::new(mock));
+
+        let cleanup = PrereleaseCleanup { keep: 1, older_than_days: None };
+        let candidates = use_case.prerelease_cleanup(&cli(), &cleanup).await.unwrap();
+        let results = use_case.delete_all(&cli(), &candidates).await;
+        assert_eq!(results[0], ("v1.0.0-rc.1".to_string(), RepoActionStatus::Done));
+        assert!(matches!(results[1], (ref tag, RepoActionStatus::Failed(_)) if tag == "v2.0.0-rc.1"));
+        assert_eq!(results.len(), 2);
+    }
+}
@@ -1,7 +1,10 @@
 use std::sync::Arc;
-use crate::domain::repo::Repository;
+use crate::domain::repo::{LanguageShare, NewRepository, OwnerRepoType, RepoDetails, RepoQuery, RepoRef, Repository};
 use crate::domain::github::GithubRepository;
-use crate::domain::error::Result;
+use crate::domain::lifecycle::{RepoAction, RepoActionResult, RepoActionStatus};
+use crate::domain::settings::{RepoSettings, SettingsPlan};
+use futures::stream::{self, StreamExt};
+use crate::domain::error::{GhTuiError, Result};
 
 pub struct RepoUseCase {
     repo: Arc<dyn GithubRepository>,
@@ -12,34 +15,128 @@ impl RepoUseCase {
         Self { repo }
     }
 
+    /// Repositories of `owner` (or of the authenticated user) matching `query`.
+    /// `repo_type` only applies when an owner is given.
     pub async fn list_repos(
         &self,
         owner: Option<String>,
-        filter_private: bool,
-        filter_public: bool,
-        all: bool,
+        repo_type: OwnerRepoType,
+        query: &RepoQuery,
     ) -> Result<Vec<Repository>> {
-        let mut repos = match owner {
-            Some(ref o) => self.repo.list_repos_for(o).await?,
+        let repos = match owner {
+            Some(ref o) => self.repo.list_repos_for(o, repo_type).await?,
             None => self.repo.list_user_repos().await?,
         };
 
-        if !all {
-            repos.retain(|r| !r.archived);
-        }
+        Ok(query.apply(&repos).into_iter().cloned().collect())
+    }
 
-        if filter_private {
-            repos.retain(|r| r.private);
-        }
-        if filter_public {
-            repos.retain(|r| !r.private);
+    pub async fn get_repo(&self, target: &RepoRef) -> Result<Repository> {
+        self.repo.get_repo(&target.owner, &target.name).await
+    }
+
+    pub async fn create(&self, new_repo: &NewRepository) -> Result<Repository> {
+        new_repo.validate().map_err(GhTuiError::InvalidInput)?;
+        self.repo.create_repo(new_repo).await
+    }
+
+    pub async fn fork(&self, target: &RepoRef, org: Option<String>) -> Result<Repository> {
+        self.repo.fork_repo(&target.owner, &target.name, org).await
+    }
+
+    /// Archive, unarchive or delete each repository, one at a time. Repositories
+    /// already in the target state are left alone; nothing is changed on a dry run.
+    pub async fn apply_action(&self, repos: &[Repository], action: RepoAction, dry_run: bool) -> Vec<RepoActionResult> {
+        let mut results = Vec::with_capacity(repos.len());
+        for repo in repos {
+            let status = if action.is_noop(repo) {
+                RepoActionStatus::Unchanged
+            } else if dry_run {
+                RepoActionStatus::Pending
+            } else {
+                let result = match action {
+                    RepoAction::Archive => self.repo.set_repo_archived(&repo.owner, &repo.name, true).await,
+                    RepoAction::Unarchive => self.repo.set_repo_archived(&repo.owner, &repo.name, false).await,
+                    RepoAction::Delete => self.repo.delete_repo(&repo.owner, &repo.name).await,
+                };
+                match result {
+                    Ok(()) => RepoActionStatus::Done,
+                    Err(e) => RepoActionStatus::Failed(e.to_string()),
+                }
+            };
+            results.push(RepoActionResult {
+                repo: repo.full_name.clone(),
+                action,
+                status,
+            });
         }
+        results
+    }
+
+    /// Compare each repository with the desired settings, reading up to eight
+    /// at a time. Plans come back in the order of `repos`.
+    pub async fn plan_settings(&self, repos: &[Repository], desired: &RepoSettings) -> Result<Vec<SettingsPlan>> {
+        desired.validate().map_err(GhTuiError::InvalidInput)?;
 
-        Ok(repos)
+        let plans = stream::iter(repos)
+            .map(|repo| async move {
+                let (changes, status) = match self.repo.get_repo_settings(&repo.owner, &repo.name).await {
+                    Ok(current) => {
+                        let changes = desired.diff(&current);
+                        let status = if changes.is_empty() { RepoActionStatus::Unchanged } else { RepoActionStatus::Pending };
+                        (changes, status)
+                    }
+                    Err(e) => (Vec::new(), RepoActionStatus::Failed(e.to_string())),
+                };
+                SettingsPlan {
+                    owner: repo.owner.clone(),
+                    name: repo.name.clone(),
+                    update: desired.only(&changes),
+                    changes,
+                    status,
+                }
+            })
+            .buffered(8)
+            .collect()
+            .await;
+        Ok(plans)
+    }
+
+    /// Apply the pending plans one at a time, recording `Done` or `Failed`.
+    pub async fn apply_settings(&self, plans: &mut [SettingsPlan]) {
+        for plan in plans.iter_mut().filter(|p| p.status == RepoActionStatus::Pending) {
+            plan.status = match self.repo.update_repo_settings(&plan.owner, &plan.name, &plan.update).await {
+                Ok(()) => RepoActionStatus::Done,
+                Err(e) => RepoActionStatus::Failed(e.to_string()),
+            };
+        }
     }
 
-    pub async fn get_repo(&self, owner: &str, name: &str) -> Result<Repository> {
-        self.repo.get_repo(owner, name).await
+    /// Repository with its languages, activity and README. Only the repository
+    /// itself is required; sections the token cannot read are left empty.
+    pub async fn view(&self, target: &RepoRef) -> Result<RepoDetails> {
+        let (owner, name) = (target.owner.as_str(), target.name.as_str());
+        let repo = self.repo.get_repo(owner, name).await?;
+
+        let (languages, pull_requests, release, run, readme) = futures::join!(
+            self.repo.get_repo_languages(owner, name),
+            self.repo.count_open_pull_requests(owner, name),
+            self.repo.get_latest_release(owner, name),
+            self.repo.get_last_workflow_run(owner, name),
+            self.repo.get_readme(owner, name),
+        );
+
+        // GitHub counts pull requests as issues
+        let open_pull_requests = pull_requests.ok();
+        Ok(RepoDetails {
+            languages: LanguageShare::from_bytes(languages.unwrap_or_default()),
+            open_issues: repo.open_issues_count.saturating_sub(open_pull_requests.unwrap_or(0)),
+            open_pull_requests,
+            latest_release: release.ok().flatten(),
+            last_workflow_run: run.ok().flatten(),
+            readme: readme.ok().flatten(),
+            repo,
+        })
     }
 }
 
@@ -47,6 +144,9 @@ impl RepoUseCase {
 mod tests {
     use super::*;
     use crate::domain::github::MockGithubRepository;
+    use crate::domain::repo::RepoSort;
+    use chrono::{Duration, Utc};
+    use mockall::predicate::eq;
 
     #[tokio::test]
     async fn test_list_repos_filtering() {
@@ -54,16 +154,316 @@ mod tests {
         mock.expect_list_user_repos()
             .times(1)
             .returning(|| {
-                let mut r1 = Repository::default();
-                r1.private = true;
-                let mut r2 = Repository::default();
-                r2.private = false;
+                let r1 = Repository { private: true, ..Default::default() };
+                let r2 = Repository { private: false, ..Default::default() };
                 Ok(vec![r1, r2])
             });
 
         let use_case = RepoUseCase::new(Arc::new(mock));
-        let private_only = use_case.list_repos(None, true, false, false).await.unwrap();
+        let query = RepoQuery { private: Some(true), ..Default::default() };
+        let private_only = use_case.list_repos(None, OwnerRepoType::All, &query).await.unwrap();
         assert_eq!(private_only.len(), 1);
         assert!(private_only[0].private);
     }
+
+    fn repo(name: &str, stars: u32, pushed_days_ago: i64) -> Repository {
+        Repository {
+            name: name.into(),
+            stargazers_count: stars,
+            pushed_at: Some(Utc::now() - Duration::days(pushed_days_ago)),
+            size: stars as u64 * 10,
+            ..Default::default()
+        }
+    }
+
+    fn use_case_with(repos: Vec<Repository>) -> RepoUseCase {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_repos_for()
+            .with(eq("acme"), eq(OwnerRepoType::All))
+            .times(1)
+            .returning(move |_, _| Ok(repos.clone()));
+        RepoUseCase::new(Arc::new(mock))
+    }
+
+    fn named(name: &str, archived: bool) -> Repository {
+        Repository {
+            name: name.into(),
+            owner: "acme".into(),
+            full_name: format!("acme/{}", name),
+            archived,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_skips_archived_and_reports_failures() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_set_repo_archived()
+            .with(eq("acme"), eq("api"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock.expect_set_repo_archived()
+            .with(eq("acme"), eq("web"), eq(true))
+            .times(1)
+            .returning(|_, _, _| Err(GhTuiError::Auth("forbidden".into())));
+
+        let use_case = RepoUseCase::new(Arc::new(mock));
+        let repos = vec![named("api", false), named("old", true), named("web", false)];
+        let results = use_case.apply_action(&repos, RepoAction::Archive, false).await;
+
+        let statuses: Vec<&RepoActionStatus> = results.iter().map(|r| &r.status).collect();
+        assert_eq!(statuses[0], &RepoActionStatus::Done);
+        assert_eq!(statuses[1], &RepoActionStatus::Unchanged);
+        assert!(matches!(statuses[2], RepoActionStatus::Failed(e) if e.contains("forbidden")));
+    }
+
+    #[tokio::test]
+    async fn test_delete_dry_run_changes_nothing() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_delete_repo().never();
+        mock.expect_set_repo_archived().never();
+
+        let use_case = RepoUseCase::new(Arc::new(mock));
+        let results = use_case
+            .apply_action(&[named("api", false), named("old", true)], RepoAction::Delete, true)
+            .await;
+        assert!(results.iter().all(|r| r.status == RepoActionStatus::Pending));
+        assert_eq!(results[1].repo, "acme/old");
+    }
+
+    #[tokio::test]
+    async fn test_unarchive_and_delete() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_set_repo_archived()
+            .with(eq("acme"), eq("old"), eq(false))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock.expect_delete_repo()
+            .with(eq("acme"), eq("old"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let use_case = RepoUseCase::new(Arc::new(mock));
+        let repos = vec![named("old", true)];
+        assert_eq!(use_case.apply_action(&repos, RepoAction::Unarchive, false).await[0].status, RepoActionStatus::Done);
+        assert_eq!(use_case.apply_action(&repos, RepoAction::Delete, false).await[0].status, RepoActionStatus::Done);
+    }
+
+    #[tokio::test]
+    async fn test_view_combines_sections() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo()
+            .with(eq("acme"), eq("api"))
+            .returning(|_, name| Ok(Repository { name: name.into(), open_issues_count: 12, ..Default::default() }));
+        mock.expect_get_repo_languages()
+            .returning(|_, _| Ok(vec![("Shell".into(), 100), ("Rust".into(), 300)]));
+        mock.expect_count_open_pull_requests().returning(|_, _| Ok(5));
+        mock.expect_get_latest_release().returning(|_, _| Ok(None));
+        mock.expect_get_last_workflow_run()
+            .returning(|_, _| Err(GhTuiError::Auth("missing actions:read scope".into())));
+        mock.expect_get_readme().returning(|_, _| Ok(Some("# API".into())));
+
+        let use_case = RepoUseCase::new(Arc::new(mock));
+        let details = use_case.view(&"acme/api".parse().unwrap()).await.unwrap();
+        assert_eq!(details.repo.name, "api");
+        assert_eq!(details.open_issues, 7);
+        assert_eq!(details.open_pull_requests, Some(5));
+        assert_eq!(details.languages[0].name, "Rust");
+        assert!(details.last_workflow_run.is_none());
+        assert_eq!(details.readme.as_deref(), Some("# API"));
+    }
+
+    #[tokio::test]
+    async fn test_view_keeps_pull_requests_in_issues_when_they_cannot_be_counted() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo()
+            .returning(|_, name| Ok(Repository { name: name.into(), open_issues_count: 12, ..Default::default() }));
+        mock.expect_get_repo_languages().returning(|_, _| Ok(vec![]));
+        mock.expect_count_open_pull_requests()
+            .returning(|_, _| Err(GhTuiError::Unknown("secondary rate limit".into())));
+        mock.expect_get_latest_release().returning(|_, _| Ok(None));
+        mock.expect_get_last_workflow_run().returning(|_, _| Ok(None));
+        mock.expect_get_readme().returning(|_, _| Ok(None));
+
+        let use_case = RepoUseCase::new(Arc::new(mock));
+        let details = use_case.view(&"acme/api".parse().unwrap()).await.unwrap();
+        assert_eq!(details.open_issues, 12);
+        assert_eq!(details.open_pull_requests, None);
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_before_calling_github() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_create_repo().never();
+        let use_case = RepoUseCase::new(Arc::new(mock));
+
+        let err = use_case
+            .create(&NewRepository { name: "my repo".into(), ..Default::default() })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("' ' is not allowed"), "{}", err);
+
+        let err = use_case
+            .create(&NewRepository {
+                name: "svc".into(),
+                template: Some("acme/tpl".parse().unwrap()),
+                license_template: Some("mit".into()),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GhTuiError::InvalidInput(_)));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_fork() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_create_repo()
+            .withf(|r| r.name == "svc" && r.org.as_deref() == Some("acme") && r.private)
+            .times(1)
+            .returning(|r| Ok(named(&r.name, false)));
+        mock.expect_fork_repo()
+            .with(eq("rust-lang"), eq("log"), eq(Some("acme".to_string())))
+            .times(1)
+            .returning(|_, name, _| Ok(named(name, false)));
+        let use_case = RepoUseCase::new(Arc::new(mock));
+
+        let new_repo = NewRepository {
+            name: "svc".into(),
+            org: Some("acme".into()),
+            private: true,
+            ..Default::default()
+        };
+        assert_eq!(use_case.create(&new_repo).await.unwrap().full_name, "acme/svc");
+        let fork = use_case.fork(&"rust-lang/log".parse().unwrap(), Some("acme".into())).await.unwrap();
+        assert_eq!(fork.full_name, "acme/log");
+    }
+
+    #[tokio::test]
+    async fn test_plan_and_apply_settings() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo_settings().returning(|_, name| match name {
+            "api" => Ok(RepoSettings {
+                has_wiki: Some(true),
+                delete_branch_on_merge: Some(false),
+                ..Default::default()
+            }),
+            "web" => Ok(RepoSettings {
+                has_wiki: Some(false),
+                delete_branch_on_merge: Some(true),
+                ..Default::default()
+            }),
+            _ => Err(GhTuiError::NotFound(format!("repository 'acme/{}'", name))),
+        });
+        mock.expect_update_repo_settings()
+            .withf(|_, name, update| {
+                name == "api" && *update == RepoSettings { has_wiki: Some(false), delete_branch_on_merge: Some(true), ..Default::default() }
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let use_case = RepoUseCase::new(Arc::new(mock));
+
+        let desired = RepoSettings { has_wiki: Some(false), delete_branch_on_merge: Some(true), ..Default::default() };
+        let repos = vec![named("api", false), named("web", false), named("gone", false)];
+        let mut plans = use_case.plan_settings(&repos, &desired).await.unwrap();
+        assert_eq!(plans[0].changes.len(), 2);
+        assert_eq!(plans[1].status, RepoActionStatus::Unchanged);
+        assert!(matches!(plans[2].status, RepoActionStatus::Failed(_)));
+
+        use_case.apply_settings(&mut plans).await;
+        assert_eq!(plans[0].status, RepoActionStatus::Done);
+        assert_eq!(plans[1].status, RepoActionStatus::Unchanged);
+    }
+
+    #[tokio::test]
+    async fn test_plan_settings_rejects_invalid_settings() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo_settings().never();
+        let use_case = RepoUseCase::new(Arc::new(mock));
+
+        let desired = RepoSettings { topics: Some(vec!["Not Valid".into()]), ..Default::default() };
+        let err = use_case.plan_settings(&[named("api", false)], &desired).await.unwrap_err();
+        assert!(matches!(err, GhTuiError::InvalidInput(_)));
+    }
+
+    #[tokio::test]
+    async fn test_view_unknown_repo() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo()
+            .returning(|o, r| Err(GhTuiError::NotFound(format!("repository '{}/{}'", o, r))));
+        mock.expect_get_readme().never();
+
+        let use_case = RepoUseCase::new(Arc::new(mock));
+        let err = use_case.view(&"acme/nope".parse().unwrap()).await.unwrap_err();
+        assert!(matches!(err, GhTuiError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_list_repos_unknown_owner() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_list_repos_for()
+            .with(eq("acme-typo"), eq(OwnerRepoType::Sources))
+            .times(1)
+            .returning(|owner, _| Err(GhTuiError::NotFound(format!("no user or organization named '{}'", owner))));
+
+        let use_case = RepoUseCase::new(Arc::new(mock));
+        let err = use_case
+            .list_repos(Some("acme-typo".into()), OwnerRepoType::Sources, &RepoQuery::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GhTuiError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_list_repos_sorting_and_limit() {
+        let use_case = use_case_with(vec![repo("web", 5, 1), repo("api", 40, 3), repo("docs", 12, 2)]);
+        let query = RepoQuery {
+            sort: Some(RepoSort::Stars),
+            descending: true,
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let repos = use_case.list_repos(Some("acme".into()), OwnerRepoType::All, &query).await.unwrap();
+        let names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "docs"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_repos_rich_filters() {
+        let mut fork = repo("fork", 50, 1);
+        fork.fork = true;
+        let mut rust = repo("engine", 20, 10);
+        rust.language = Some("Rust".into());
+        let mut archived = repo("legacy", 90, 400);
+        archived.archived = true;
+        let use_case = use_case_with(vec![fork, rust, repo("stale", 30, 200), archived]);
+
+        let query = RepoQuery {
+            fork: Some(false),
+            min_stars: Some(10),
+            pushed_after: Some(Utc::now() - Duration::days(30)),
+            ..Default::default()
+        };
+        let repos = use_case.list_repos(Some("acme".into()), OwnerRepoType::All, &query).await.unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].name, "engine");
+    }
+
+    #[tokio::test]
+    async fn test_list_repos_pushed_before_and_archived() {
+        let mut archived = repo("legacy", 90, 400);
+        archived.archived = true;
+        let use_case = use_case_with(vec![repo("api", 1, 1), repo("stale", 30, 200), archived]);
+
+        let query = RepoQuery {
+            include_archived: true,
+            pushed_before: Some(Utc::now() - Duration::days(180)),
+            sort: Some(RepoSort::Pushed),
+            ..Default::default()
+        };
+        let repos = use_case.list_repos(Some("acme".into()), OwnerRepoType::All, &query).await.unwrap();
+        let names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["legacy", "stale"]);
+    }
 }
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+use crate::domain::error::Result;
+use crate::domain::github::GithubRepository;
+use crate::domain::repo::{RepoRef, Repository};
+use crate::domain::retention::{validate_days, RetentionChange, RetentionChangeStatus, RetentionPolicy};
+
+pub struct RetentionUseCase {
+    repo: Arc<dyn GithubRepository>,
+}
+
+impl RetentionUseCase {
+    pub fn new(repo: Arc<dyn GithubRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn get_repo(&self, repo: &RepoRef) -> Result<RetentionPolicy> {
+        self.repo.get_repo_retention(&repo.owner, &repo.name).await
+    }
+
+    /// Set the retention of a repository and return the previous policy.
+    pub async fn set_repo(&self, repo: &RepoRef, days: u32) -> Result<RetentionPolicy> {
+        let current = self.get_repo(repo).await?;
+        current.validate(days)?;
+        self.repo.set_repo_retention(&repo.owner, &repo.name, days).await?;
+        Ok(current)
+    }
+
+    pub async fn get_org(&self, org: &str) -> Result<RetentionPolicy> {
+        self.repo.get_org_retention(org).await
+    }
+
+    /// Set the default retention of an organization and return the previous policy.
+    pub async fn set_org(&self, org: &str, days: u32) -> Result<RetentionPolicy> {
+        let current = self.get_org(org).await?;
+        current.validate(days)?;
+        self.repo.set_org_retention(org, days).await?;
+        Ok(current)
+    }
+
+    /// Retention of each repository; `None` when it could not be read.
+    pub async fn get_for_repos(&self, repos: &[Repository]) -> Vec<(String, Option<RetentionPolicy>)> {
+        let mut policies = Vec::new();
+        for r in repos {
+            let policy = self.repo.get_repo_retention(&r.owner, &r.name).await.ok();
+            policies.push((r.full_name.clone(), policy));
+        }
+        policies
+    }
+
+    /// Apply `days` to every repository. Failures are reported per repository
+    /// instead of aborting the whole run.
+    pub async fn set_for_repos(&self, repos: &[Repository], days: u32, dry_run: bool) -> Result<Vec<RetentionChange>> {
+        validate_days(days)?;
+
+        let mut changes = Vec::new();
+        for r in repos {
+            let mut change = RetentionChange {
+                repo: r.full_name.clone(),
+                previous_days: None,
+                days,
+                status: RetentionChangeStatus::Pending,
+            };
+
+            change.status = match self.repo.get_repo_retention(&r.owner, &r.name).await {
+                Err(e) => RetentionChangeStatus::Failed(e.to_string()),
+                Ok(current) => {
+                    change.previous_days = Some(current.days);
+                    if current.days == days {
+                        RetentionChangeStatus::Unchanged
+                    } else if let Err(e) = current.validate(days) {
+                        RetentionChangeStatus::Failed(e.to_string())
+                    } else if dry_run {
+                        RetentionChangeStatus::Pending
+                    } else {
+                        match self.repo.set_repo_retention(&r.owner, &r.name, days).await {
+                            Ok(()) => RetentionChangeStatus::Updated,
+                            Err(e) => RetentionChangeStatus::Failed(e.to_string()),
+                        }
+                    }
+                }
+            };
+            changes.push(change);
+        }
+
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::GhTuiError;
+    use crate::domain::github::MockGithubRepository;
+    use mockall::predicate::eq;
+
+    fn repo(name: &str) -> Repository {
+        Repository {
+            name: name.into(),
+            owner: "acme".into(),
+            full_name: format!("acme/{}", name),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_repo_rejects_days_above_org_maximum() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo_retention()
+            .returning(|_, _| Ok(RetentionPolicy { days: 30, maximum_allowed_days: Some(30) }));
+        mock.expect_set_repo_retention().never();
+
+        let use_case = RetentionUseCase::new(Arc::new(mock));
+        let target: RepoRef = "acme/api".parse().unwrap();
+        let result = use_case.set_repo(&target, 60).await;
+        assert!(matches!(result, Err(GhTuiError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_for_repos_reports_each_repo() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo_retention()
+            .with(eq("acme"), eq("api"))
+            .returning(|_, _| Ok(RetentionPolicy { days: 90, maximum_allowed_days: None }));
+        mock.expect_get_repo_retention()
+            .with(eq("acme"), eq("web"))
+            .returning(|_, _| Ok(RetentionPolicy { days: 14, maximum_allowed_days: None }));
+        mock.expect_get_repo_retention()
+            .with(eq("acme"), eq("legacy"))
+            .returning(|_, _| Err(GhTuiError::Unknown("forbidden".into())));
+        mock.expect_set_repo_retention()
+            .with(eq("acme"), eq("api"), eq(14))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let use_case = RetentionUseCase::new(Arc::new(mock));
+        let changes = use_case
+            .set_for_repos(&[repo("api"), repo("web"), repo("legacy")], 14, false)
+            .await
+            .unwrap();
+
+        assert_eq!(changes[0].status, RetentionChangeStatus::Updated);
+        assert_eq!(changes[0].previous_days, Some(90));
+        assert_eq!(changes[1].status, RetentionChangeStatus::Unchanged);
+        assert!(matches!(changes[2].status, RetentionChangeStatus::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_set_for_repos_dry_run_does_not_write() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_get_repo_retention()
+            .returning(|_, _| Ok(RetentionPolicy { days: 90, maximum_allowed_days: None }));
+        mock.expect_set_repo_retention().never();
+
+        let use_case = RetentionUseCase::new(Arc::new(mock));
+        let changes = use_case.set_for_repos(&[repo("api")], 30, true).await.unwrap();
+        assert_eq!(changes[0].status, RetentionChangeStatus::Pending);
+    }
+}
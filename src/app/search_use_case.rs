@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::github::GithubRepository;
+use crate::domain::search::{SearchHits, SearchKind, SearchQuery, SearchResults, SEARCH_RESULT_CAP};
+
+pub struct SearchUseCase {
+    github: Arc<dyn GithubRepository>,
+}
+
+impl SearchUseCase {
+    pub fn new(github: Arc<dyn GithubRepository>) -> Self {
+        Self { github }
+    }
+
+    /// Run the search, fetching at most `SEARCH_RESULT_CAP` results.
+    pub async fn search(&self, query: &SearchQuery) -> Result<SearchResults> {
+        query.validate().map_err(GhTuiError::InvalidInput)?;
+        let api_query = query.api_query();
+        let limit = query.limit.min(SEARCH_RESULT_CAP);
+
+        let (total_count, incomplete_results, hits) = match query.kind {
+            SearchKind::Issues | SearchKind::PullRequests => {
+                let page = self.github.search_issues(&api_query, limit).await?;
+                (page.total_count, page.incomplete_results, SearchHits::Issues(page.items))
+            }
+            SearchKind::Repositories => {
+                let page = self.github.search_repos(&api_query, limit).await?;
+                (page.total_count, page.incomplete_results, SearchHits::Repositories(page.items))
+            }
+            SearchKind::Code => {
+                let page = self.github.search_code(&api_query, limit).await?;
+                (page.total_count, page.incomplete_results, SearchHits::Code(page.items))
+            }
+        };
+        Ok(SearchResults { total_count, incomplete_results, hits, terms: query.terms() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::github::MockGithubRepository;
+    use crate::domain::search::{CodeHit, SearchPage};
+    use mockall::predicate::eq;
+
+    #[tokio::test]
+    async fn test_pull_request_search_is_typed_and_capped() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_search_issues()
+            .with(eq("author:@me state:open is:pr"), eq(SEARCH_RESULT_CAP))
+            .times(1)
+            .returning(|_, _| Ok(SearchPage { total_count: 2, incomplete_results: false, items: Vec::new() }));
+        let use_case = SearchUseCase::new(Arc::new(mock));
+
+        let query = SearchQuery { kind: SearchKind::PullRequests, query: "author:@me state:open".into(), limit: 5000 };
+        let results = use_case.search(&query).await.unwrap();
+        assert_eq!(results.total_count, 2);
+        assert!(results.terms.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_code_search_keeps_terms() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_search_code()
+            .with(eq("unwrap org:acme"), eq(30))
+            .returning(|_, _| {
+                Ok(SearchPage {
+                    total_count: 1,
+                    incomplete_results: true,
+                    items: vec![CodeHit {
+                        repository: "acme/api".into(),
+                        path: "src/main.rs".into(),
+                        html_url: String::new(),
+                        fragment: Some("x.unwrap()".into()),
+                    }],
+                })
+            });
+        let use_case = SearchUseCase::new(Arc::new(mock));
+
+        let query = SearchQuery { kind: SearchKind::Code, query: "unwrap org:acme".into(), limit: 30 };
+        let results = use_case.search(&query).await.unwrap();
+        assert!(results.incomplete_results);
+        assert_eq!(results.hits.len(), 1);
+        assert_eq!(results.terms, vec!["unwrap"]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_query_makes_no_call() {
+        let mut mock = MockGithubRepository::new();
+        mock.expect_search_code().never();
+        let use_case = SearchUseCase::new(Arc::new(mock));
+
+        let query = SearchQuery { kind: SearchKind::Code, query: "org:acme".into(), limit: 30 };
+        assert!(matches!(use_case.search(&query).await, Err(GhTuiError::InvalidInput(_))));
+    }
+}
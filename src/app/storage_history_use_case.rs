@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use chrono::Utc;
+use crate::domain::error::Result;
+use crate::domain::forecast::StorageForecast;
+use crate::domain::history::{usage_series, StorageHistoryRepository, StorageSnapshot, UsagePoint};
+use crate::domain::storage::StorageUsageReport;
+
+pub struct StorageHistoryUseCase {
+    history: Arc<dyn StorageHistoryRepository>,
+}
+
+impl StorageHistoryUseCase {
+    pub fn new(history: Arc<dyn StorageHistoryRepository>) -> Self {
+        Self { history }
+    }
+
+    pub fn record(&self, report: &StorageUsageReport) -> Result<StorageSnapshot> {
+        let snapshot = StorageSnapshot::from_report(report, Utc::now());
+        self.history.append(&snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Forecast of each account's usage against its own quota, based on the
+    /// recorded snapshots when there are enough of them, otherwise on the
+    /// items' creation dates.
+    pub fn forecast(&self, report: &StorageUsageReport) -> Result<Vec<StorageForecast>> {
+        let snapshots = self.history.load()?;
+        let now = Utc::now();
+        Ok(report
+            .accounts
+            .iter()
+            .map(|a| {
+                StorageForecast::new(
+                    &a.account,
+                    a.used_bytes(),
+                    a.quota_bytes(),
+                    &report.account_items(&a.account),
+                    &snapshots,
+                    now,
+                )
+            })
+            .collect())
+    }
+
+    /// The last `limit` points of the usage series (total, or for one `owner/repo`)
+    pub fn series(&self, repo: Option<&str>, limit: Option<usize>) -> Result<Vec<UsagePoint>> {
+        let mut points = usage_series(&self.history.load()?, repo);
+        if let Some(limit) = limit {
+            let skip = points.len().saturating_sub(limit);
+            points.drain(..skip);
+        }
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::billing::{AccountStorage, AccountType};
+    use crate::domain::forecast::ForecastBasis;
+    use crate::domain::history::{AccountUsage, MockStorageHistoryRepository};
+    use chrono::Duration;
+
+    fn snapshot(total_used: u64, days_ago: i64) -> StorageSnapshot {
+        StorageSnapshot {
+            taken_at: Utc::now() - Duration::days(days_ago),
+            total_used,
+            accounts: vec![AccountUsage { account: "octocat".into(), used: total_used, quota: Some(1000) }],
+            repos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_appends_snapshot() {
+        let mut mock = MockStorageHistoryRepository::new();
+        mock.expect_append()
+            .withf(|s| s.total_used == 0 && s.repos.is_empty())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let report = StorageUsageReport::new(vec![AccountStorage::new("octocat", AccountType::User)], vec![]);
+        let use_case = StorageHistoryUseCase::new(Arc::new(mock));
+        use_case.record(&report).unwrap();
+    }
+
+    #[test]
+    fn test_forecast_uses_recorded_snapshots() {
+        let mut mock = MockStorageHistoryRepository::new();
+        mock.expect_load()
+            .times(1)
+            .returning(|| Ok(vec![snapshot(0, 4), snapshot(400, 0)]));
+
+        let mut account = AccountStorage::new("octocat", AccountType::User);
+        account.quota_override = Some(1000);
+        account.scanned_bytes = 400;
+        let report = StorageUsageReport { accounts: vec![account], items: vec![] };
+
+        let use_case = StorageHistoryUseCase::new(Arc::new(mock));
+        let forecasts = use_case.forecast(&report).unwrap();
+        assert_eq!(forecasts.len(), 1);
+        assert_eq!(forecasts[0].basis, ForecastBasis::Snapshots);
+        assert!((forecasts[0].days_until_full.unwrap() - 6.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_forecast_compares_each_account_with_its_own_quota() {
+        let mut mock = MockStorageHistoryRepository::new();
+        mock.expect_load().times(1).returning(|| {
+            let mut first = snapshot(550, 4);
+            first.accounts.push(AccountUsage { account: "acme".into(), used: 0, quota: Some(100_000) });
+            let mut last = snapshot(950, 0);
+            last.accounts.push(AccountUsage { account: "acme".into(), used: 4000, quota: Some(100_000) });
+            Ok(vec![first, last])
+        });
+
+        let mut user = AccountStorage::new("octocat", AccountType::User);
+        user.quota_override = Some(1000);
+        user.scanned_bytes = 950;
+        let mut org = AccountStorage::new("acme", AccountType::Organization);
+        org.quota_override = Some(100_000);
+        org.scanned_bytes = 4000;
+        let report = StorageUsageReport { accounts: vec![user, org], items: vec![] };
+
+        let use_case = StorageHistoryUseCase::new(Arc::new(mock));
+        let forecasts = use_case.forecast(&report).unwrap();
+        // Summed, 4950 of 101000 bytes would hide that octocat is nearly full
+        assert_eq!(forecasts[0].account, "octocat");
+        assert!((forecasts[0].days_until_full.unwrap() - 0.5).abs() < 1e-3);
+        assert_eq!(forecasts[1].account, "acme");
+        assert!((forecasts[1].days_until_full.unwrap() - 96.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_forecast_without_quota_still_reports_expiry() {
+        let mut mock = MockStorageHistoryRepository::new();
+        mock.expect_load().times(1).returning(|| Ok(vec![]));
+
+        let org = AccountStorage::new("acme", AccountType::Organization);
+        let report = StorageUsageReport { accounts: vec![org], items: vec![] };
+
+        let use_case = StorageHistoryUseCase::new(Arc::new(mock));
+        let forecasts = use_case.forecast(&report).unwrap();
+        assert_eq!(forecasts[0].quota, None);
+        assert_eq!(forecasts[0].days_until_full, None);
+        assert_eq!(forecasts[0].expiring.len(), 3);
+    }
+
+    #[test]
+    fn test_series_keeps_latest_points() {
+        let mut mock = MockStorageHistoryRepository::new();
+        mock.expect_load()
+            .times(1)
+            .returning(|| Ok(vec![snapshot(100, 3), snapshot(150, 2), snapshot(120, 1)]));
+
+        let use_case = StorageHistoryUseCase::new(Arc::new(mock));
+        let points = use_case.series(None, Some(2)).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].used, 150);
+        assert_eq!(points[0].delta, 50);
+        assert_eq!(points[1].delta, -30);
+    }
+}
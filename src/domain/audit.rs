@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::repo::Repository;
+
+/// A compliance check run by `ghr audit repos`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditRule {
+    Description,
+    License,
+    BranchProtection,
+    SecretScanning,
+    DefaultBranchName,
+    DeleteBranchOnMerge,
+}
+
+impl AuditRule {
+    pub const ALL: [AuditRule; 6] = [
+        AuditRule::Description,
+        AuditRule::License,
+        AuditRule::BranchProtection,
+        AuditRule::SecretScanning,
+        AuditRule::DefaultBranchName,
+        AuditRule::DeleteBranchOnMerge,
+    ];
+
+    /// Short column header for the audit matrix
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuditRule::Description => "Description",
+            AuditRule::License => "License",
+            AuditRule::BranchProtection => "Protected",
+            AuditRule::SecretScanning => "Secrets",
+            AuditRule::DefaultBranchName => "Branch name",
+            AuditRule::DeleteBranchOnMerge => "Auto-delete",
+        }
+    }
+}
+
+/// Which rules to check, read from a rules file. Every rule is on by default:
+///
+/// ```toml
+/// secret_scanning = false
+/// stale_branch_names = ["master", "develop"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditRules {
+    pub description: bool,
+    pub license: bool,
+    /// The default branch must be protected (branch protection or a ruleset)
+    pub branch_protection: bool,
+    pub secret_scanning: bool,
+    pub delete_branch_on_merge: bool,
+    /// Default branch names that fail the audit; empty disables the check
+    pub stale_branch_names: Vec<String>,
+}
+
+impl Default for AuditRules {
+    fn default() -> Self {
+        Self {
+            description: true,
+            license: true,
+            branch_protection: true,
+            secret_scanning: true,
+            delete_branch_on_merge: true,
+            stale_branch_names: vec!["master".into()],
+        }
+    }
+}
+
+/// What GitHub told us about a repository. `None` when the token cannot see
+/// it (most security settings are only visible to admins).
+#[derive(Debug, Clone, Default)]
+pub struct RepoFacts {
+    pub repo: Repository,
+    pub default_branch_protected: Option<bool>,
+    pub secret_scanning: Option<bool>,
+    pub delete_branch_on_merge: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckOutcome {
+    Pass,
+    Fail,
+    /// Not visible with the current token
+    Unknown,
+}
+
+impl From<Option<bool>> for CheckOutcome {
+    fn from(value: Option<bool>) -> Self {
+        match value {
+            Some(true) => CheckOutcome::Pass,
+            Some(false) => CheckOutcome::Fail,
+            None => CheckOutcome::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleCheck {
+    pub rule: AuditRule,
+    pub outcome: CheckOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RepoAudit {
+    /// `owner/repo`
+    pub repo: String,
+    pub checks: Vec<RuleCheck>,
+}
+
+impl RepoAudit {
+    pub fn count(&self, outcome: CheckOutcome) -> usize {
+        self.checks.iter().filter(|c| c.outcome == outcome).count()
+    }
+
+    pub fn outcome(&self, rule: AuditRule) -> Option<CheckOutcome> {
+        self.checks.iter().find(|c| c.rule == rule).map(|c| c.outcome)
+    }
+}
+
+impl AuditRules {
+    pub fn enabled(&self) -> Vec<AuditRule> {
+        AuditRule::ALL.into_iter().filter(|r| self.is_enabled(*r)).collect()
+    }
+
+    pub fn is_enabled(&self, rule: AuditRule) -> bool {
+        match rule {
+            AuditRule::Description => self.description,
+            AuditRule::License => self.license,
+            AuditRule::BranchProtection => self.branch_protection,
+            AuditRule::SecretScanning => self.secret_scanning,
+            AuditRule::DefaultBranchName => !self.stale_branch_names.is_empty(),
+            AuditRule::DeleteBranchOnMerge => self.delete_branch_on_merge,
+        }
+    }
+
+    /// Run every enabled rule against one repository.
+    pub fn evaluate(&self, facts: &RepoFacts) -> RepoAudit {
+        let repo = &facts.repo;
+        let checks = self
+            .enabled()
+            .into_iter()
+            .map(|rule| {
+                let outcome = match rule {
+                    AuditRule::Description => {
+                        Some(repo.description.as_deref().is_some_and(|d| !d.trim().is_empty())).into()
+                    }
+                    AuditRule::License => Some(repo.license.is_some()).into(),
+                    AuditRule::BranchProtection => facts.default_branch_protected.into(),
+                    AuditRule::SecretScanning => facts.secret_scanning.into(),
+                    AuditRule::DefaultBranchName => Some(
+                        !self
+                            .stale_branch_names
+                            .iter()
+                            .any(|stale| stale.eq_ignore_ascii_case(&repo.default_branch)),
+                    )
+                    .into(),
+                    AuditRule::DeleteBranchOnMerge => facts.delete_branch_on_merge.into(),
+                };
+                RuleCheck { rule, outcome }
+            })
+            .collect();
+        RepoAudit {
+            repo: repo.full_name.clone(),
+            checks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditReport {
+    pub owner: String,
+    pub rules: Vec<AuditRule>,
+    pub repos: Vec<RepoAudit>,
+    /// Percentage of passed checks; those that could not be read count as not passed
+    pub score: f64,
+    pub violations: usize,
+    /// Checks that could not be read
+    pub unknown: usize,
+}
+
+impl AuditReport {
+    pub fn new(owner: String, rules: Vec<AuditRule>, repos: Vec<RepoAudit>) -> Self {
+        let passed: usize = repos.iter().map(|r| r.count(CheckOutcome::Pass)).sum();
+        let violations: usize = repos.iter().map(|r| r.count(CheckOutcome::Fail)).sum();
+        let unknown: usize = repos.iter().map(|r| r.count(CheckOutcome::Unknown)).sum();
+        let checks = passed + violations + unknown;
+        let score = if checks == 0 { 100.0 } else { passed as f64 * 100.0 / checks as f64 };
+        Self { owner, rules, repos, score, violations, unknown }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts() -> RepoFacts {
+        RepoFacts {
+            repo: Repository {
+                full_name: "acme/api".into(),
+                description: Some("API".into()),
+                license: Some("MIT".into()),
+                default_branch: "main".into(),
+                ..Default::default()
+            },
+            default_branch_protected: Some(true),
+            secret_scanning: None,
+            delete_branch_on_merge: Some(false),
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let audit = AuditRules::default().evaluate(&facts());
+        assert_eq!(audit.repo, "acme/api");
+        assert_eq!(audit.outcome(AuditRule::Description), Some(CheckOutcome::Pass));
+        assert_eq!(audit.outcome(AuditRule::SecretScanning), Some(CheckOutcome::Unknown));
+        assert_eq!(audit.outcome(AuditRule::DefaultBranchName), Some(CheckOutcome::Pass));
+        assert_eq!(audit.outcome(AuditRule::DeleteBranchOnMerge), Some(CheckOutcome::Fail));
+        assert_eq!((audit.count(CheckOutcome::Pass), audit.count(CheckOutcome::Fail)), (4, 1));
+
+        let mut stale = facts();
+        stale.repo.default_branch = "Master".into();
+        stale.repo.description = Some("  ".into());
+        let audit = AuditRules::default().evaluate(&stale);
+        assert_eq!(audit.outcome(AuditRule::DefaultBranchName), Some(CheckOutcome::Fail));
+        assert_eq!(audit.outcome(AuditRule::Description), Some(CheckOutcome::Fail));
+    }
+
+    #[test]
+    fn test_rules_file() {
+        let rules: AuditRules = toml::from_str("secret_scanning = false\nstale_branch_names = []\n").unwrap();
+        assert!(rules.license);
+        assert_eq!(rules.enabled().len(), 4);
+        assert_eq!(rules.evaluate(&facts()).outcome(AuditRule::SecretScanning), None);
+
+        assert!(toml::from_str::<AuditRules>("licence = true").is_err());
+    }
+
+    #[test]
+    fn test_report_score() {
+        let rules = AuditRules::default();
+        let report = AuditReport::new("acme".into(), rules.enabled(), vec![rules.evaluate(&facts())]);
+        // 4 passed, 1 failed and secret scanning unknown
+        assert_eq!(report.violations, 1);
+        assert_eq!(report.unknown, 1);
+        assert!((report.score - 66.67).abs() < 0.01);
+
+        let empty = AuditReport::new("acme".into(), rules.enabled(), Vec::new());
+        assert_eq!(empty.score, 100.0);
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AccountType {
+    User,
+    Organization,
+}
+
+/// Response of `GET /{users|orgs}/{account}/settings/billing/shared-storage`.
+/// Storage figures are expressed in GB, the paid amount in USD. GitHub reports
+/// the storage used and paid for, but not the storage included with the plan.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharedStorageBilling {
+    #[serde(default)]
+    pub days_left_in_billing_cycle: u32,
+    #[serde(default)]
+    pub estimated_paid_storage_for_month: f64,
+    #[serde(default)]
+    pub estimated_storage_for_month: f64,
+}
+
+impl SharedStorageBilling {
+    pub fn used_bytes(&self) -> u64 {
+        (self.estimated_storage_for_month * BYTES_PER_GB).round() as u64
+    }
+}
+
+/// Response of `GET /{users|orgs}/{account}/settings/billing/actions`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ActionsBilling {
+    #[serde(default)]
+    pub total_minutes_used: f64,
+    #[serde(default)]
+    pub total_paid_minutes_used: f64,
+    #[serde(default)]
+    pub included_minutes: f64,
+    #[serde(default)]
+    pub minutes_used_breakdown: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum QuotaSource {
+    /// Set manually in the ghr config file
+    Config,
+    /// The billing API does not report it and no override is configured
+    Unknown,
+}
+
+/// Storage billing for a single account (the user or one of their organizations).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountStorage {
+    pub account: String,
+    pub account_type: AccountType,
+    pub shared_storage: Option<SharedStorageBilling>,
+    pub actions: Option<ActionsBilling>,
+    /// Bytes found by scanning the account's artifacts and caches
+    pub scanned_bytes: u64,
+    pub quota_override: Option<u64>,
+}
+
+impl AccountStorage {
+    pub fn new(account: impl Into<String>, account_type: AccountType) -> Self {
+        Self {
+            account: account.into(),
+            account_type,
+            shared_storage: None,
+            actions: None,
+            scanned_bytes: 0,
+            quota_override: None,
+        }
+    }
+
+    /// Billing usage may include packages that are not scanned, while the scan is
+    /// more up to date than the billing estimate, so the larger of both is used.
+    pub fn used_bytes(&self) -> u64 {
+        let billed = self.shared_storage.as_ref().map(|s| s.used_bytes()).unwrap_or(0);
+        std::cmp::max(billed, self.scanned_bytes)
+    }
+
+    pub fn paid_amount(&self) -> f64 {
+        self.shared_storage
+            .as_ref()
+            .map(|s| s.estimated_paid_storage_for_month)
+            .unwrap_or(0.0)
+    }
+
+    /// The storage quota, only known when configured: the billing API does not
+    /// report the storage included with the plan.
+    pub fn quota_bytes(&self) -> Option<u64> {
+        self.quota_override
+    }
+
+    pub fn quota_source(&self) -> QuotaSource {
+        if self.quota_override.is_some() {
+            QuotaSource::Config
+        } else {
+            QuotaSource::Unknown
+        }
+    }
+
+    pub fn usage_percentage(&self) -> Option<f64> {
+        match self.quota_bytes() {
+            Some(quota) if quota > 0 => Some(self.used_bytes() as f64 / quota as f64 * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Quotas configured by the user, in bytes. The billing API reports usage
+/// (with the `user` or `admin:org` scope) but not the included storage.
+#[derive(Debug, Clone, Default)]
+pub struct QuotaOverrides {
+    pub default: Option<u64>,
+    pub accounts: HashMap<String, u64>,
+}
+
+impl QuotaOverrides {
+    pub fn for_account(&self, account: &str) -> Option<u64> {
+        self.accounts
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(account))
+            .map(|(_, quota)| *quota)
+            .or(self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES_PER_MB: u64 = 1024 * 1024;
+
+    const USER_SHARED_STORAGE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/billing/user_shared_storage.json"));
+    const USER_ACTIONS: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/billing/user_actions.json"));
+    const ORG_SHARED_STORAGE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/billing/org_shared_storage.json"));
+    const ORG_ACTIONS: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/billing/org_actions.json"));
+
+    #[test]
+    fn test_parse_shared_storage_in_gigabytes() {
+        let storage: SharedStorageBilling = serde_json::from_str(USER_SHARED_STORAGE).unwrap();
+        assert_eq!(storage.days_left_in_billing_cycle, 20);
+        assert_eq!(storage.estimated_paid_storage_for_month, 0.0);
+        // 0.75 GB
+        assert_eq!(storage.used_bytes(), 805_306_368);
+    }
+
+    #[test]
+    fn test_parse_actions_billing() {
+        let actions: ActionsBilling = serde_json::from_str(USER_ACTIONS).unwrap();
+        assert_eq!(actions.total_minutes_used, 305.0);
+        assert_eq!(actions.included_minutes, 3000.0);
+        assert_eq!(actions.total_paid_minutes_used, 0.0);
+        assert_eq!(actions.minutes_used_breakdown.get("UBUNTU"), Some(&205.0));
+    }
+
+    #[test]
+    fn test_billing_alone_leaves_quota_unknown() {
+        let mut account = AccountStorage::new("octocat", AccountType::User);
+        account.shared_storage = Some(serde_json::from_str(USER_SHARED_STORAGE).unwrap());
+        account.actions = Some(serde_json::from_str(USER_ACTIONS).unwrap());
+        assert_eq!(account.quota_bytes(), None);
+        assert_eq!(account.quota_source(), QuotaSource::Unknown);
+        assert_eq!(account.usage_percentage(), None);
+    }
+
+    #[test]
+    fn test_account_storage_uses_billing_and_scan() {
+        let mut account = AccountStorage::new("acme", AccountType::Organization);
+        account.shared_storage = Some(serde_json::from_str(ORG_SHARED_STORAGE).unwrap());
+        account.actions = Some(serde_json::from_str(ORG_ACTIONS).unwrap());
+        account.quota_override = Some(50 * 1024 * BYTES_PER_MB);
+
+        assert_eq!(account.used_bytes(), 12 * 1024 * 1024 * 1024);
+        assert_eq!(account.paid_amount(), 1.25);
+        assert_eq!(account.usage_percentage(), Some(24.0));
+
+        account.scanned_bytes = 20 * 1024 * 1024 * 1024;
+        assert_eq!(account.used_bytes(), 20 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_quota_override_takes_precedence() {
+        let mut account = AccountStorage::new("octocat", AccountType::User);
+        assert_eq!(account.quota_source(), QuotaSource::Unknown);
+        assert_eq!(account.usage_percentage(), None);
+
+        let overrides = QuotaOverrides {
+            default: Some(500 * BYTES_PER_MB),
+            accounts: HashMap::from([("OctoCat".to_string(), 4096 * BYTES_PER_MB)]),
+        };
+        account.quota_override = overrides.for_account(&account.account);
+        assert_eq!(account.quota_bytes(), Some(4096 * BYTES_PER_MB));
+        assert_eq!(account.quota_source(), QuotaSource::Config);
+        assert_eq!(overrides.for_account("someone-else"), Some(500 * BYTES_PER_MB));
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PullRequestState {
+    Open,
+    Draft,
+    Closed,
+    Merged,
+}
+
+impl fmt::Display for PullRequestState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PullRequestState::Open => write!(f, "open"),
+            PullRequestState::Draft => write!(f, "draft"),
+            PullRequestState::Closed => write!(f, "closed"),
+            PullRequestState::Merged => write!(f, "merged"),
+        }
+    }
+}
+
+/// The most recent pull request opened from a branch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BranchPullRequest {
+    pub number: u64,
+    pub state: PullRequestState,
+    /// Last commit of the pull request (for a merged one, the commit it was merged at)
+    pub head_sha: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Branch {
+    pub name: String,
+    pub is_default: bool,
+    /// Protected by branch protection or a ruleset
+    pub protected: bool,
+    pub last_commit_sha: Option<String>,
+    pub last_commit_at: Option<DateTime<Utc>>,
+    pub last_commit_author: Option<String>,
+    /// Commits on the branch that the default branch does not have
+    pub ahead: u32,
+    /// Commits on the default branch that the branch does not have
+    pub behind: u32,
+    pub pull_request: Option<BranchPullRequest>,
+}
+
+/// Why a branch can be pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PruneReason {
+    Merged,
+    Stale,
+}
+
+impl fmt::Display for PruneReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PruneReason::Merged => write!(f, "merged"),
+            PruneReason::Stale => write!(f, "stale"),
+        }
+    }
+}
+
+impl Branch {
+    /// Fully contained in the default branch, or its pull request was merged
+    /// at the branch's last commit (squash and rebase merges leave the branch
+    /// ahead). Commits pushed after the merge keep the branch unmerged.
+    pub fn is_merged(&self) -> bool {
+        !self.is_default && (self.ahead == 0 || self.merged_pull_request())
+    }
+
+    fn merged_pull_request(&self) -> bool {
+        self.pull_request.as_ref().is_some_and(|pr| {
+            pr.state == PullRequestState::Merged && pr.head_sha.is_some() && pr.head_sha == self.last_commit_sha
+        })
+    }
+
+    /// No commit for `days` days.
+    pub fn is_stale(&self, days: i64, now: DateTime<Utc>) -> bool {
+        self.last_commit_at.is_some_and(|at| now - at > Duration::days(days))
+    }
+
+    pub fn has_open_pull_request(&self) -> bool {
+        self.pull_request
+            .as_ref()
+            .is_some_and(|pr| matches!(pr.state, PullRequestState::Open | PullRequestState::Draft))
+    }
+
+    /// Why the branch may be deleted, if it may. The default branch, protected
+    /// branches and branches with an open pull request are always kept.
+    pub fn prune_reason(&self, merged: bool, stale_days: Option<i64>, now: DateTime<Utc>) -> Option<PruneReason> {
+        if self.is_default || self.protected || self.has_open_pull_request() {
+            return None;
+        }
+        if merged && self.is_merged() {
+            Some(PruneReason::Merged)
+        } else if stale_days.is_some_and(|days| self.is_stale(days, now)) {
+            Some(PruneReason::Stale)
+        } else {
+            None
+        }
+    }
+}
+
+/// Which branches `ghr branches list` shows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BranchFilter {
+    pub merged: bool,
+    pub stale_days: Option<i64>,
+}
+
+impl BranchFilter {
+    pub fn matches(&self, branch: &Branch, now: DateTime<Utc>) -> bool {
+        (!self.merged || branch.is_merged()) && self.stale_days.is_none_or(|days| branch.is_stale(days, now))
+    }
+}
+
+/// A branch selected by `ghr branches prune`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PruneCandidate {
+    pub branch: Branch,
+    pub reason: PruneReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn branch(name: &str, ahead: u32, days_ago: i64) -> Branch {
+        Branch {
+            name: name.into(),
+            last_commit_sha: Some("abc123".into()),
+            ahead,
+            last_commit_at: Some(Utc::now() - Duration::days(days_ago)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merged_and_stale() {
+        let now = Utc::now();
+        assert!(branch("fix", 0, 1).is_merged());
+        assert!(!branch("feature", 3, 1).is_merged());
+
+        let mut squashed = branch("squashed", 3, 1);
+        let merged_at = |sha: &str| BranchPullRequest { number: 7, state: PullRequestState::Merged, head_sha: Some(sha.into()) };
+        squashed.pull_request = Some(merged_at("abc123"));
+        assert!(squashed.is_merged());
+        // Pushed to again after the merge
+        squashed.pull_request = Some(merged_at("def456"));
+        assert!(!squashed.is_merged());
+
+        assert!(branch("old", 3, 120).is_stale(90, now));
+        assert!(!branch("recent", 3, 10).is_stale(90, now));
+
+        let filter = BranchFilter { merged: true, stale_days: Some(90) };
+        assert!(filter.matches(&branch("old-merged", 0, 100), now));
+        assert!(!filter.matches(&branch("new-merged", 0, 10), now));
+    }
+
+    #[test]
+    fn test_prune_reason_keeps_default_protected_and_open_prs() {
+        let now = Utc::now();
+        assert_eq!(branch("fix", 0, 1).prune_reason(true, None, now), Some(PruneReason::Merged));
+        assert_eq!(branch("old", 2, 200).prune_reason(true, Some(90), now), Some(PruneReason::Stale));
+        assert_eq!(branch("old", 2, 200).prune_reason(true, None, now), None);
+
+        let default = Branch { is_default: true, ..branch("main", 0, 200) };
+        assert_eq!(default.prune_reason(true, Some(90), now), None);
+
+        let protected = Branch { protected: true, ..branch("release", 0, 200) };
+        assert_eq!(protected.prune_reason(true, Some(90), now), None);
+
+        let mut open = branch("wip", 0, 200);
+        open.pull_request = Some(BranchPullRequest { number: 3, state: PullRequestState::Draft, head_sha: None });
+        assert_eq!(open.prune_reason(true, Some(90), now), None);
+    }
+}
@@ -13,6 +13,15 @@ pub enum GhTuiError {
     #[error("Configuration error: {0}")]
     Config(String),
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    #[error("Git error: {0}")]
+    Git(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -33,6 +42,11 @@ pub enum GhTuiError {
 
     #[error("Unknown error: {0}")]
     Unknown(String),
+
+    /// The command already reported its outcome and only sets the exit code
+    /// (e.g. a `--fail-above` threshold or audit violations)
+    #[error("exit code {0}")]
+    Exit(u8),
 }
 
 impl From<dialoguer::Error> for GhTuiError {
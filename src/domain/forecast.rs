@@ -0,0 +1,232 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::history::StorageSnapshot;
+use crate::domain::storage::StorageItem;
+
+/// Windows (in days) for which the naturally expiring storage is reported
+pub const EXPIRY_WINDOWS: [i64; 3] = [7, 30, 90];
+/// Snapshots and artifacts older than this are ignored to reflect the current trend
+const TREND_WINDOW_DAYS: i64 = 30;
+/// Minimum time covered by snapshots before they are trusted over artifact dates
+const MIN_SNAPSHOT_SPAN_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ForecastBasis {
+    /// Net growth measured between recorded usage snapshots
+    Snapshots,
+    /// Gross growth from the artifacts and caches created recently
+    CreationDates,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExpiryWindow {
+    pub days: i64,
+    pub bytes: u64,
+    pub count: usize,
+}
+
+/// Forecast of a single account's usage against its own quota.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StorageForecast {
+    pub account: String,
+    pub basis: ForecastBasis,
+    pub growth_per_day: f64,
+    pub quota: Option<u64>,
+    /// `None` when usage is not growing or the quota is unknown
+    pub days_until_full: Option<f64>,
+    /// Bytes freed by artifact expiry within each of `EXPIRY_WINDOWS`
+    pub expiring: Vec<ExpiryWindow>,
+}
+
+impl StorageForecast {
+    /// `items` are the items owned by `account`, and the snapshots are only
+    /// read for that account's usage.
+    pub fn new(
+        account: &str,
+        used: u64,
+        quota: Option<u64>,
+        items: &[StorageItem],
+        snapshots: &[StorageSnapshot],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let (basis, growth_per_day) = match growth_from_snapshots(snapshots, account, now) {
+            Some(growth) => (ForecastBasis::Snapshots, growth),
+            None => (ForecastBasis::CreationDates, growth_from_creation_dates(items, now)),
+        };
+
+        let days_until_full = match quota {
+            None | Some(0) => None,
+            Some(quota) if used >= quota => Some(0.0),
+            Some(quota) if growth_per_day > 0.0 => Some((quota - used) as f64 / growth_per_day),
+            Some(_) => None,
+        };
+
+        let expiring = EXPIRY_WINDOWS
+            .iter()
+            .map(|&days| {
+                let limit = now + Duration::days(days);
+                let expiring: Vec<_> = items
+                    .iter()
+                    .filter(|i| i.expires_at.map(|e| e <= limit).unwrap_or(false))
+                    .collect();
+                ExpiryWindow {
+                    days,
+                    bytes: expiring.iter().map(|i| i.size_in_bytes).sum(),
+                    count: expiring.len(),
+                }
+            })
+            .collect();
+
+        Self {
+            account: account.to_string(),
+            basis,
+            growth_per_day,
+            quota,
+            days_until_full,
+            expiring,
+        }
+    }
+}
+
+/// Least-squares slope of the account's usage in the recent snapshots, in
+/// bytes per day. Snapshots without that account are skipped.
+fn growth_from_snapshots(snapshots: &[StorageSnapshot], account: &str, now: DateTime<Utc>) -> Option<f64> {
+    let since = now - Duration::days(TREND_WINDOW_DAYS);
+    let recent: Vec<_> = snapshots
+        .iter()
+        .filter(|s| s.taken_at >= since)
+        .filter_map(|s| s.account(account).map(|a| (s.taken_at, a.used)))
+        .collect();
+    let (first, last) = (recent.first()?, recent.last()?);
+    if last.0 - first.0 < Duration::hours(MIN_SNAPSHOT_SPAN_HOURS) {
+        return None;
+    }
+
+    let points: Vec<(f64, f64)> = recent
+        .iter()
+        .map(|(taken_at, used)| {
+            let days = (*taken_at - first.0).num_seconds() as f64 / 86_400.0;
+            (days, *used as f64)
+        })
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Average daily size of the items created during the trend window.
+fn growth_from_creation_dates(items: &[StorageItem], now: DateTime<Utc>) -> f64 {
+    let since = now - Duration::days(TREND_WINDOW_DAYS);
+    let created: u64 = items
+        .iter()
+        .filter(|i| i.created_at.map(|c| c >= since).unwrap_or(false))
+        .map(|i| i.size_in_bytes)
+        .sum();
+    created as f64 / TREND_WINDOW_DAYS as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::history::AccountUsage;
+    use crate::domain::storage::StorageItemType;
+
+    fn item(size: u64, created_days_ago: i64, expires_in_days: i64, now: DateTime<Utc>) -> StorageItem {
+        StorageItem {
+            id: 1,
+            name: "Artifact: dist".into(),
+            owner: "acme".into(),
+            repo: "api".into(),
+            size_in_bytes: size,
+            item_type: StorageItemType::Artifact,
+            created_at: Some(now - Duration::days(created_days_ago)),
+            expires_at: Some(now + Duration::days(expires_in_days)),
+        }
+    }
+
+    fn snapshot(used: u64, days_ago: i64, now: DateTime<Utc>) -> StorageSnapshot {
+        StorageSnapshot {
+            taken_at: now - Duration::days(days_ago),
+            total_used: used,
+            accounts: vec![AccountUsage { account: "acme".into(), used, quota: Some(2000) }],
+            repos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_forecast_from_snapshots() {
+        let now = Utc::now();
+        let snapshots = vec![snapshot(700, 3, now), snapshot(800, 2, now), snapshot(900, 1, now), snapshot(1000, 0, now)];
+
+        let forecast = StorageForecast::new("acme", 1000, Some(2000), &[], &snapshots, now);
+        assert_eq!(forecast.account, "acme");
+        assert_eq!(forecast.basis, ForecastBasis::Snapshots);
+        assert!((forecast.growth_per_day - 100.0).abs() < 1e-6);
+        assert!((forecast.days_until_full.unwrap() - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_forecast_reads_only_its_account_in_snapshots() {
+        let now = Utc::now();
+        let octocat = |used| AccountUsage { account: "octocat".into(), used, quota: Some(10_000) };
+        let mut latest = snapshot(1000, 0, now);
+        latest.accounts.push(octocat(9500));
+        // octocat is absent from the first snapshot and alone in the second
+        let snapshots = vec![
+            snapshot(700, 3, now),
+            StorageSnapshot {
+                taken_at: now - Duration::hours(36),
+                total_used: 9000,
+                accounts: vec![octocat(9000)],
+                repos: Vec::new(),
+            },
+            latest,
+        ];
+
+        let acme = StorageForecast::new("acme", 1000, Some(2000), &[], &snapshots, now);
+        assert!((acme.growth_per_day - 100.0).abs() < 1e-6);
+        assert!((acme.days_until_full.unwrap() - 10.0).abs() < 1e-6);
+
+        let octocat = StorageForecast::new("octocat", 9500, Some(10_000), &[], &snapshots, now);
+        assert_eq!(octocat.basis, ForecastBasis::Snapshots);
+        assert!((octocat.days_until_full.unwrap() - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_forecast_falls_back_to_creation_dates() {
+        let now = Utc::now();
+        let items = vec![item(3000, 5, 85, now), item(9000, 60, 30, now)];
+        // A single snapshot is not enough to measure a trend
+        let snapshots = vec![snapshot(12000, 0, now)];
+
+        let forecast = StorageForecast::new("acme", 12000, Some(15000), &items, &snapshots, now);
+        assert_eq!(forecast.basis, ForecastBasis::CreationDates);
+        assert_eq!(forecast.growth_per_day, 100.0);
+        assert_eq!(forecast.days_until_full, Some(30.0));
+    }
+
+    #[test]
+    fn test_forecast_without_growth_or_quota() {
+        let now = Utc::now();
+        let shrinking = vec![snapshot(1000, 2, now), snapshot(500, 0, now)];
+
+        assert_eq!(StorageForecast::new("acme", 500, Some(2000), &[], &shrinking, now).days_until_full, None);
+        assert_eq!(StorageForecast::new("acme", 500, None, &[], &shrinking, now).days_until_full, None);
+        assert_eq!(StorageForecast::new("acme", 2500, Some(2000), &[], &shrinking, now).days_until_full, Some(0.0));
+    }
+
+    #[test]
+    fn test_expiring_windows() {
+        let now = Utc::now();
+        let items = vec![item(100, 1, 3, now), item(200, 1, 20, now), item(400, 1, 200, now)];
+
+        let forecast = StorageForecast::new("acme", 700, None, &items, &[], now);
+        let bytes: Vec<u64> = forecast.expiring.iter().map(|w| w.bytes).collect();
+        assert_eq!(bytes, vec![100, 300, 300]);
+        assert_eq!(forecast.expiring[1].count, 2);
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::domain::error::Result;
+use crate::domain::repo::RepoRef;
+
+/// Transport used for clone URLs, `https` unless configured otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitProtocol {
+    #[default]
+    Https,
+    Ssh,
+}
+
+impl FromStr for GitProtocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "https" => Ok(Self::Https),
+            "ssh" => Ok(Self::Ssh),
+            _ => Err(format!("invalid protocol '{}' (expected ssh or https)", s)),
+        }
+    }
+}
+
+/// How repositories are addressed when cloning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloneUrl {
+    Protocol(GitProtocol),
+    /// Custom URL with `{owner}` and `{name}` placeholders, e.g. a mirror
+    /// such as `file:///srv/mirrors/{owner}/{name}.git`
+    Template(String),
+}
+
+impl CloneUrl {
+    pub fn for_repo(&self, repo: &RepoRef) -> String {
+        match self {
+            CloneUrl::Protocol(GitProtocol::Https) => format!("https://github.com/{}/{}.git", repo.owner, repo.name),
+            CloneUrl::Protocol(GitProtocol::Ssh) => format!("git@github.com:{}/{}.git", repo.owner, repo.name),
+            CloneUrl::Template(template) => template.replace("{owner}", &repo.owner).replace("{name}", &repo.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum CloneStatus {
+    Cloned,
+    /// Already present locally, fetched instead
+    Updated,
+    Failed(String),
+}
+
+impl fmt::Display for CloneStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloneStatus::Cloned => write!(f, "cloned"),
+            CloneStatus::Updated => write!(f, "updated"),
+            CloneStatus::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneOutcome {
+    /// `owner/repo`
+    pub repo: String,
+    pub path: PathBuf,
+    pub status: CloneStatus,
+}
+
+/// The GitHub repository a remote URL points to, for HTTPS and SSH URLs.
+pub fn repo_from_remote_url(url: &str) -> Option<RepoRef> {
+    let (_, path) = url.trim().split_once("github.com")?;
+    let path = path.strip_prefix(':').or_else(|| path.strip_prefix('/'))?;
+    let path = path.trim_end_matches('/');
+    path.strip_suffix(".git").unwrap_or(path).parse().ok()
+}
+
+/// Local git operations, implemented on top of the `git` executable.
+#[cfg_attr(test, mockall::automock)]
+pub trait GitOperations: Send + Sync {
+    fn clone_repo(&self, url: &str, dest: &Path) -> Result<()>;
+    /// Fetch every remote of an existing clone
+    fn fetch(&self, repo_dir: &Path) -> Result<()>;
+    fn remote_url(&self, repo_dir: &Path, remote: &str) -> Result<String>;
+    /// Fetch a single ref of `remote` into `FETCH_HEAD`
+    fn fetch_ref(&self, repo_dir: &Path, remote: &str, refspec: &str) -> Result<()>;
+    /// Switch to `branch`, creating it at `start_point` or fast-forwarding it
+    /// there if it exists
+    fn checkout_branch(&self, repo_dir: &Path, branch: &str, start_point: &str) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_urls() {
+        let repo: RepoRef = "acme/api".parse().unwrap();
+        assert_eq!(CloneUrl::Protocol(GitProtocol::Https).for_repo(&repo), "https://github.com/acme/api.git");
+        assert_eq!(CloneUrl::Protocol(GitProtocol::Ssh).for_repo(&repo), "git@github.com:acme/api.git");
+        assert_eq!(
+            CloneUrl::Template("file:///srv/{owner}/{name}.git".into()).for_repo(&repo),
+            "file:///srv/acme/api.git"
+        );
+        assert!("git".parse::<GitProtocol>().unwrap_err().contains("expected ssh or https"));
+    }
+
+    #[test]
+    fn test_repo_from_remote_url() {
+        let api: RepoRef = "acme/api".parse().unwrap();
+        assert_eq!(repo_from_remote_url("https://github.com/acme/api.git"), Some(api.clone()));
+        assert_eq!(repo_from_remote_url("https://token@github.com/acme/api/"), Some(api.clone()));
+        assert_eq!(repo_from_remote_url("git@github.com:acme/api.git"), Some(api.clone()));
+        assert_eq!(repo_from_remote_url("ssh://git@github.com/acme/api"), Some(api));
+        assert_eq!(repo_from_remote_url("https://gitlab.com/acme/api.git"), None);
+    }
+}
@@ -1,19 +1,104 @@
+use std::path::Path;
 use async_trait::async_trait;
 use crate::domain::artifact::Artifact;
-use crate::domain::repo::Repository;
+use crate::domain::branch::Branch;
+use crate::domain::issue::{CloseReason, Issue, IssueComment, IssueQuery, IssueState, NewIssue};
+use crate::domain::repo::{NewRepository, OwnerRepoType, ReleaseSummary, Repository, WorkflowRunSummary};
+use crate::domain::search::{CodeHit, IssueHit, SearchPage};
+use crate::domain::pull_request::{MergeMethod, NewReview, PrListQuery, PrStatus, PrStatusQuery, PullRequest, PullRequestDetails};
+use crate::domain::protection::{BranchProtection, Ruleset};
+use crate::domain::release::{AssetUpload, NewRelease, Release, ReleaseAsset, TransferProgress};
+use crate::domain::settings::RepoSettings;
 use crate::domain::user::User;
 use crate::domain::error::Result;
 
 use crate::domain::storage::StorageUsageReport;
+use crate::domain::retention::RetentionPolicy;
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait GithubRepository: Send + Sync {
     async fn get_current_user(&self) -> Result<User>;
     async fn list_user_repos(&self) -> Result<Vec<Repository>>;
-    async fn list_repos_for(&self, owner: &str) -> Result<Vec<Repository>>;
+    /// Repositories of a user or organization; `NotFound` if the owner does not exist
+    async fn list_repos_for(&self, owner: &str, repo_type: OwnerRepoType) -> Result<Vec<Repository>>;
+    /// `NotFound` if the repository does not exist or is not visible
     async fn get_repo(&self, owner: &str, repo: &str) -> Result<Repository>;
-    
+    /// Bytes of code per language
+    async fn get_repo_languages(&self, owner: &str, repo: &str) -> Result<Vec<(String, u64)>>;
+    async fn count_open_pull_requests(&self, owner: &str, repo: &str) -> Result<u32>;
+    async fn get_latest_release(&self, owner: &str, repo: &str) -> Result<Option<ReleaseSummary>>;
+    async fn get_last_workflow_run(&self, owner: &str, repo: &str) -> Result<Option<WorkflowRunSummary>>;
+    async fn get_readme(&self, owner: &str, repo: &str) -> Result<Option<String>>;
+    /// Create a repository, from its template through the generate endpoint if it has one
+    async fn create_repo(&self, new_repo: &NewRepository) -> Result<Repository>;
+    /// Fork into `org`, or into the authenticated user's account when `None`
+    async fn fork_repo(&self, owner: &str, repo: &str, org: Option<String>) -> Result<Repository>;
+    /// Current settings; merge strategies and secret scanning are only visible to admins
+    async fn get_repo_settings(&self, owner: &str, repo: &str) -> Result<RepoSettings>;
+    /// Change the settings that are `Some`, leaving the others as they are
+    async fn update_repo_settings(&self, owner: &str, repo: &str, settings: &RepoSettings) -> Result<()>;
+    /// Whether branch protection or a ruleset protects the branch
+    async fn is_branch_protected(&self, owner: &str, repo: &str, branch: &str) -> Result<bool>;
+    /// Classic branch protection; `None` when the branch is not protected
+    async fn get_branch_protection(&self, owner: &str, repo: &str, branch: &str) -> Result<Option<BranchProtection>>;
+    /// Replace the branch protection with `protection`
+    async fn set_branch_protection(&self, owner: &str, repo: &str, branch: &str, protection: &BranchProtection) -> Result<()>;
+    /// Branch rulesets defined on the repository itself
+    async fn list_rulesets(&self, owner: &str, repo: &str) -> Result<Vec<Ruleset>>;
+    /// Update the ruleset, or create it when it has no id yet
+    async fn save_ruleset(&self, owner: &str, repo: &str, ruleset: &Ruleset) -> Result<()>;
+    /// Every branch with its last commit, position relative to the default branch and latest pull request
+    async fn list_branches(&self, owner: &str, repo: &str) -> Result<Vec<Branch>>;
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()>;
+    /// Issues matching the query, pull requests excluded, most recently created first
+    async fn list_issues(&self, owner: &str, repo: &str, query: &IssueQuery) -> Result<Vec<Issue>>;
+    /// `NotFound` if there is no such issue, `InvalidInput` if it is a pull request
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue>;
+    async fn list_issue_comments(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<IssueComment>>;
+    async fn create_issue(&self, owner: &str, repo: &str, issue: &NewIssue) -> Result<Issue>;
+    /// Close (with an optional reason) or reopen an issue
+    async fn set_issue_state(&self, owner: &str, repo: &str, number: u64, state: IssueState, reason: Option<CloseReason>) -> Result<Issue>;
+    async fn add_issue_comment(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<IssueComment>;
+    /// Issues and pull requests across GitHub; `query` is in GitHub search syntax
+    async fn search_issues(&self, query: &str, limit: usize) -> Result<SearchPage<IssueHit>>;
+    async fn search_repos(&self, query: &str, limit: usize) -> Result<SearchPage<Repository>>;
+    async fn search_code(&self, query: &str, limit: usize) -> Result<SearchPage<CodeHit>>;
+    /// Open pull requests authored by, awaiting review from or assigned to the
+    /// authenticated user, in one GraphQL request
+    async fn pull_request_status(&self, query: &PrStatusQuery) -> Result<PrStatus>;
+    async fn list_pull_requests(&self, query: &PrListQuery) -> Result<Vec<PullRequest>>;
+    async fn get_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest>;
+    /// A pull request with its description, checks, reviews and changed files
+    async fn get_pull_request_details(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequestDetails>;
+    /// The unified diff of a pull request
+    async fn get_pull_request_diff(&self, owner: &str, repo: &str, number: u64) -> Result<String>;
+    /// Submit a review, returning its URL
+    async fn submit_review(&self, owner: &str, repo: &str, number: u64, review: &NewReview) -> Result<String>;
+    /// Merge a pull request, provided its head is still `head_sha`
+    async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64, method: MergeMethod, head_sha: &str) -> Result<()>;
+    /// Let GitHub merge the pull request with node ID `id` once it can
+    async fn enable_auto_merge(&self, id: &str, method: MergeMethod) -> Result<()>;
+    async fn set_repo_archived(&self, owner: &str, repo: &str, archived: bool) -> Result<()>;
+    /// Permanently delete a repository (requires the `delete_repo` scope)
+    async fn delete_repo(&self, owner: &str, repo: &str) -> Result<()>;
+
+    // Releases
+    /// All releases, drafts included when the token can push, newest first
+    async fn list_releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>>;
+    /// `NotFound` if no published release has this tag
+    async fn get_release_by_tag(&self, owner: &str, repo: &str, tag: &str) -> Result<Release>;
+    /// Create a release, and its tag from `target` when the tag does not exist
+    async fn create_release(&self, owner: &str, repo: &str, release: &NewRelease) -> Result<Release>;
+    /// Stream the file of `upload` as an asset named `upload.name`
+    async fn upload_release_asset(&self, owner: &str, repo: &str, release_id: u64, upload: &AssetUpload, progress: TransferProgress) -> Result<ReleaseAsset>;
+    /// Stream an asset into the file at `path`, created or truncated
+    async fn download_release_asset(&self, owner: &str, repo: &str, asset_id: u64, path: &Path, progress: TransferProgress) -> Result<()>;
+    async fn rename_release_asset(&self, owner: &str, repo: &str, asset_id: u64, name: &str) -> Result<ReleaseAsset>;
+    /// Delete a release; its tag is kept
+    async fn delete_release(&self, owner: &str, repo: &str, release_id: u64) -> Result<()>;
+    async fn delete_release_asset(&self, owner: &str, repo: &str, asset_id: u64) -> Result<()>;
+
     // Artifacts
     async fn list_artifacts_for_repo(&self, owner: &str, repo: &str) -> Result<Vec<Artifact>>;
     async fn list_all_artifacts(&self) -> Result<Vec<Artifact>>;
@@ -21,5 +106,13 @@ pub trait GithubRepository: Send + Sync {
     async fn delete_cache(&self, owner: &str, repo: &str, cache_id: u64) -> Result<()>;
 
     // Billing
-    async fn get_storage_usage(&self) -> Result<StorageUsageReport>;
+    /// Billing and scanned items of every account; release assets are scanned
+    /// too when `include_release_assets` is set
+    async fn get_storage_usage(&self, include_release_assets: bool) -> Result<StorageUsageReport>;
+
+    // Artifact and log retention
+    async fn get_repo_retention(&self, owner: &str, repo: &str) -> Result<RetentionPolicy>;
+    async fn set_repo_retention(&self, owner: &str, repo: &str, days: u32) -> Result<()>;
+    async fn get_org_retention(&self, org: &str) -> Result<RetentionPolicy>;
+    async fn set_org_retention(&self, org: &str, days: u32) -> Result<()>;
 }
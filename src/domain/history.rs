@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::error::Result;
+use crate::domain::storage::{group_items, StorageGrouping, StorageUsageReport};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepoUsage {
+    /// `owner/repo`
+    pub repo: String,
+    pub size_in_bytes: u64,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccountUsage {
+    pub account: String,
+    pub used: u64,
+    /// `None` when the quota was unknown at that time
+    pub quota: Option<u64>,
+}
+
+/// Point-in-time copy of a `StorageUsageReport`, kept to track usage trends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StorageSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub total_used: u64,
+    /// Empty for snapshots recorded before usage was kept per account
+    #[serde(default)]
+    pub accounts: Vec<AccountUsage>,
+    pub repos: Vec<RepoUsage>,
+}
+
+impl StorageSnapshot {
+    pub fn from_report(report: &StorageUsageReport, taken_at: DateTime<Utc>) -> Self {
+        let repos = group_items(&report.actions_items(), StorageGrouping::Repo)
+            .into_iter()
+            .map(|g| RepoUsage {
+                repo: g.key,
+                size_in_bytes: g.total_bytes,
+                count: g.count,
+            })
+            .collect();
+
+        let accounts = report
+            .accounts
+            .iter()
+            .map(|a| AccountUsage {
+                account: a.account.clone(),
+                used: a.used_bytes(),
+                quota: a.quota_bytes(),
+            })
+            .collect();
+
+        Self {
+            taken_at,
+            total_used: report.total_used(),
+            accounts,
+            repos,
+        }
+    }
+
+    pub fn account(&self, account: &str) -> Option<&AccountUsage> {
+        self.accounts.iter().find(|a| a.account.eq_ignore_ascii_case(account))
+    }
+
+    /// Usage and sum of the quotas of the accounts whose quota was known, so
+    /// that accounts without a quota do not inflate the usage percentage.
+    pub fn usage_with_known_quota(&self) -> Option<(u64, u64)> {
+        let known: Vec<_> = self.accounts.iter().filter(|a| a.quota.is_some()).collect();
+        if known.is_empty() {
+            return None;
+        }
+        Some((
+            known.iter().map(|a| a.used).sum(),
+            known.iter().filter_map(|a| a.quota).sum(),
+        ))
+    }
+
+    /// Usage of a single repository; 0 if it had no items at that time
+    pub fn repo_usage(&self, repo: &str) -> u64 {
+        self.repos
+            .iter()
+            .find(|r| r.repo.eq_ignore_ascii_case(repo))
+            .map(|r| r.size_in_bytes)
+            .unwrap_or(0)
+    }
+}
+
+/// Usage at one snapshot and its change since the previous one
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsagePoint {
+    pub taken_at: DateTime<Utc>,
+    pub used: u64,
+    /// Quota the usage percentage is computed against, when known
+    pub quota: Option<u64>,
+    pub usage_percentage: Option<f64>,
+    pub delta: i64,
+}
+
+/// Total usage over time, or the usage of a single repository when `repo` is set.
+/// The percentage only covers the accounts with a known quota; for a repository
+/// it is its share of the quota of the account that owns it.
+pub fn usage_series(snapshots: &[StorageSnapshot], repo: Option<&str>) -> Vec<UsagePoint> {
+    let mut previous: Option<u64> = None;
+    snapshots
+        .iter()
+        .map(|s| {
+            let (used, usage) = match repo {
+                Some(r) => {
+                    let used = s.repo_usage(r);
+                    let owner = r.split('/').next().unwrap_or(r);
+                    (used, s.account(owner).and_then(|a| a.quota).map(|quota| (used, quota)))
+                }
+                None => (s.total_used, s.usage_with_known_quota()),
+            };
+            let delta = previous.map(|p| used as i64 - p as i64).unwrap_or(0);
+            previous = Some(used);
+            UsagePoint {
+                taken_at: s.taken_at,
+                used,
+                quota: usage.map(|(_, quota)| quota),
+                usage_percentage: usage
+                    .filter(|&(_, quota)| quota > 0)
+                    .map(|(used, quota)| used as f64 / quota as f64 * 100.0),
+                delta,
+            }
+        })
+        .collect()
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait StorageHistoryRepository: Send + Sync {
+    fn append(&self, snapshot: &StorageSnapshot) -> Result<()>;
+    /// All snapshots, oldest first
+    fn load(&self) -> Result<Vec<StorageSnapshot>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::billing::{AccountStorage, AccountType};
+    use crate::domain::storage::{StorageItem, StorageItemType};
+
+    fn item(owner: &str, repo: &str, size: u64) -> StorageItem {
+        StorageItem {
+            id: 1,
+            name: "Artifact: dist".into(),
+            owner: owner.into(),
+            repo: repo.into(),
+            size_in_bytes: size,
+            item_type: StorageItemType::Artifact,
+            created_at: None,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_from_report() {
+        let mut account = AccountStorage::new("acme", AccountType::Organization);
+        account.quota_override = Some(10_000);
+        let items = vec![item("acme", "api", 300), item("acme", "api", 200), item("acme", "web", 100)];
+        let report = StorageUsageReport::new(vec![account], items);
+
+        let snapshot = StorageSnapshot::from_report(&report, Utc::now());
+        assert_eq!(snapshot.total_used, 600);
+        assert_eq!(snapshot.accounts, vec![AccountUsage { account: "acme".into(), used: 600, quota: Some(10_000) }]);
+        assert_eq!(snapshot.repos[0], RepoUsage { repo: "acme/api".into(), size_in_bytes: 500, count: 2 });
+        assert_eq!(snapshot.repo_usage("acme/web"), 100);
+        assert_eq!(snapshot.repo_usage("acme/docs"), 0);
+    }
+
+    #[test]
+    fn test_usage_percentage_ignores_accounts_without_quota() {
+        let mut user = AccountStorage::new("octocat", AccountType::User);
+        user.quota_override = Some(1000);
+        let org = AccountStorage::new("acme", AccountType::Organization);
+        let items = vec![item("octocat", "dotfiles", 250), item("acme", "api", 5000)];
+        let report = StorageUsageReport::new(vec![user, org], items);
+
+        let snapshots = vec![StorageSnapshot::from_report(&report, Utc::now())];
+        let total = &usage_series(&snapshots, None)[0];
+        assert_eq!(total.used, 5250);
+        assert_eq!(total.quota, Some(1000));
+        assert_eq!(total.usage_percentage, Some(25.0));
+
+        let repo = &usage_series(&snapshots, Some("octocat/dotfiles"))[0];
+        assert_eq!(repo.usage_percentage, Some(25.0));
+        assert_eq!(usage_series(&snapshots, Some("acme/api"))[0].usage_percentage, None);
+    }
+
+    #[test]
+    fn test_snapshots_without_accounts_have_no_percentage() {
+        let line = r#"{"taken_at":"2026-01-01T00:00:00Z","total_used":600,"max_allowed":1000,"repos":[]}"#;
+        let snapshot: StorageSnapshot = serde_json::from_str(line).unwrap();
+        assert!(snapshot.accounts.is_empty());
+        assert_eq!(usage_series(&[snapshot], None)[0].usage_percentage, None);
+    }
+
+    #[test]
+    fn test_usage_series_deltas() {
+        let snapshot = |total_used, api| StorageSnapshot {
+            taken_at: Utc::now(),
+            total_used,
+            accounts: Vec::new(),
+            repos: vec![RepoUsage { repo: "acme/api".into(), size_in_bytes: api, count: 1 }],
+        };
+        let snapshots = vec![snapshot(100, 50), snapshot(300, 20), snapshot(250, 80)];
+
+        let totals: Vec<i64> = usage_series(&snapshots, None).iter().map(|p| p.delta).collect();
+        assert_eq!(totals, vec![0, 200, -50]);
+
+        let api: Vec<u64> = usage_series(&snapshots, Some("acme/api")).iter().map(|p| p.used).collect();
+        assert_eq!(api, vec![50, 20, 80]);
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::domain::repo::RepoRef;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueState {
+    Open,
+    Closed,
+}
+
+impl fmt::Display for IssueState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueState::Open => write!(f, "open"),
+            IssueState::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+/// Why an issue was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseReason {
+    Completed,
+    NotPlanned,
+}
+
+impl CloseReason {
+    /// Value of `state_reason` in the GitHub API
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            CloseReason::Completed => "completed",
+            CloseReason::NotPlanned => "not_planned",
+        }
+    }
+}
+
+impl FromStr for CloseReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "completed" => Ok(Self::Completed),
+            "not-planned" => Ok(Self::NotPlanned),
+            _ => Err(format!("invalid reason '{}' (expected completed or not-planned)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub state: IssueState,
+    /// `completed`, `not_planned` or `reopened`, when GitHub has one
+    pub state_reason: Option<String>,
+    pub author: String,
+    pub assignees: Vec<String>,
+    pub labels: Vec<String>,
+    /// Milestone title
+    pub milestone: Option<String>,
+    pub comments: u32,
+    pub body: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub html_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueComment {
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub html_url: String,
+}
+
+/// An issue with its conversation, as shown by `ghr issue view`.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueDetails {
+    pub issue: Issue,
+    pub comments: Vec<IssueComment>,
+}
+
+/// Which issues `ghr issue list` shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IssueStateFilter {
+    #[default]
+    Open,
+    Closed,
+    All,
+}
+
+impl IssueStateFilter {
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            IssueStateFilter::Open => "open",
+            IssueStateFilter::Closed => "closed",
+            IssueStateFilter::All => "all",
+        }
+    }
+}
+
+impl FromStr for IssueStateFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(Self::Open),
+            "closed" => Ok(Self::Closed),
+            "all" => Ok(Self::All),
+            _ => Err(format!("invalid state '{}' (expected open, closed or all)", s)),
+        }
+    }
+}
+
+/// Filters of `ghr issue list`. Unset fields do not filter anything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IssueQuery {
+    pub state: IssueStateFilter,
+    /// Issues must have every one of these labels
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+    pub author: Option<String>,
+    /// Milestone title
+    pub milestone: Option<String>,
+    /// Free text in GitHub search syntax, e.g. `crash in:title`
+    pub search: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl IssueQuery {
+    /// The issues endpoint filters milestones by number only, so milestone
+    /// titles and free text go through issue search.
+    pub fn needs_search(&self) -> bool {
+        self.search.is_some() || self.milestone.is_some()
+    }
+
+    /// The query string of `GET /search/issues` for the issues of `repo`.
+    pub fn search_terms(&self, repo: &RepoRef) -> String {
+        let mut terms = vec![format!("repo:{}", repo), "is:issue".to_string()];
+        match self.state {
+            IssueStateFilter::Open => terms.push("state:open".into()),
+            IssueStateFilter::Closed => terms.push("state:closed".into()),
+            IssueStateFilter::All => {}
+        }
+        terms.extend(self.labels.iter().map(|l| format!("label:{}", quote(l))));
+        if let Some(ref assignee) = self.assignee {
+            terms.push(format!("assignee:{}", assignee));
+        }
+        if let Some(ref author) = self.author {
+            terms.push(format!("author:{}", author));
+        }
+        if let Some(ref milestone) = self.milestone {
+            terms.push(format!("milestone:{}", quote(milestone)));
+        }
+        if let Some(ref search) = self.search {
+            terms.push(search.clone());
+        }
+        terms.join(" ")
+    }
+}
+
+fn quote(value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// An issue to open.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewIssue {
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+}
+
+impl NewIssue {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("the issue title cannot be empty".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_terms() {
+        let repo: RepoRef = "acme/api".parse().unwrap();
+        let query = IssueQuery {
+            labels: vec!["bug".into(), "good first issue".into()],
+            author: Some("octocat".into()),
+            milestone: Some("v1.0".into()),
+            search: Some("crash in:title".into()),
+            ..Default::default()
+        };
+        assert!(query.needs_search());
+        assert_eq!(
+            query.search_terms(&repo),
+            "repo:acme/api is:issue state:open label:bug label:\"good first issue\" author:octocat milestone:v1.0 crash in:title"
+        );
+
+        let all = IssueQuery { state: IssueStateFilter::All, ..Default::default() };
+        assert!(!all.needs_search());
+        assert_eq!(all.search_terms(&repo), "repo:acme/api is:issue");
+    }
+
+    #[test]
+    fn test_parse_filters() {
+        assert_eq!("Closed".parse::<IssueStateFilter>(), Ok(IssueStateFilter::Closed));
+        assert!("merged".parse::<IssueStateFilter>().is_err());
+        assert_eq!("not-planned".parse::<CloseReason>(), Ok(CloseReason::NotPlanned));
+        assert_eq!("not_planned".parse::<CloseReason>(), Ok(CloseReason::NotPlanned));
+        assert!(NewIssue { title: "  ".into(), ..Default::default() }.validate().is_err());
+    }
+}
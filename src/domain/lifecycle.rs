@@ -0,0 +1,74 @@
+use std::fmt;
+use serde::Serialize;
+use crate::domain::repo::Repository;
+
+/// Bulk operations used to clean up stale repositories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RepoAction {
+    Archive,
+    Unarchive,
+    Delete,
+}
+
+impl RepoAction {
+    /// True when the repository is already in the state the action leads to.
+    pub fn is_noop(&self, repo: &Repository) -> bool {
+        match self {
+            RepoAction::Archive => repo.archived,
+            RepoAction::Unarchive => !repo.archived,
+            RepoAction::Delete => false,
+        }
+    }
+
+    /// Archived repositories are only worth listing when they are the target.
+    pub fn includes_archived(&self) -> bool {
+        !matches!(self, RepoAction::Archive)
+    }
+}
+
+impl fmt::Display for RepoAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepoAction::Archive => write!(f, "archive"),
+            RepoAction::Unarchive => write!(f, "unarchive"),
+            RepoAction::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum RepoActionStatus {
+    Done,
+    /// Already archived (or unarchived)
+    Unchanged,
+    /// Dry run: would be applied
+    Pending,
+    /// Not confirmed by the user
+    Skipped,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoActionResult {
+    /// `owner/repo`
+    pub repo: String,
+    pub action: RepoAction,
+    pub status: RepoActionStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_actions() {
+        let archived = Repository { archived: true, ..Default::default() };
+        let active = Repository::default();
+
+        assert!(RepoAction::Archive.is_noop(&archived));
+        assert!(!RepoAction::Archive.is_noop(&active));
+        assert!(RepoAction::Unarchive.is_noop(&active));
+        assert!(!RepoAction::Delete.is_noop(&archived));
+        assert!(!RepoAction::Archive.includes_archived());
+    }
+}
@@ -4,3 +4,17 @@ pub mod user;
 pub mod error;
 pub mod github;
 pub mod storage;
+pub mod billing;
+pub mod history;
+pub mod forecast;
+pub mod retention;
+pub mod git;
+pub mod lifecycle;
+pub mod settings;
+pub mod audit;
+pub mod protection;
+pub mod branch;
+pub mod issue;
+pub mod search;
+pub mod pull_request;
+pub mod release;
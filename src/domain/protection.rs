@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::settings::SettingChange;
+
+/// Rules protecting a branch, applied either as classic branch protection or
+/// as a repository ruleset. This is also the format of protection files:
+///
+/// ```toml
+/// required_approving_reviews = 1
+/// dismiss_stale_reviews = true
+/// required_status_checks = ["build", "test"]
+/// required_linear_history = true
+/// ```
+///
+/// Anything left out is turned off, so a file describes the whole protection.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BranchProtection {
+    /// Approvals needed to merge a pull request; `None` allows pushing without one
+    pub required_approving_reviews: Option<u32>,
+    pub dismiss_stale_reviews: bool,
+    pub require_code_owner_reviews: bool,
+    /// Status checks that must pass before merging; `None` requires none
+    pub required_status_checks: Option<Vec<String>>,
+    /// Branches must be up to date with the base branch before merging
+    pub strict_status_checks: bool,
+    /// Apply the rules to administrators too
+    pub enforce_admins: bool,
+    pub required_linear_history: bool,
+    pub allow_force_pushes: bool,
+    pub allow_deletions: bool,
+    pub required_conversation_resolution: bool,
+}
+
+/// A repository ruleset targeting branches.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Ruleset {
+    /// `None` until it is created
+    pub id: Option<u64>,
+    pub name: String,
+    /// `active`, `evaluate` or `disabled`
+    pub enforcement: String,
+    /// Ref patterns the ruleset applies to, e.g. `refs/heads/main` or `~DEFAULT_BRANCH`
+    pub branches: Vec<String>,
+    pub protection: BranchProtection,
+}
+
+impl Ruleset {
+    /// The ref pattern of `branch`, or of the default branch whatever its name.
+    pub fn branch_pattern(branch: Option<&str>) -> String {
+        match branch {
+            Some(branch) => format!("refs/heads/{}", branch),
+            None => "~DEFAULT_BRANCH".to_string(),
+        }
+    }
+
+    /// Changes that turn `current`, the existing ruleset of the same name if
+    /// any, into `self`.
+    pub fn diff(&self, current: Option<&Ruleset>) -> Vec<SettingChange> {
+        let Some(current) = current else {
+            let mut changes = vec![SettingChange { setting: "ruleset", current: "none".into(), desired: self.name.clone() }];
+            changes.extend(self.protection.diff(Some(&BranchProtection::unprotected())));
+            return changes;
+        };
+
+        let mut changes = Vec::new();
+        if current.enforcement != self.enforcement {
+            changes.push(SettingChange {
+                setting: "enforcement",
+                current: current.enforcement.clone(),
+                desired: self.enforcement.clone(),
+            });
+        }
+        if current.branches != self.branches {
+            changes.push(SettingChange {
+                setting: "branches",
+                current: current.branches.join(", "),
+                desired: self.branches.join(", "),
+            });
+        }
+        changes.extend(self.protection.diff(Some(&current.protection)));
+        changes
+    }
+}
+
+impl BranchProtection {
+    /// What an unprotected branch allows, to compare against when there is no protection.
+    pub fn unprotected() -> Self {
+        Self {
+            allow_force_pushes: true,
+            allow_deletions: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.required_approving_reviews.is_some_and(|n| n > 6) {
+            return Err("required_approving_reviews must be between 0 and 6".into());
+        }
+        if self.required_approving_reviews.is_none() && (self.dismiss_stale_reviews || self.require_code_owner_reviews) {
+            return Err("dismiss_stale_reviews and require_code_owner_reviews need required_approving_reviews".into());
+        }
+        if self.required_status_checks.is_none() && self.strict_status_checks {
+            return Err("strict_status_checks needs required_status_checks".into());
+        }
+        Ok(())
+    }
+
+    /// Rules of `self` (the desired protection) that differ from `current`;
+    /// `None` means the branch is not protected.
+    pub fn diff(&self, current: Option<&BranchProtection>) -> Vec<SettingChange> {
+        let mut changes = Vec::new();
+        let unprotected = Self::unprotected();
+        let current = match current {
+            Some(current) => current,
+            None => {
+                changes.push(SettingChange { setting: "protected", current: "false".into(), desired: "true".into() });
+                &unprotected
+            }
+        };
+
+        let mut check = |setting: &'static str, current: String, desired: String| {
+            if current != desired {
+                changes.push(SettingChange { setting, current, desired });
+            }
+        };
+        check(
+            "required_approving_reviews",
+            optional(current.required_approving_reviews),
+            optional(self.required_approving_reviews),
+        );
+        check("dismiss_stale_reviews", current.dismiss_stale_reviews.to_string(), self.dismiss_stale_reviews.to_string());
+        check(
+            "require_code_owner_reviews",
+            current.require_code_owner_reviews.to_string(),
+            self.require_code_owner_reviews.to_string(),
+        );
+        check(
+            "required_status_checks",
+            check_list(current.required_status_checks.as_deref()),
+            check_list(self.required_status_checks.as_deref()),
+        );
+        check("strict_status_checks", current.strict_status_checks.to_string(), self.strict_status_checks.to_string());
+        check("enforce_admins", current.enforce_admins.to_string(), self.enforce_admins.to_string());
+        check(
+            "required_linear_history",
+            current.required_linear_history.to_string(),
+            self.required_linear_history.to_string(),
+        );
+        check("allow_force_pushes", current.allow_force_pushes.to_string(), self.allow_force_pushes.to_string());
+        check("allow_deletions", current.allow_deletions.to_string(), self.allow_deletions.to_string());
+        check(
+            "required_conversation_resolution",
+            current.required_conversation_resolution.to_string(),
+            self.required_conversation_resolution.to_string(),
+        );
+        changes
+    }
+}
+
+fn optional(value: Option<u32>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "off".into())
+}
+
+fn check_list(checks: Option<&[String]>) -> String {
+    match checks {
+        Some(checks) => {
+            let mut sorted = checks.to_vec();
+            sorted.sort();
+            sorted.dedup();
+            if sorted.is_empty() { "none".into() } else { sorted.join(", ") }
+        }
+        None => "off".into(),
+    }
+}
+
+/// Protection of one branch, as shown by `ghr protect show`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProtectionReport {
+    /// `owner/repo`
+    pub repo: String,
+    pub branch: String,
+    /// Classic branch protection; `None` when the branch has none
+    pub protection: Option<BranchProtection>,
+    pub rulesets: Vec<Ruleset>,
+    /// Why the rulesets could not be read, in which case `rulesets` is empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rulesets_error: Option<String>,
+}
+
+/// The protection changes to make on one repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProtectionPlan {
+    pub owner: String,
+    pub name: String,
+    pub branch: String,
+    /// The ruleset to update or create, when applying a ruleset
+    #[serde(skip)]
+    pub ruleset: Option<Ruleset>,
+    pub changes: Vec<SettingChange>,
+    /// `Pending` until applied, `Unchanged` when already protected this way
+    /// and `Failed` when the current protection could not be read
+    pub status: RepoActionStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desired() -> BranchProtection {
+        toml::from_str(
+            "required_approving_reviews = 1\nrequired_status_checks = [\"test\", \"build\"]\nrequired_linear_history = true\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_against_unprotected_branch() {
+        let changes = desired().diff(None);
+        let settings: Vec<_> = changes.iter().map(|c| c.setting).collect();
+        assert_eq!(
+            settings,
+            vec![
+                "protected",
+                "required_approving_reviews",
+                "required_status_checks",
+                "required_linear_history",
+                "allow_force_pushes",
+                "allow_deletions",
+            ]
+        );
+        assert_eq!(changes[2].desired, "build, test");
+    }
+
+    #[test]
+    fn test_diff_against_current_protection() {
+        let mut current = desired();
+        current.required_status_checks = Some(vec!["build".into(), "test".into()]);
+        assert!(desired().diff(Some(&current)).is_empty());
+
+        current.required_approving_reviews = Some(2);
+        current.allow_force_pushes = true;
+        let changes = desired().diff(Some(&current));
+        assert_eq!(
+            changes,
+            vec![
+                SettingChange { setting: "required_approving_reviews", current: "2".into(), desired: "1".into() },
+                SettingChange { setting: "allow_force_pushes", current: "true".into(), desired: "false".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_and_file_format() {
+        assert!(desired().validate().is_ok());
+        let file: BranchProtection = toml::from_str("dismiss_stale_reviews = true").unwrap();
+        assert!(file.validate().is_err());
+        assert!(toml::from_str::<BranchProtection>("required_reviews = 1").is_err());
+        assert_eq!(Ruleset::branch_pattern(Some("main")), "refs/heads/main");
+        assert_eq!(Ruleset::branch_pattern(None), "~DEFAULT_BRANCH");
+    }
+
+    #[test]
+    fn test_ruleset_diff() {
+        let ruleset = Ruleset {
+            id: None,
+            name: "main".into(),
+            enforcement: "active".into(),
+            branches: vec![Ruleset::branch_pattern(None)],
+            protection: desired(),
+        };
+        let changes = ruleset.diff(None);
+        assert_eq!(changes[0], SettingChange { setting: "ruleset", current: "none".into(), desired: "main".into() });
+        assert!(!changes.iter().any(|c| c.setting == "protected"));
+
+        let existing = Ruleset {
+            id: Some(7),
+            enforcement: "evaluate".into(),
+            ..ruleset.clone()
+        };
+        let changes = ruleset.diff(Some(&existing));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].setting, "enforcement");
+    }
+}
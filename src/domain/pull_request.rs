@@ -0,0 +1,487 @@
+use std::fmt;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::domain::branch::PullRequestState;
+use crate::domain::repo::RepoRef;
+
+/// Combined state of the checks and commit statuses of a pull request's
+/// head commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Success,
+    Failure,
+    Pending,
+    /// No checks ran on the head commit
+    #[default]
+    None,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Success => write!(f, "passing"),
+            CheckStatus::Failure => write!(f, "failing"),
+            CheckStatus::Pending => write!(f, "pending"),
+            CheckStatus::None => write!(f, "none"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+impl fmt::Display for ReviewDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewDecision::Approved => write!(f, "approved"),
+            ReviewDecision::ChangesRequested => write!(f, "changes requested"),
+            ReviewDecision::ReviewRequired => write!(f, "review required"),
+        }
+    }
+}
+
+/// Whether a pull request merges cleanly into its base branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeableState {
+    Mergeable,
+    Conflicting,
+    /// GitHub has not computed it yet
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for MergeableState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeableState::Mergeable => write!(f, "mergeable"),
+            MergeableState::Conflicting => write!(f, "conflicts"),
+            MergeableState::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// GitHub's verdict on merging a pull request right now, branch protection
+/// included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStateStatus {
+    Clean,
+    /// Mergeable, but checks that are not required are failing
+    Unstable,
+    HasHooks,
+    /// The base branch requires pull requests to be up to date
+    Behind,
+    /// Required checks or reviews are missing
+    Blocked,
+    /// Conflicts with the base branch
+    Dirty,
+    Draft,
+    #[default]
+    Unknown,
+}
+
+/// A pull request with what decides whether it can be merged.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PullRequest {
+    /// GraphQL node ID
+    pub id: String,
+    /// `owner/repo`
+    pub repository: String,
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    /// `Open` for drafts too, see `draft`
+    pub state: PullRequestState,
+    pub draft: bool,
+    pub head_ref: String,
+    pub head_sha: String,
+    /// `owner/repo` the head branch lives in, `None` when that fork was deleted
+    pub head_repository: Option<String>,
+    pub base_ref: String,
+    pub checks: CheckStatus,
+    /// Only set when the base branch requires reviews or someone reviewed
+    pub review_decision: Option<ReviewDecision>,
+    pub mergeable: MergeableState,
+    pub merge_state: MergeStateStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub html_url: String,
+}
+
+impl PullRequest {
+    pub fn is_from_fork(&self) -> bool {
+        self.head_repository.as_deref() != Some(self.repository.as_str())
+    }
+
+    /// Name of the local branch `ghr pr checkout` creates: the head branch,
+    /// prefixed with the fork owner for pull requests from forks.
+    pub fn local_branch(&self) -> String {
+        match self.head_repository.as_deref().and_then(|r| r.split_once('/')) {
+            Some((owner, _)) if self.is_from_fork() => format!("{}/{}", owner, self.head_ref),
+            None => format!("pr-{}", self.number),
+            _ => self.head_ref.clone(),
+        }
+    }
+
+    /// Why GitHub would refuse to merge the pull request now, empty when
+    /// nothing is known to block it.
+    pub fn merge_blockers(&self) -> Vec<String> {
+        let mut blockers = Vec::new();
+        if self.draft {
+            blockers.push("it is a draft".to_string());
+        }
+        if self.mergeable == MergeableState::Conflicting || self.merge_state == MergeStateStatus::Dirty {
+            blockers.push(format!("it conflicts with {}", self.base_ref));
+        }
+        if self.merge_state == MergeStateStatus::Behind {
+            blockers.push(format!("it is behind {} and must be updated first", self.base_ref));
+        }
+        if self.merge_state == MergeStateStatus::Blocked {
+            let before = blockers.len();
+            match self.checks {
+                CheckStatus::Failure => blockers.push("required checks are failing".to_string()),
+                CheckStatus::Pending => blockers.push("required checks have not finished".to_string()),
+                CheckStatus::Success | CheckStatus::None => {}
+            }
+            match self.review_decision {
+                Some(ReviewDecision::ChangesRequested) => blockers.push("a reviewer requested changes".to_string()),
+                Some(ReviewDecision::ReviewRequired) => blockers.push("it needs an approving review".to_string()),
+                Some(ReviewDecision::Approved) | None => {}
+            }
+            if blockers.len() == before {
+                blockers.push(format!("the protection rules of {} are not satisfied", self.base_ref));
+            }
+        }
+        blockers
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeMethod {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeMethod {
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        }
+    }
+}
+
+/// What `ghr pr merge` did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// `branch_error` is why the head branch could not be deleted after the
+    /// merge, e.g. GitHub already deleted it on merge
+    Merged { branch_deleted: bool, branch_error: Option<String> },
+    /// GitHub merges it once checks and reviews allow
+    AutoMergeEnabled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewEvent {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+impl ReviewEvent {
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            ReviewEvent::Approve => "APPROVE",
+            ReviewEvent::RequestChanges => "REQUEST_CHANGES",
+            ReviewEvent::Comment => "COMMENT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewReview {
+    pub event: ReviewEvent,
+    pub body: Option<String>,
+}
+
+impl NewReview {
+    pub fn validate(&self) -> Result<(), String> {
+        let has_body = self.body.as_deref().is_some_and(|b| !b.trim().is_empty());
+        match self.event {
+            ReviewEvent::RequestChanges if !has_body => Err("requesting changes needs a --body explaining them".into()),
+            ReviewEvent::Comment if !has_body => Err("a comment review needs a --body".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrStateFilter {
+    #[default]
+    Open,
+    Closed,
+    Merged,
+    All,
+}
+
+impl PrStateFilter {
+    pub const ALL: [PrStateFilter; 4] = [PrStateFilter::Open, PrStateFilter::Closed, PrStateFilter::Merged, PrStateFilter::All];
+
+    /// The next filter, wrapping around.
+    pub fn next(&self) -> PrStateFilter {
+        let i = Self::ALL.iter().position(|s| s == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    fn qualifier(&self) -> Option<&'static str> {
+        match self {
+            PrStateFilter::Open => Some("is:open"),
+            PrStateFilter::Closed => Some("is:closed is:unmerged"),
+            PrStateFilter::Merged => Some("is:merged"),
+            PrStateFilter::All => None,
+        }
+    }
+}
+
+impl fmt::Display for PrStateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrStateFilter::Open => write!(f, "open"),
+            PrStateFilter::Closed => write!(f, "closed"),
+            PrStateFilter::Merged => write!(f, "merged"),
+            PrStateFilter::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Pull requests listed in the TUI.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrListQuery {
+    pub state: PrStateFilter,
+    /// Login of the author, `@me` for the authenticated user
+    pub author: Option<String>,
+    /// Only pull requests awaiting a review from the authenticated user
+    pub review_requested: bool,
+    /// Pull requests to fetch, at most 100
+    pub limit: usize,
+}
+
+impl PrListQuery {
+    /// The GitHub search query. Without an author or review filter it matches
+    /// every pull request involving the authenticated user.
+    pub fn search_query(&self) -> String {
+        let mut terms = vec!["is:pr".to_string(), "archived:false".to_string()];
+        terms.extend(self.state.qualifier().map(String::from));
+        if let Some(ref author) = self.author {
+            terms.push(format!("author:{}", author));
+        }
+        if self.review_requested {
+            terms.push("review-requested:@me".to_string());
+        }
+        if self.author.is_none() && !self.review_requested {
+            terms.push("involves:@me".to_string());
+        }
+        terms.push("sort:updated-desc".to_string());
+        terms.join(" ")
+    }
+}
+
+/// A check run or commit status of the head commit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: CheckStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    /// Started but not submitted yet
+    Pending,
+}
+
+impl fmt::Display for ReviewState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewState::Approved => write!(f, "approved"),
+            ReviewState::ChangesRequested => write!(f, "requested changes"),
+            ReviewState::Commented => write!(f, "commented"),
+            ReviewState::Dismissed => write!(f, "dismissed"),
+            ReviewState::Pending => write!(f, "pending"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Review {
+    pub author: String,
+    pub state: ReviewState,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangedFile {
+    pub path: String,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// A pull request with its description, checks, reviews and changed files.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PullRequestDetails {
+    pub pull_request: PullRequest,
+    pub body: Option<String>,
+    pub checks: Vec<CheckRun>,
+    /// Oldest first
+    pub reviews: Vec<Review>,
+    /// The first 100 files
+    pub files: Vec<ChangedFile>,
+    /// Number of changed files, possibly more than `files` holds
+    pub changed_files: u32,
+}
+
+/// Narrows `ghr pr status` to one repository or organization.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrStatusQuery {
+    pub repo: Option<RepoRef>,
+    pub org: Option<String>,
+    /// Pull requests to fetch per section, at most 100
+    pub limit: usize,
+}
+
+impl PrStatusQuery {
+    /// The GitHub search queries of the authored, review requested and
+    /// assigned sections, most recently updated first.
+    pub fn search_queries(&self) -> [String; 3] {
+        let mut scope = String::from("is:pr is:open archived:false sort:updated-desc");
+        if let Some(ref repo) = self.repo {
+            scope.push_str(&format!(" repo:{}", repo));
+        }
+        if let Some(ref org) = self.org {
+            scope.push_str(&format!(" org:{}", org));
+        }
+        ["author:@me", "review-requested:@me", "assignee:@me"].map(|who| format!("{} {}", who, scope))
+    }
+}
+
+/// The pull requests that involve the authenticated user.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PrStatus {
+    pub authored: Vec<PullRequest>,
+    pub review_requested: Vec<PullRequest>,
+    pub assigned: Vec<PullRequest>,
+}
+
+impl PrStatus {
+    pub fn is_empty(&self) -> bool {
+        self.authored.is_empty() && self.review_requested.is_empty() && self.assigned.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr() -> PullRequest {
+        PullRequest {
+            id: "PR_1".into(),
+            repository: "acme/api".into(),
+            number: 7,
+            title: "Add retries".into(),
+            author: "octocat".into(),
+            state: PullRequestState::Open,
+            draft: false,
+            head_ref: "retries".into(),
+            head_sha: "abc123".into(),
+            head_repository: Some("acme/api".into()),
+            base_ref: "main".into(),
+            checks: CheckStatus::Success,
+            review_decision: Some(ReviewDecision::Approved),
+            mergeable: MergeableState::Mergeable,
+            merge_state: MergeStateStatus::Clean,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            html_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_blockers() {
+        assert!(pr().merge_blockers().is_empty());
+
+        let blocked = PullRequest {
+            merge_state: MergeStateStatus::Blocked,
+            checks: CheckStatus::Failure,
+            review_decision: Some(ReviewDecision::ReviewRequired),
+            ..pr()
+        };
+        assert_eq!(blocked.merge_blockers(), vec!["required checks are failing", "it needs an approving review"]);
+
+        let blocked = PullRequest { merge_state: MergeStateStatus::Blocked, ..pr() };
+        assert_eq!(blocked.merge_blockers(), vec!["the protection rules of main are not satisfied"]);
+
+        let conflicting = PullRequest { draft: true, mergeable: MergeableState::Conflicting, ..pr() };
+        assert_eq!(conflicting.merge_blockers(), vec!["it is a draft", "it conflicts with main"]);
+
+        // Failing checks that are not required do not block
+        let unstable = PullRequest { merge_state: MergeStateStatus::Unstable, checks: CheckStatus::Failure, ..pr() };
+        assert!(unstable.merge_blockers().is_empty());
+    }
+
+    #[test]
+    fn test_local_branch() {
+        assert_eq!(pr().local_branch(), "retries");
+        let fork = PullRequest { head_repository: Some("hubot/api".into()), ..pr() };
+        assert!(fork.is_from_fork());
+        assert_eq!(fork.local_branch(), "hubot/retries");
+        assert_eq!(PullRequest { head_repository: None, ..pr() }.local_branch(), "pr-7");
+    }
+
+    #[test]
+    fn test_list_query() {
+        assert_eq!(
+            PrListQuery::default().search_query(),
+            "is:pr archived:false is:open involves:@me sort:updated-desc"
+        );
+        let query = PrListQuery { state: PrStateFilter::All, author: Some("hubot".into()), review_requested: true, limit: 50 };
+        assert_eq!(query.search_query(), "is:pr archived:false author:hubot review-requested:@me sort:updated-desc");
+        let query = PrListQuery { state: PrStateFilter::Closed, ..Default::default() };
+        assert!(query.search_query().contains("is:closed is:unmerged"));
+        assert_eq!(PrStateFilter::All.next(), PrStateFilter::Open);
+    }
+
+    #[test]
+    fn test_review_needs_body() {
+        let review = |event, body: Option<&str>| NewReview { event, body: body.map(String::from) };
+        assert!(review(ReviewEvent::Approve, None).validate().is_ok());
+        assert!(review(ReviewEvent::RequestChanges, Some(" ")).validate().is_err());
+        assert!(review(ReviewEvent::Comment, None).validate().is_err());
+        assert!(review(ReviewEvent::Comment, Some("Looks good")).validate().is_ok());
+    }
+
+    #[test]
+    fn test_search_queries_are_scoped() {
+        let query = PrStatusQuery { repo: None, org: Some("acme".into()), limit: 20 };
+        let [authored, review, assigned] = query.search_queries();
+        assert_eq!(authored, "author:@me is:pr is:open archived:false sort:updated-desc org:acme");
+        assert!(review.starts_with("review-requested:@me is:pr"));
+        assert!(assigned.starts_with("assignee:@me is:pr"));
+
+        let query = PrStatusQuery { repo: "acme/api".parse().ok(), org: None, limit: 20 };
+        assert!(query.search_queries()[0].ends_with(" repo:acme/api"));
+    }
+}
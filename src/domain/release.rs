@@ -0,0 +1,248 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseAsset {
+    pub id: u64,
+    pub name: String,
+    pub size_in_bytes: u64,
+    pub download_count: u64,
+    pub content_type: String,
+    pub created_at: DateTime<Utc>,
+    pub browser_download_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub author: String,
+    pub created_at: Option<DateTime<Utc>>,
+    /// `None` for drafts
+    pub published_at: Option<DateTime<Utc>>,
+    pub html_url: String,
+    pub assets: Vec<ReleaseAsset>,
+}
+
+impl Release {
+    /// The release name, or its tag when it has none
+    pub fn title(&self) -> &str {
+        self.name.as_deref().filter(|n| !n.is_empty()).unwrap_or(&self.tag_name)
+    }
+
+    /// Publication date, or creation date for drafts
+    pub fn date(&self) -> Option<DateTime<Utc>> {
+        self.published_at.or(self.created_at)
+    }
+
+    pub fn assets_size(&self) -> u64 {
+        self.assets.iter().map(|a| a.size_in_bytes).sum()
+    }
+
+    /// Assets whose name matches any of `patterns` (all of them when empty)
+    pub fn matching_assets(&self, patterns: &[String]) -> Vec<&ReleaseAsset> {
+        self.assets
+            .iter()
+            .filter(|a| patterns.is_empty() || patterns.iter().any(|p| glob_match(p, &a.name)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewRelease {
+    pub tag: String,
+    /// Branch or commit to create the tag from when it does not exist yet
+    pub target: Option<String>,
+    pub name: Option<String>,
+    pub notes: Option<String>,
+    /// Let GitHub write the notes from the pull requests merged since the
+    /// previous release; `notes` are prepended to them
+    pub generate_notes: bool,
+    pub draft: bool,
+    pub prerelease: bool,
+}
+
+impl NewRelease {
+    pub fn validate(&self) -> Result<(), String> {
+        let tag = self.tag.trim();
+        if tag.is_empty() {
+            return Err("the tag cannot be empty".into());
+        }
+        if tag.contains(char::is_whitespace) {
+            return Err(format!("invalid tag '{}': tags cannot contain spaces", tag));
+        }
+        Ok(())
+    }
+}
+
+/// A local file to upload as a release asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetUpload {
+    pub path: PathBuf,
+    /// Asset name: the file name
+    pub name: String,
+    pub size_in_bytes: u64,
+    /// Existing asset with the same name, deleted once the file is uploaded
+    pub replaces: Option<u64>,
+}
+
+/// Called with the number of bytes transferred so far while an asset is
+/// uploaded or downloaded.
+pub type TransferProgress = Arc<dyn Fn(u64) + Send + Sync>;
+
+/// Which pre-releases `ghr release delete --prereleases` removes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PrereleaseCleanup {
+    /// Spare the N most recent pre-releases
+    pub keep: usize,
+    /// Only pre-releases published more than DAYS days ago
+    pub older_than_days: Option<i64>,
+}
+
+impl PrereleaseCleanup {
+    /// Published pre-releases to delete, oldest first. Drafts and full
+    /// releases are never selected.
+    pub fn select(&self, releases: &[Release], now: DateTime<Utc>) -> Vec<Release> {
+        let mut prereleases: Vec<&Release> = releases.iter().filter(|r| r.prerelease && !r.draft).collect();
+        prereleases.sort_by_key(|r| std::cmp::Reverse(r.date()));
+
+        let mut selected: Vec<Release> = prereleases
+            .into_iter()
+            .skip(self.keep)
+            .filter(|r| match (self.older_than_days, r.date()) {
+                (Some(days), Some(date)) => now - date >= Duration::days(days),
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .cloned()
+            .collect();
+        selected.reverse();
+        selected
+    }
+}
+
+/// Shell-style match of a file name: `*` matches any run of characters and
+/// `?` a single one.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and of the name when it was reached, to backtrack
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool, draft: bool, days_ago: i64, now: DateTime<Utc>) -> Release {
+        Release {
+            id: 1,
+            tag_name: tag.into(),
+            name: None,
+            body: None,
+            draft,
+            prerelease,
+            author: "octocat".into(),
+            created_at: Some(now - Duration::days(days_ago)),
+            published_at: if draft { None } else { Some(now - Duration::days(days_ago)) },
+            html_url: String::new(),
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.tar.gz", "ghr-linux.tar.gz"));
+        assert!(glob_match("ghr-*-x86_64*", "ghr-1.2.0-x86_64-linux.zip"));
+        assert!(glob_match("v?.txt", "v1.txt"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("exact", "exact"));
+        assert!(!glob_match("*.zip", "ghr.tar.gz"));
+        assert!(!glob_match("v?.txt", "v10.txt"));
+        assert!(!glob_match("ghr", "ghr.exe"));
+    }
+
+    #[test]
+    fn test_matching_assets() {
+        let now = Utc::now();
+        let asset = |name: &str| ReleaseAsset {
+            id: 1,
+            name: name.into(),
+            size_in_bytes: 10,
+            download_count: 0,
+            content_type: "application/octet-stream".into(),
+            created_at: now,
+            browser_download_url: String::new(),
+        };
+        let release = Release {
+            assets: vec![asset("ghr.zip"), asset("ghr.tar.gz"), asset("checksums.txt")],
+            ..release("v1.0.0", false, false, 0, now)
+        };
+
+        let names = |patterns: &[&str]| -> Vec<String> {
+            let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+            release.matching_assets(&patterns).iter().map(|a| a.name.clone()).collect()
+        };
+        assert_eq!(names(&[]).len(), 3);
+        assert_eq!(names(&["*.zip", "*.txt"]), vec!["ghr.zip", "checksums.txt"]);
+        assert!(names(&["*.deb"]).is_empty());
+        assert_eq!(release.assets_size(), 30);
+    }
+
+    #[test]
+    fn test_prerelease_cleanup() {
+        let now = Utc::now();
+        let releases = vec![
+            release("v2.0.0-rc.3", true, false, 1, now),
+            release("v2.0.0-rc.2", true, false, 10, now),
+            release("v1.0.0", false, false, 20, now),
+            release("v2.0.0-rc.1", true, false, 40, now),
+            release("v2.1.0-rc.1", true, true, 60, now),
+        ];
+        let tags = |cleanup: PrereleaseCleanup| -> Vec<String> {
+            cleanup.select(&releases, now).into_iter().map(|r| r.tag_name).collect()
+        };
+
+        assert_eq!(tags(PrereleaseCleanup::default()), vec!["v2.0.0-rc.1", "v2.0.0-rc.2", "v2.0.0-rc.3"]);
+        assert_eq!(tags(PrereleaseCleanup { keep: 1, older_than_days: None }), vec!["v2.0.0-rc.1", "v2.0.0-rc.2"]);
+        assert_eq!(tags(PrereleaseCleanup { keep: 0, older_than_days: Some(30) }), vec!["v2.0.0-rc.1"]);
+        assert!(tags(PrereleaseCleanup { keep: 3, older_than_days: None }).is_empty());
+    }
+
+    #[test]
+    fn test_new_release_validation() {
+        let release = NewRelease { tag: "v1.0.0".into(), ..Default::default() };
+        assert!(release.validate().is_ok());
+        assert!(NewRelease { tag: " ".into(), ..Default::default() }.validate().is_err());
+        assert!(NewRelease { tag: "v1 beta".into(), ..Default::default() }.validate().is_err());
+    }
+}
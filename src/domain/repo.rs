@@ -1,3 +1,5 @@
+use std::fmt;
+use std::str::FromStr;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +24,16 @@ pub struct Repository {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub pushed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Size in kilobytes as reported by GitHub
+    #[serde(default)]
+    pub size: u64,
+    /// SPDX identifier (e.g. `MIT`), or the license name when GitHub has none
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
 }
 
 impl From<octocrab::models::Repository> for Repository {
@@ -46,6 +58,10 @@ impl From<octocrab::models::Repository> for Repository {
             created_at: repo.created_at.unwrap_or_else(Utc::now),
             updated_at: repo.updated_at.unwrap_or_else(Utc::now),
             pushed_at: repo.pushed_at,
+            topics: repo.topics.unwrap_or_default(),
+            size: repo.size.unwrap_or(0) as u64,
+            license: repo.license.map(|l| if l.spdx_id.is_empty() || l.spdx_id == "NOASSERTION" { l.name } else { l.spdx_id }),
+            homepage: repo.homepage.filter(|h| !h.is_empty()),
         }
     }
 }
@@ -66,3 +82,316 @@ impl Repository {
         }
     }
 }
+
+/// Share of a language in the repository's code, as measured by GitHub
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LanguageShare {
+    pub name: String,
+    pub bytes: u64,
+    pub percentage: f64,
+}
+
+impl LanguageShare {
+    /// Percentages from the byte counts of `GET /repos/{owner}/{repo}/languages`,
+    /// largest first.
+    pub fn from_bytes(languages: Vec<(String, u64)>) -> Vec<Self> {
+        let total: u64 = languages.iter().map(|(_, bytes)| bytes).sum();
+        let mut shares: Vec<Self> = languages
+            .into_iter()
+            .map(|(name, bytes)| Self {
+                name,
+                bytes,
+                percentage: if total == 0 { 0.0 } else { bytes as f64 / total as f64 * 100.0 },
+            })
+            .collect();
+        shares.sort_by_key(|l| std::cmp::Reverse(l.bytes));
+        shares
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseSummary {
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub html_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkflowRunSummary {
+    pub name: String,
+    pub branch: String,
+    /// `queued`, `in_progress`, `completed`, ...
+    pub status: String,
+    /// `success`, `failure`, `cancelled`, ... once completed
+    pub conclusion: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub html_url: String,
+}
+
+/// Everything `ghr repo view` shows about a repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoDetails {
+    #[serde(flatten)]
+    pub repo: Repository,
+    pub languages: Vec<LanguageShare>,
+    /// Open issues, excluding pull requests when they could be counted
+    pub open_issues: u32,
+    /// `None` when the pull requests could not be counted, in which case
+    /// `open_issues` still includes them
+    pub open_pull_requests: Option<u32>,
+    pub latest_release: Option<ReleaseSummary>,
+    pub last_workflow_run: Option<WorkflowRunSummary>,
+    /// README source (usually Markdown)
+    pub readme: Option<String>,
+}
+
+/// Reference to a repository given as `owner/repo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoRef {
+    pub owner: String,
+    pub name: String,
+}
+
+impl FromStr for RepoRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => Ok(Self {
+                owner: owner.to_string(),
+                name: name.to_string(),
+            }),
+            _ => Err(format!("invalid repository '{}' (expected owner/repo)", s)),
+        }
+    }
+}
+
+impl fmt::Display for RepoRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+/// A repository to create, either empty (optionally initialized with a
+/// `.gitignore` and license) or generated from a template repository.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NewRepository {
+    pub name: String,
+    /// Organization to create it in; the authenticated user when `None`
+    pub org: Option<String>,
+    pub description: Option<String>,
+    pub private: bool,
+    pub template: Option<RepoRef>,
+    /// Name of a GitHub `.gitignore` template such as `Rust`
+    pub gitignore_template: Option<String>,
+    /// License keyword such as `mit` or `apache-2.0`
+    pub license_template: Option<String>,
+}
+
+impl NewRepository {
+    /// Catch what GitHub would reject before any API call.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() || self.name == "." || self.name == ".." {
+            return Err(format!("invalid repository name '{}'", self.name));
+        }
+        if let Some(c) = self.name.chars().find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))) {
+            return Err(format!(
+                "invalid repository name '{}' ('{}' is not allowed, use letters, digits, '-', '_' or '.')",
+                self.name, c
+            ));
+        }
+        if self.template.is_some() && (self.gitignore_template.is_some() || self.license_template.is_some()) {
+            return Err("a .gitignore or license cannot be added to a repository generated from a template".into());
+        }
+        Ok(())
+    }
+}
+
+/// Which repositories of a user or organization to list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OwnerRepoType {
+    /// Every repository the owner has (all of an org's, or owned by a user)
+    #[default]
+    All,
+    /// Repositories the owner is a member of (for a user) or can access as a member (for an org)
+    Member,
+    /// Repositories that are not forks
+    Sources,
+}
+
+impl FromStr for OwnerRepoType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(Self::All),
+            "member" => Ok(Self::Member),
+            "sources" => Ok(Self::Sources),
+            _ => Err(format!("invalid repository type '{}' (expected all, member or sources)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoSort {
+    Name,
+    Stars,
+    Updated,
+    Pushed,
+    Size,
+}
+
+impl RepoSort {
+    pub const ALL: [RepoSort; 5] = [RepoSort::Name, RepoSort::Stars, RepoSort::Updated, RepoSort::Pushed, RepoSort::Size];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RepoSort::Name => "name",
+            RepoSort::Stars => "stars",
+            RepoSort::Updated => "updated",
+            RepoSort::Pushed => "pushed",
+            RepoSort::Size => "size",
+        }
+    }
+
+    fn compare(&self, a: &Repository, b: &Repository) -> std::cmp::Ordering {
+        match self {
+            RepoSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            RepoSort::Stars => a.stargazers_count.cmp(&b.stargazers_count),
+            RepoSort::Updated => a.updated_at.cmp(&b.updated_at),
+            RepoSort::Pushed => a.pushed_at.cmp(&b.pushed_at),
+            RepoSort::Size => a.size.cmp(&b.size),
+        }
+    }
+}
+
+impl FromStr for RepoSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RepoSort::ALL
+            .into_iter()
+            .find(|sort| sort.label().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("invalid sort '{}' (expected name, stars, updated, pushed or size)", s))
+    }
+}
+
+/// Filters, ordering and limit applied to a list of repositories.
+///
+/// Unset fields do not filter anything, so `RepoQuery::default()` only hides
+/// archived repositories.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepoQuery {
+    pub include_archived: bool,
+    /// `Some(true)` for private repositories only, `Some(false)` for public ones
+    pub private: Option<bool>,
+    /// `Some(true)` for forks only, `Some(false)` for source repositories
+    pub fork: Option<bool>,
+    pub language: Option<String>,
+    pub topic: Option<String>,
+    /// Case-insensitive match on the name or description
+    pub text: Option<String>,
+    pub pushed_before: Option<DateTime<Utc>>,
+    pub pushed_after: Option<DateTime<Utc>>,
+    pub min_stars: Option<u32>,
+    pub sort: Option<RepoSort>,
+    pub descending: bool,
+    pub limit: Option<usize>,
+}
+
+impl RepoQuery {
+    pub fn matches(&self, repo: &Repository) -> bool {
+        let eq = |expected: &Option<String>, actual: Option<&str>| match expected {
+            Some(e) => actual.map(|a| a.eq_ignore_ascii_case(e)).unwrap_or(false),
+            None => true,
+        };
+
+        (self.include_archived || !repo.archived)
+            && self.private.map(|p| repo.private == p).unwrap_or(true)
+            && self.fork.map(|f| repo.fork == f).unwrap_or(true)
+            && eq(&self.language, repo.language.as_deref())
+            && self.topic.as_ref().map(|t| repo.topics.iter().any(|rt| rt.eq_ignore_ascii_case(t))).unwrap_or(true)
+            && self.text.as_ref().map(|t| {
+                let t = t.to_lowercase();
+                repo.name.to_lowercase().contains(&t)
+                    || repo.description.as_ref().map(|d| d.to_lowercase().contains(&t)).unwrap_or(false)
+            }).unwrap_or(true)
+            // Repositories that were never pushed match neither date bound
+            && self.pushed_before.map(|d| repo.pushed_at.map(|p| p < d).unwrap_or(false)).unwrap_or(true)
+            && self.pushed_after.map(|d| repo.pushed_at.map(|p| p > d).unwrap_or(false)).unwrap_or(true)
+            && self.min_stars.map(|m| repo.stargazers_count >= m).unwrap_or(true)
+    }
+
+    /// Matching repositories, sorted and truncated to the limit.
+    pub fn apply<'a>(&self, repos: impl IntoIterator<Item = &'a Repository>) -> Vec<&'a Repository> {
+        let mut matched: Vec<&Repository> = repos.into_iter().filter(|r| self.matches(r)).collect();
+        if let Some(sort) = self.sort {
+            matched.sort_by(|a, b| {
+                let ordering = sort.compare(a, b);
+                if self.descending { ordering.reverse() } else { ordering }
+            });
+        }
+        if let Some(limit) = self.limit {
+            matched.truncate(limit);
+        }
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repo_ref() {
+        let r: RepoRef = "octocat/hello-world".parse().unwrap();
+        assert_eq!(r.owner, "octocat");
+        assert_eq!(r.name, "hello-world");
+        assert_eq!(r.to_string(), "octocat/hello-world");
+
+        assert!("octocat".parse::<RepoRef>().is_err());
+        assert!("/repo".parse::<RepoRef>().is_err());
+        assert!("a/b/c".parse::<RepoRef>().is_err());
+    }
+
+    #[test]
+    fn test_language_shares() {
+        let shares = LanguageShare::from_bytes(vec![("Shell".into(), 250), ("Rust".into(), 750)]);
+        assert_eq!(shares[0].name, "Rust");
+        assert_eq!(shares[0].percentage, 75.0);
+        assert_eq!(shares[1].percentage, 25.0);
+        assert!(LanguageShare::from_bytes(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_owner_repo_type() {
+        assert_eq!("Sources".parse::<OwnerRepoType>(), Ok(OwnerRepoType::Sources));
+        assert!("forks".parse::<OwnerRepoType>().unwrap_err().contains("expected all, member or sources"));
+    }
+
+    #[test]
+    fn test_parse_repo_sort() {
+        assert_eq!("Stars".parse::<RepoSort>(), Ok(RepoSort::Stars));
+        assert!("popularity".parse::<RepoSort>().unwrap_err().contains("expected name, stars"));
+    }
+
+    #[test]
+    fn test_repo_query_text_and_topic() {
+        let repo = Repository {
+            name: "Billing-API".into(),
+            description: Some("Invoices service".into()),
+            topics: vec!["rust".into(), "payments".into()],
+            ..Default::default()
+        };
+
+        let query = RepoQuery { text: Some("invoice".into()), ..Default::default() };
+        assert!(query.matches(&repo));
+        let query = RepoQuery { topic: Some("Payments".into()), ..Default::default() };
+        assert!(query.matches(&repo));
+        let query = RepoQuery { topic: Some("go".into()), ..Default::default() };
+        assert!(!query.matches(&repo));
+        let query = RepoQuery { language: Some("rust".into()), ..Default::default() };
+        assert!(!query.matches(&repo));
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::error::{GhTuiError, Result};
+
+/// Absolute limit accepted by GitHub for artifact and log retention
+pub const MAX_RETENTION_DAYS: u32 = 400;
+
+/// Response of `GET /{repos/{owner}/{repo}|orgs/{org}}/actions/permissions/artifact-and-log-retention`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub days: u32,
+    /// Upper bound set by the organization or enterprise
+    #[serde(default)]
+    pub maximum_allowed_days: Option<u32>,
+}
+
+impl RetentionPolicy {
+    /// Check that `days` can be applied under this policy's limit.
+    pub fn validate(&self, days: u32) -> Result<()> {
+        validate_days(days)?;
+        match self.maximum_allowed_days {
+            Some(max) if days > max => Err(GhTuiError::InvalidInput(format!(
+                "retention of {} days exceeds the maximum of {} days allowed by the organization",
+                days, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn validate_days(days: u32) -> Result<()> {
+    if days == 0 || days > MAX_RETENTION_DAYS {
+        return Err(GhTuiError::InvalidInput(format!(
+            "retention must be between 1 and {} days (got {})",
+            MAX_RETENTION_DAYS, days
+        )));
+    }
+    Ok(())
+}
+
+/// Outcome of setting the retention of one repository in bulk mode
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetentionChange {
+    /// `owner/repo`
+    pub repo: String,
+    pub previous_days: Option<u32>,
+    pub days: u32,
+    pub status: RetentionChangeStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RetentionChangeStatus {
+    Updated,
+    Unchanged,
+    /// Dry-run: would be updated
+    Pending,
+    Failed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policy() {
+        let policy: RetentionPolicy = serde_json::from_str(r#"{"days": 90, "maximum_allowed_days": 90}"#).unwrap();
+        assert_eq!(policy.days, 90);
+        assert_eq!(policy.maximum_allowed_days, Some(90));
+
+        let policy: RetentionPolicy = serde_json::from_str(r#"{"days": 30}"#).unwrap();
+        assert_eq!(policy.maximum_allowed_days, None);
+    }
+
+    #[test]
+    fn test_validate_days() {
+        let policy = RetentionPolicy { days: 90, maximum_allowed_days: Some(90) };
+        assert!(policy.validate(30).is_ok());
+        assert!(policy.validate(91).is_err());
+        assert!(policy.validate(0).is_err());
+        assert!(validate_days(401).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+use std::ops::Range;
+use serde::Serialize;
+use crate::domain::issue::Issue;
+use crate::domain::repo::Repository;
+
+/// GitHub search never returns more than this many results for a query.
+pub const SEARCH_RESULT_CAP: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchKind {
+    #[default]
+    Issues,
+    PullRequests,
+    Repositories,
+    Code,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 4] = [
+        SearchKind::Issues,
+        SearchKind::PullRequests,
+        SearchKind::Repositories,
+        SearchKind::Code,
+    ];
+
+    /// The next kind, wrapping around.
+    pub fn next(&self) -> SearchKind {
+        let i = Self::ALL.iter().position(|k| k == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for SearchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchKind::Issues => write!(f, "issues"),
+            SearchKind::PullRequests => write!(f, "pull requests"),
+            SearchKind::Repositories => write!(f, "repositories"),
+            SearchKind::Code => write!(f, "code"),
+        }
+    }
+}
+
+/// A search as typed by the user: free text and qualifiers such as
+/// `author:@me state:open org:acme`, passed through to GitHub.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    pub kind: SearchKind,
+    pub query: String,
+    /// Results to fetch, at most `SEARCH_RESULT_CAP`
+    pub limit: usize,
+}
+
+impl SearchQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.query.trim().is_empty() {
+            return Err("the search query cannot be empty".into());
+        }
+        if self.kind == SearchKind::Code && self.terms().is_empty() {
+            return Err("code search needs at least one search term besides qualifiers".into());
+        }
+        Ok(())
+    }
+
+    /// The query sent to GitHub. Issues and pull requests share one endpoint,
+    /// so `is:issue` or `is:pr` is added unless the query already picks one.
+    pub fn api_query(&self) -> String {
+        let picks_type = |token: &str| {
+            let token = token.to_lowercase();
+            ["is:issue", "is:pr", "type:issue", "type:pr"].contains(&token.as_str())
+        };
+        let qualifier = match self.kind {
+            SearchKind::Issues => "is:issue",
+            SearchKind::PullRequests => "is:pr",
+            SearchKind::Repositories | SearchKind::Code => return self.query.trim().to_string(),
+        };
+        if self.query.split_whitespace().any(picks_type) {
+            self.query.trim().to_string()
+        } else {
+            format!("{} {}", self.query.trim(), qualifier)
+        }
+    }
+
+    /// Free-text words and quoted phrases of the query, without qualifiers,
+    /// exclusions and boolean operators: what results are highlighted with.
+    pub fn terms(&self) -> Vec<String> {
+        let mut terms = Vec::new();
+        let mut rest = self.query.as_str();
+        while let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+            rest = &rest[start..];
+            let (token, tail) = if let Some(quoted) = rest.strip_prefix('"') {
+                match quoted.find('"') {
+                    Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                    None => (quoted, ""),
+                }
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let token = &rest[..end];
+                // A qualifier's value may be quoted: label:"good first issue"
+                if let Some(colon) = token.find(":\"") {
+                    let value = colon + 2;
+                    let close = rest[value..].find('"').map(|i| value + i + 1).unwrap_or(rest.len());
+                    rest = &rest[close..];
+                    continue;
+                }
+                if token.contains(':') || token.starts_with('-') || ["AND", "OR", "NOT"].contains(&token) {
+                    rest = &rest[end..];
+                    continue;
+                }
+                (token, &rest[end..])
+            };
+            if !token.is_empty() {
+                terms.push(token.to_string());
+            }
+            rest = tail;
+        }
+        terms
+    }
+}
+
+/// An issue or pull request found by search, in any repository.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueHit {
+    /// `owner/repo`
+    pub repository: String,
+    pub pull_request: bool,
+    #[serde(flatten)]
+    pub issue: Issue,
+}
+
+/// A file found by code search.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CodeHit {
+    /// `owner/repo`
+    pub repository: String,
+    pub path: String,
+    pub html_url: String,
+    /// Excerpt of the file around the first match
+    pub fragment: Option<String>,
+}
+
+/// One page of results as returned by the search API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchPage<T> {
+    /// Matches on GitHub, possibly more than could be fetched
+    pub total_count: u64,
+    /// GitHub timed out and some matches may be missing
+    pub incomplete_results: bool,
+    pub items: Vec<T>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SearchHits {
+    Issues(Vec<IssueHit>),
+    Repositories(Vec<Repository>),
+    Code(Vec<CodeHit>),
+}
+
+impl SearchHits {
+    pub fn len(&self) -> usize {
+        match self {
+            SearchHits::Issues(hits) => hits.len(),
+            SearchHits::Repositories(hits) => hits.len(),
+            SearchHits::Code(hits) => hits.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResults {
+    pub total_count: u64,
+    pub incomplete_results: bool,
+    pub hits: SearchHits,
+    /// See `SearchQuery::terms`
+    pub terms: Vec<String>,
+}
+
+/// Byte ranges of `text` matching any of `terms`, case-insensitively,
+/// sorted and merged where they overlap.
+pub fn highlight_ranges(text: &str, terms: &[String]) -> Vec<Range<usize>> {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths outside ASCII; only highlight when it did not
+    if lower.len() != text.len() {
+        return Vec::new();
+    }
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for term in terms.iter().map(|t| t.to_lowercase()).filter(|t| !t.is_empty()) {
+        let mut from = 0;
+        while let Some(i) = lower[from..].find(&term) {
+            let start = from + i;
+            ranges.push(start..start + term.len());
+            from = start + term.len();
+        }
+    }
+    ranges.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(kind: SearchKind, query: &str) -> SearchQuery {
+        SearchQuery { kind, query: query.into(), limit: 30 }
+    }
+
+    #[test]
+    fn test_api_query_adds_type() {
+        assert_eq!(query(SearchKind::PullRequests, "author:@me state:open").api_query(), "author:@me state:open is:pr");
+        assert_eq!(query(SearchKind::Issues, " crash ").api_query(), "crash is:issue");
+        assert_eq!(query(SearchKind::Issues, "crash is:PR").api_query(), "crash is:PR");
+        assert_eq!(query(SearchKind::Code, "fn main language:rust").api_query(), "fn main language:rust");
+    }
+
+    #[test]
+    fn test_terms_skip_qualifiers() {
+        let q = query(SearchKind::Issues, "panic \"out of memory\" label:\"good first issue\" -label:wontfix NOT flaky org:acme");
+        assert_eq!(q.terms(), vec!["panic", "out of memory", "flaky"]);
+        assert!(query(SearchKind::Code, "org:acme language:rust").validate().is_err());
+        assert!(query(SearchKind::Issues, "org:acme").validate().is_ok());
+        assert!(query(SearchKind::Issues, "  ").validate().is_err());
+    }
+
+    #[test]
+    fn test_highlight_ranges() {
+        let terms = vec!["cache".to_string(), "Cache miss".to_string()];
+        assert_eq!(highlight_ranges("Fix cache miss in CACHE layer", &terms), vec![4..14, 18..23]);
+        assert!(highlight_ranges("nothing here", &terms).is_empty());
+    }
+
+    #[test]
+    fn test_kind_cycles() {
+        assert_eq!(SearchKind::Code.next(), SearchKind::Issues);
+        assert_eq!(SearchKind::Issues.next(), SearchKind::PullRequests);
+    }
+}
@@ -0,0 +1,313 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::domain::lifecycle::RepoActionStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    Public,
+    Private,
+    /// Visible to members of the enterprise (GitHub Enterprise only)
+    Internal,
+}
+
+impl FromStr for Visibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "public" => Ok(Visibility::Public),
+            "private" => Ok(Visibility::Private),
+            "internal" => Ok(Visibility::Internal),
+            _ => Err(format!("invalid visibility '{}' (expected public, private or internal)", s)),
+        }
+    }
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Visibility::Public => write!(f, "public"),
+            Visibility::Private => write!(f, "private"),
+            Visibility::Internal => write!(f, "internal"),
+        }
+    }
+}
+
+/// Editable settings of a repository. As a desired state, `None` leaves the
+/// setting alone; read from GitHub, `None` means the token cannot see it.
+///
+/// This is also the format of settings files:
+///
+/// ```toml
+/// delete_branch_on_merge = true
+/// allow_merge_commit = false
+/// has_wiki = false
+/// topics = ["backend"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoSettings {
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub topics: Option<Vec<String>>,
+    pub visibility: Option<Visibility>,
+    pub default_branch: Option<String>,
+    pub allow_merge_commit: Option<bool>,
+    pub allow_squash_merge: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+    pub delete_branch_on_merge: Option<bool>,
+    pub has_issues: Option<bool>,
+    pub has_wiki: Option<bool>,
+    pub has_projects: Option<bool>,
+    /// Read from GitHub only: whether secret scanning is enabled, `None` when
+    /// the token cannot see `security_and_analysis` (admins only)
+    #[serde(skip)]
+    pub secret_scanning: Option<bool>,
+}
+
+impl From<octocrab::models::Repository> for RepoSettings {
+    fn from(repo: octocrab::models::Repository) -> Self {
+        let visibility = repo
+            .visibility
+            .as_deref()
+            .and_then(|v| v.parse().ok())
+            .or(repo.private.map(|p| if p { Visibility::Private } else { Visibility::Public }));
+        Self {
+            // GitHub returns null for an empty description or homepage
+            description: Some(repo.description.unwrap_or_default()),
+            homepage: Some(repo.homepage.unwrap_or_default()),
+            topics: Some(repo.topics.unwrap_or_default()),
+            visibility,
+            default_branch: repo.default_branch,
+            allow_merge_commit: repo.allow_merge_commit,
+            allow_squash_merge: repo.allow_squash_merge,
+            allow_rebase_merge: repo.allow_rebase_merge,
+            delete_branch_on_merge: repo.delete_branch_on_merge,
+            has_issues: repo.has_issues,
+            has_wiki: repo.has_wiki,
+            has_projects: repo.has_projects,
+            // octocrab's Repository model has no security_and_analysis
+            secret_scanning: None,
+        }
+    }
+}
+
+/// One setting whose current value differs from the desired one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingChange {
+    pub setting: &'static str,
+    pub current: String,
+    pub desired: String,
+}
+
+impl RepoSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `self` with the settings of `overrides` replacing its own, e.g. command
+    /// line flags on top of a settings file.
+    pub fn merge(self, overrides: RepoSettings) -> Self {
+        Self {
+            description: overrides.description.or(self.description),
+            homepage: overrides.homepage.or(self.homepage),
+            topics: overrides.topics.or(self.topics),
+            visibility: overrides.visibility.or(self.visibility),
+            default_branch: overrides.default_branch.or(self.default_branch),
+            allow_merge_commit: overrides.allow_merge_commit.or(self.allow_merge_commit),
+            allow_squash_merge: overrides.allow_squash_merge.or(self.allow_squash_merge),
+            allow_rebase_merge: overrides.allow_rebase_merge.or(self.allow_rebase_merge),
+            delete_branch_on_merge: overrides.delete_branch_on_merge.or(self.delete_branch_on_merge),
+            has_issues: overrides.has_issues.or(self.has_issues),
+            has_wiki: overrides.has_wiki.or(self.has_wiki),
+            has_projects: overrides.has_projects.or(self.has_projects),
+            secret_scanning: overrides.secret_scanning.or(self.secret_scanning),
+        }
+    }
+
+    /// Catch what GitHub would reject before any API call.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allow_merge_commit == Some(false) && self.allow_squash_merge == Some(false) && self.allow_rebase_merge == Some(false) {
+            return Err("at least one of merge commits, squash merging or rebase merging must stay enabled".into());
+        }
+        if let Some(topic) = self.topics.iter().flatten().find(|t| !is_valid_topic(t)) {
+            return Err(format!(
+                "invalid topic '{}' (use lowercase letters, digits and '-', at most 50 characters)",
+                topic
+            ));
+        }
+        if self.default_branch.as_deref() == Some("") {
+            return Err("the default branch cannot be empty".into());
+        }
+        Ok(())
+    }
+
+    /// Desired settings (`self`) that differ from `current`. Topics are
+    /// compared as sets.
+    pub fn diff(&self, current: &RepoSettings) -> Vec<SettingChange> {
+        let mut changes = Vec::new();
+        let mut check = |setting: &'static str, desired: Option<String>, current: Option<String>| {
+            if let Some(desired) = desired {
+                if Some(&desired) != current.as_ref() {
+                    changes.push(SettingChange {
+                        setting,
+                        current: current.unwrap_or_else(|| "?".into()),
+                        desired,
+                    });
+                }
+            }
+        };
+
+        check("description", self.description.clone(), current.description.clone());
+        check("homepage", self.homepage.clone(), current.homepage.clone());
+        check("topics", self.topics.as_deref().map(topic_list), current.topics.as_deref().map(topic_list));
+        check("visibility", show(self.visibility), show(current.visibility));
+        check("default_branch", self.default_branch.clone(), current.default_branch.clone());
+        check("allow_merge_commit", show(self.allow_merge_commit), show(current.allow_merge_commit));
+        check("allow_squash_merge", show(self.allow_squash_merge), show(current.allow_squash_merge));
+        check("allow_rebase_merge", show(self.allow_rebase_merge), show(current.allow_rebase_merge));
+        check("delete_branch_on_merge", show(self.delete_branch_on_merge), show(current.delete_branch_on_merge));
+        check("has_issues", show(self.has_issues), show(current.has_issues));
+        check("has_wiki", show(self.has_wiki), show(current.has_wiki));
+        check("has_projects", show(self.has_projects), show(current.has_projects));
+        changes
+    }
+
+    /// The subset of `self` named in `changes`, i.e. what to send to GitHub.
+    pub fn only(&self, changes: &[SettingChange]) -> Self {
+        let keep = |setting: &str| changes.iter().any(|c| c.setting == setting);
+        Self {
+            description: self.description.clone().filter(|_| keep("description")),
+            homepage: self.homepage.clone().filter(|_| keep("homepage")),
+            topics: self.topics.clone().filter(|_| keep("topics")),
+            visibility: self.visibility.filter(|_| keep("visibility")),
+            default_branch: self.default_branch.clone().filter(|_| keep("default_branch")),
+            allow_merge_commit: self.allow_merge_commit.filter(|_| keep("allow_merge_commit")),
+            allow_squash_merge: self.allow_squash_merge.filter(|_| keep("allow_squash_merge")),
+            allow_rebase_merge: self.allow_rebase_merge.filter(|_| keep("allow_rebase_merge")),
+            delete_branch_on_merge: self.delete_branch_on_merge.filter(|_| keep("delete_branch_on_merge")),
+            has_issues: self.has_issues.filter(|_| keep("has_issues")),
+            has_wiki: self.has_wiki.filter(|_| keep("has_wiki")),
+            has_projects: self.has_projects.filter(|_| keep("has_projects")),
+            secret_scanning: None,
+        }
+    }
+}
+
+fn show<T: ToString>(value: Option<T>) -> Option<String> {
+    value.map(|v| v.to_string())
+}
+
+fn topic_list(topics: &[String]) -> String {
+    let mut sorted = topics.to_vec();
+    sorted.sort();
+    sorted.dedup();
+    sorted.join(", ")
+}
+
+fn is_valid_topic(topic: &str) -> bool {
+    !topic.is_empty()
+        && topic.len() <= 50
+        && !topic.starts_with('-')
+        && topic.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// The settings to change on one repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsPlan {
+    pub owner: String,
+    pub name: String,
+    pub changes: Vec<SettingChange>,
+    /// Only the settings in `changes`
+    #[serde(skip)]
+    pub update: RepoSettings,
+    /// `Pending` until applied, `Unchanged` when already compliant and
+    /// `Failed` when the current settings could not be read
+    pub status: RepoActionStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current() -> RepoSettings {
+        RepoSettings {
+            description: Some("API".into()),
+            homepage: Some(String::new()),
+            topics: Some(vec!["rust".into(), "api".into()]),
+            visibility: Some(Visibility::Public),
+            default_branch: Some("master".into()),
+            allow_merge_commit: Some(true),
+            allow_squash_merge: Some(true),
+            allow_rebase_merge: Some(true),
+            delete_branch_on_merge: Some(false),
+            has_issues: Some(true),
+            has_wiki: Some(true),
+            has_projects: None,
+            secret_scanning: Some(false),
+        }
+    }
+
+    #[test]
+    fn test_diff_only_reports_desired_changes() {
+        let desired = RepoSettings {
+            description: Some("API".into()),
+            topics: Some(vec!["api".into(), "rust".into()]),
+            default_branch: Some("main".into()),
+            delete_branch_on_merge: Some(true),
+            has_projects: Some(false),
+            ..Default::default()
+        };
+        let changes = desired.diff(&current());
+        assert_eq!(
+            changes,
+            vec![
+                SettingChange { setting: "default_branch", current: "master".into(), desired: "main".into() },
+                SettingChange { setting: "delete_branch_on_merge", current: "false".into(), desired: "true".into() },
+                SettingChange { setting: "has_projects", current: "?".into(), desired: "false".into() },
+            ]
+        );
+        assert!(RepoSettings::default().diff(&current()).is_empty());
+
+        let update = desired.only(&changes);
+        assert_eq!(update.description, None);
+        assert_eq!(update.default_branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_settings_file_and_merge() {
+        let file: RepoSettings = toml::from_str(
+            "visibility = \"private\"\nallow_merge_commit = false\nhas_wiki = false\ntopics = [\"backend\"]\n",
+        )
+        .unwrap();
+        assert_eq!(file.visibility, Some(Visibility::Private));
+
+        let flags = RepoSettings { has_wiki: Some(true), description: Some("Billing".into()), ..Default::default() };
+        let merged = file.merge(flags);
+        assert_eq!(merged.has_wiki, Some(true));
+        assert_eq!(merged.allow_merge_commit, Some(false));
+        assert_eq!(merged.description.as_deref(), Some("Billing"));
+
+        let err = toml::from_str::<RepoSettings>("has_wikis = false").unwrap_err();
+        assert!(err.to_string().contains("unknown field"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate() {
+        let no_merge = RepoSettings {
+            allow_merge_commit: Some(false),
+            allow_squash_merge: Some(false),
+            allow_rebase_merge: Some(false),
+            ..Default::default()
+        };
+        assert!(no_merge.validate().is_err());
+
+        let bad_topic = RepoSettings { topics: Some(vec!["Rust Lang".into()]), ..Default::default() };
+        assert!(bad_topic.validate().unwrap_err().contains("'Rust Lang'"));
+
+        assert!(current().validate().is_ok());
+        assert!("secret".parse::<Visibility>().is_err());
+    }
+}
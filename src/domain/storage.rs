@@ -1,9 +1,22 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::domain::billing::{AccountStorage, QuotaOverrides};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum StorageItemType {
     Artifact,
     Cache,
+    /// Only scanned on request; release assets are not billed as Actions storage
+    ReleaseAsset,
+}
+
+impl StorageItemType {
+    /// Whether the item counts towards the Actions storage quota
+    pub fn is_actions_storage(&self) -> bool {
+        !matches!(self, StorageItemType::ReleaseAsset)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,10 +27,264 @@ pub struct StorageItem {
     pub repo: String,
     pub size_in_bytes: u64,
     pub item_type: StorageItemType,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Artifacts are deleted by GitHub at this date; caches have no fixed expiry
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StorageItem {
+    /// Name without the "Artifact: " / "Cache: " / "Release: " prefix
+    pub fn base_name(&self) -> &str {
+        self.name
+            .strip_prefix("Artifact: ")
+            .or_else(|| self.name.strip_prefix("Cache: "))
+            .or_else(|| self.name.strip_prefix("Release: "))
+            .unwrap_or(&self.name)
+    }
+}
+
+/// Dimension used to aggregate storage items
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StorageGrouping {
+    Repo,
+    Name,
+    Type,
+    Owner,
+}
+
+impl StorageGrouping {
+    pub fn key(&self, item: &StorageItem) -> String {
+        match self {
+            StorageGrouping::Repo => format!("{}/{}", item.owner, item.repo),
+            StorageGrouping::Name => item.base_name().to_string(),
+            StorageGrouping::Type => format!("{:?}", item.item_type),
+            StorageGrouping::Owner => item.owner.clone(),
+        }
+    }
+}
+
+impl FromStr for StorageGrouping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "repo" => Ok(StorageGrouping::Repo),
+            "name" => Ok(StorageGrouping::Name),
+            "type" => Ok(StorageGrouping::Type),
+            "owner" => Ok(StorageGrouping::Owner),
+            other => Err(format!("invalid grouping '{}' (expected repo, name, type or owner)", other)),
+        }
+    }
 }
 
+/// Aggregated usage of the items sharing the same grouping key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageGroup {
+    pub key: String,
+    pub count: usize,
+    pub total_bytes: u64,
+    /// Share of the bytes of all the grouped items
+    pub percentage: f64,
+}
+
+/// Aggregate items by `grouping`, largest groups first.
+pub fn group_items(items: &[StorageItem], grouping: StorageGrouping) -> Vec<StorageGroup> {
+    let total: u64 = items.iter().map(|i| i.size_in_bytes).sum();
+    let mut groups: HashMap<String, StorageGroup> = HashMap::new();
+
+    for item in items {
+        let key = grouping.key(item);
+        let group = groups.entry(key.clone()).or_insert_with(|| StorageGroup {
+            key,
+            count: 0,
+            total_bytes: 0,
+            percentage: 0.0,
+        });
+        group.count += 1;
+        group.total_bytes += item.size_in_bytes;
+    }
+
+    let mut groups: Vec<StorageGroup> = groups
+        .into_values()
+        .map(|mut g| {
+            g.percentage = if total > 0 { g.total_bytes as f64 / total as f64 * 100.0 } else { 0.0 };
+            g
+        })
+        .collect();
+    groups.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes).then_with(|| a.key.cmp(&b.key)));
+    groups
+}
+
+#[derive(Debug, Clone)]
 pub struct StorageUsageReport {
-    pub total_used: u64,
-    pub max_allowed: u64,
+    pub accounts: Vec<AccountStorage>,
     pub items: Vec<StorageItem>,
 }
+
+impl StorageUsageReport {
+    /// Build a report, attributing scanned items to the account that owns them.
+    /// Items owned by other accounts (e.g. repos you collaborate on) and
+    /// release assets are listed but do not count towards your quota.
+    pub fn new(mut accounts: Vec<AccountStorage>, items: Vec<StorageItem>) -> Self {
+        for account in &mut accounts {
+            account.scanned_bytes = items
+                .iter()
+                .filter(|i| i.item_type.is_actions_storage() && i.owner.eq_ignore_ascii_case(&account.account))
+                .map(|i| i.size_in_bytes)
+                .sum();
+        }
+        Self { accounts, items }
+    }
+
+    /// Items counting towards the Actions storage quota
+    pub fn actions_items(&self) -> Vec<StorageItem> {
+        self.items.iter().filter(|i| i.item_type.is_actions_storage()).cloned().collect()
+    }
+
+    pub fn total_used(&self) -> u64 {
+        self.accounts.iter().map(|a| a.used_bytes()).sum()
+    }
+
+    /// Sum of the known quotas; accounts without a known quota are ignored.
+    pub fn max_allowed(&self) -> u64 {
+        self.accounts.iter().filter_map(|a| a.quota_bytes()).sum()
+    }
+
+    /// Usage of the accounts counted by `max_allowed`, to compare with it
+    pub fn used_with_known_quota(&self) -> u64 {
+        self.accounts.iter().filter(|a| a.quota_bytes().is_some()).map(|a| a.used_bytes()).sum()
+    }
+
+    /// Items of `actions_items` owned by `account`
+    pub fn account_items(&self, account: &str) -> Vec<StorageItem> {
+        self.items
+            .iter()
+            .filter(|i| i.item_type.is_actions_storage() && i.owner.eq_ignore_ascii_case(account))
+            .cloned()
+            .collect()
+    }
+
+    /// Highest usage among the accounts with a known quota
+    pub fn max_usage_percentage(&self) -> Option<f64> {
+        self.accounts
+            .iter()
+            .filter_map(|a| a.usage_percentage())
+            .fold(None, |max, p| Some(max.map_or(p, |m: f64| m.max(p))))
+    }
+
+    pub fn apply_quota_overrides(&mut self, overrides: &QuotaOverrides) {
+        for account in &mut self.accounts {
+            if let Some(quota) = overrides.for_account(&account.account) {
+                account.quota_override = Some(quota);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::billing::AccountType;
+
+    fn item(owner: &str, size: u64) -> StorageItem {
+        StorageItem {
+            id: 1,
+            name: "Artifact: build".into(),
+            owner: owner.into(),
+            repo: "repo".into(),
+            size_in_bytes: size,
+            item_type: StorageItemType::Artifact,
+            created_at: None,
+            expires_at: None,
+        }
+    }
+
+    fn named(name: &str, repo: &str, size: u64, item_type: StorageItemType) -> StorageItem {
+        StorageItem {
+            name: name.into(),
+            repo: repo.into(),
+            item_type,
+            ..item("acme", size)
+        }
+    }
+
+    #[test]
+    fn test_report_attributes_items_to_accounts() {
+        let mut user = AccountStorage::new("octocat", AccountType::User);
+        user.quota_override = Some(1000);
+        let org = AccountStorage::new("acme", AccountType::Organization);
+
+        let report = StorageUsageReport::new(
+            vec![user, org],
+            vec![item("octocat", 100), item("Acme", 250), item("someone-else", 4000)],
+        );
+
+        assert_eq!(report.accounts[0].scanned_bytes, 100);
+        assert_eq!(report.accounts[1].scanned_bytes, 250);
+        assert_eq!(report.total_used(), 350);
+        assert_eq!(report.max_allowed(), 1000);
+        // acme has no known quota, so only octocat is compared with it
+        assert_eq!(report.used_with_known_quota(), 100);
+        assert_eq!(report.account_items("acme").len(), 1);
+        assert_eq!(report.max_usage_percentage(), Some(10.0));
+        assert_eq!(report.items.len(), 3);
+    }
+
+    #[test]
+    fn test_release_assets_do_not_count_towards_quota() {
+        let account = AccountStorage::new("acme", AccountType::Organization);
+        let report = StorageUsageReport::new(
+            vec![account],
+            vec![item("acme", 100), named("Release: ghr.tar.gz", "cli", 5000, StorageItemType::ReleaseAsset)],
+        );
+
+        assert_eq!(report.accounts[0].scanned_bytes, 100);
+        assert_eq!(report.items.len(), 2);
+        assert_eq!(report.actions_items().len(), 1);
+        assert_eq!(report.items[1].base_name(), "ghr.tar.gz");
+    }
+
+    #[test]
+    fn test_group_items_by_name() {
+        let items = vec![
+            named("Artifact: coverage-report", "api", 300, StorageItemType::Artifact),
+            named("Artifact: coverage-report", "web", 200, StorageItemType::Artifact),
+            named("Artifact: coverage-report", "cli", 100, StorageItemType::Artifact),
+            named("Cache: cargo-linux", "cli", 400, StorageItemType::Cache),
+        ];
+
+        let groups = group_items(&items, StorageGrouping::Name);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].key, "coverage-report");
+        assert_eq!(groups[0].count, 3);
+        assert_eq!(groups[0].total_bytes, 600);
+        assert_eq!(groups[0].percentage, 60.0);
+        assert_eq!(groups[1].key, "cargo-linux");
+    }
+
+    #[test]
+    fn test_group_items_by_repo_and_type() {
+        let items = vec![
+            named("Artifact: a", "api", 100, StorageItemType::Artifact),
+            named("Cache: b", "api", 100, StorageItemType::Cache),
+            named("Cache: c", "web", 300, StorageItemType::Cache),
+        ];
+
+        let by_repo = group_items(&items, StorageGrouping::Repo);
+        assert_eq!(by_repo[0].key, "acme/web");
+        assert_eq!(by_repo[1].key, "acme/api");
+        assert_eq!(by_repo[1].count, 2);
+
+        let by_type = group_items(&items, StorageGrouping::Type);
+        assert_eq!(by_type[0].key, "Cache");
+        assert_eq!(by_type[0].total_bytes, 400);
+    }
+
+    #[test]
+    fn test_parse_grouping() {
+        assert_eq!("Owner".parse::<StorageGrouping>(), Ok(StorageGrouping::Owner));
+        assert!("size".parse::<StorageGrouping>().is_err());
+    }
+}
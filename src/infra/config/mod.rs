@@ -1,11 +1,17 @@
 pub mod storage;
 
+use crate::domain::audit::AuditRules;
+use crate::domain::billing::QuotaOverrides;
 use crate::domain::error::{GhTuiError, Result};
+use crate::domain::git::{CloneUrl, GitProtocol};
+use crate::domain::protection::BranchProtection;
+use crate::domain::settings::RepoSettings;
 use dirs::config_dir;
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 const APP_NAME: &str = "ghr";
@@ -15,6 +21,10 @@ const SERVICE_NAME: &str = "ghr-token";
 pub struct Config {
     pub user: Option<UserConfig>,
     pub preferences: Preferences,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub git: GitConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,10 +48,81 @@ impl Default for Preferences {
     }
 }
 
+/// Actions storage settings, used when the billing API is not accessible
+/// (e.g. the token lacks the `user` or `admin:org` scope).
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct StorageConfig {
+    /// Quota in MB for every account without a specific entry in `quotas_mb`
+    pub default_quota_mb: Option<u64>,
+    /// Quota in MB per account (user or organization login)
+    #[serde(default)]
+    pub quotas_mb: HashMap<String, u64>,
+    /// Save a usage snapshot on every `ghr df` run (see `ghr df history`)
+    #[serde(default)]
+    pub record_history: bool,
+    /// Also scan release assets in `ghr df` and the TUI Storage Manager
+    #[serde(default)]
+    pub include_release_assets: bool,
+}
+
+impl StorageConfig {
+    pub fn quota_overrides(&self) -> QuotaOverrides {
+        const MB: u64 = 1024 * 1024;
+        QuotaOverrides {
+            default: self.default_quota_mb.map(|q| q * MB),
+            accounts: self.quotas_mb.iter().map(|(k, v)| (k.clone(), v * MB)).collect(),
+        }
+    }
+}
+
+/// Settings for `ghr repo clone`.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct GitConfig {
+    /// `https` (default) or `ssh`
+    #[serde(default)]
+    pub protocol: GitProtocol,
+    /// Clone from a mirror instead of GitHub, e.g. `file:///srv/mirrors/{owner}/{name}.git`
+    pub clone_url: Option<String>,
+}
+
+impl GitConfig {
+    /// The clone URL scheme, with `protocol` (e.g. from the command line) taking
+    /// precedence over the configured one.
+    pub fn clone_url(&self, protocol: Option<GitProtocol>) -> CloneUrl {
+        match (protocol, &self.clone_url) {
+            (Some(p), _) => CloneUrl::Protocol(p),
+            (None, Some(template)) => CloneUrl::Template(template.clone()),
+            (None, None) => CloneUrl::Protocol(self.protocol),
+        }
+    }
+}
+
 pub struct ConfigManager {
     config_path: PathBuf,
 }
 
+/// Read a repository settings file (the format of `RepoSettings`), as used by
+/// `ghr repo edit --settings`.
+pub fn load_repo_settings(path: &Path) -> Result<RepoSettings> {
+    load_toml(path, "settings file")
+}
+
+/// Read the rules of `ghr audit repos --rules`.
+pub fn load_audit_rules(path: &Path) -> Result<AuditRules> {
+    load_toml(path, "rules file")
+}
+
+/// Read the protection file of `ghr protect apply --from`.
+pub fn load_branch_protection(path: &Path) -> Result<BranchProtection> {
+    load_toml(path, "protection file")
+}
+
+fn load_toml<T: serde::de::DeserializeOwned>(path: &Path, kind: &str) -> Result<T> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| GhTuiError::Config(format!("Cannot read {}: {}", path.display(), e)))?;
+    toml::from_str(&content).map_err(|e| GhTuiError::Config(format!("Invalid {} {}: {}", kind, path.display(), e)))
+}
+
 impl ConfigManager {
     pub fn new() -> Result<Self> {
         let config_path = Self::get_config_path()?;
@@ -169,9 +250,66 @@ impl ConfigManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_load_repo_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("standard.toml");
+        fs::write(&path, "delete_branch_on_merge = true\nhas_wiki = false\n").unwrap();
+        let settings = load_repo_settings(&path).unwrap();
+        assert_eq!(settings.delete_branch_on_merge, Some(true));
+        assert_eq!(settings.has_wiki, Some(false));
+
+        fs::write(&path, "wiki = false\n").unwrap();
+        let err = load_repo_settings(&path).unwrap_err();
+        assert!(err.to_string().contains("Invalid settings file"), "{}", err);
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
         assert_eq!(config.preferences.theme, "dark");
     }
+
+    #[test]
+    fn test_storage_quota_overrides() {
+        let config: Config = toml::from_str(
+            r#"
+            [preferences]
+            theme = "dark"
+            default_view = "repos"
+
+            [storage]
+            default_quota_mb = 500
+
+            [storage.quotas_mb]
+            acme = 2048
+            "#,
+        )
+        .unwrap();
+
+        let overrides = config.storage.quota_overrides();
+        assert_eq!(overrides.for_account("acme"), Some(2048 * 1024 * 1024));
+        assert_eq!(overrides.for_account("octocat"), Some(500 * 1024 * 1024));
+    }
+
+    #[test]
+    fn test_config_without_storage_section() {
+        let config: Config = toml::from_str("[preferences]\ntheme = \"light\"\ndefault_view = \"repos\"\n").unwrap();
+        assert!(config.storage.default_quota_mb.is_none());
+        assert!(config.storage.quotas_mb.is_empty());
+        assert_eq!(config.git.clone_url(None), CloneUrl::Protocol(GitProtocol::Https));
+    }
+
+    #[test]
+    fn test_git_clone_url() {
+        let config: Config = toml::from_str(
+            "[preferences]\ntheme = \"dark\"\ndefault_view = \"repos\"\n\n[git]\nprotocol = \"ssh\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.git.clone_url(None), CloneUrl::Protocol(GitProtocol::Ssh));
+        assert_eq!(config.git.clone_url(Some(GitProtocol::Https)), CloneUrl::Protocol(GitProtocol::Https));
+
+        let git = GitConfig { clone_url: Some("file:///m/{owner}/{name}.git".into()), ..Default::default() };
+        assert_eq!(git.clone_url(None), CloneUrl::Template("file:///m/{owner}/{name}.git".into()));
+    }
 }
@@ -1 +1,85 @@
-// Place holder
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::history::{StorageHistoryRepository, StorageSnapshot};
+use dirs::data_dir;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+const APP_NAME: &str = "ghr";
+const HISTORY_FILE: &str = "storage_history.jsonl";
+
+/// Storage snapshots persisted as JSON lines in the ghr data directory.
+pub struct FileStorageHistory {
+    path: PathBuf,
+}
+
+impl FileStorageHistory {
+    pub fn new() -> Result<Self> {
+        let mut path = data_dir()
+            .ok_or_else(|| GhTuiError::Config("Cannot find data directory".into()))?;
+        path.push(APP_NAME);
+        path.push(HISTORY_FILE);
+        Ok(Self::with_path(path))
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl StorageHistoryRepository for FileStorageHistory {
+    fn append(&self, snapshot: &StorageSnapshot) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<StorageSnapshot>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        let mut snapshots = content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<std::result::Result<Vec<StorageSnapshot>, _>>()?;
+        snapshots.sort_by_key(|s| s.taken_at);
+        Ok(snapshots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn snapshot(total_used: u64, days_ago: i64) -> StorageSnapshot {
+        StorageSnapshot {
+            taken_at: Utc::now() - Duration::days(days_ago),
+            total_used,
+            accounts: Vec::new(),
+            repos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_append_and_load_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = FileStorageHistory::with_path(dir.path().join("nested").join(HISTORY_FILE));
+        assert!(history.load().unwrap().is_empty());
+
+        history.append(&snapshot(200, 1)).unwrap();
+        history.append(&snapshot(100, 3)).unwrap();
+
+        let loaded = history.load().unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].total_used, 100);
+        assert_eq!(loaded[1].total_used, 200);
+    }
+}
@@ -0,0 +1,149 @@
+use std::path::Path;
+use std::process::Command;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::git::GitOperations;
+
+/// Runs the `git` executable, so the user's credentials and SSH setup apply.
+#[derive(Default)]
+pub struct GitCli;
+
+impl GitCli {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Run git, returning its standard output.
+    fn run(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            // Parallel clones must fail instead of waiting for a password prompt
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .map_err(|e| GhTuiError::Git(format!("cannot run git: {}", e)))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr
+                .lines()
+                .rev()
+                .find(|l| !l.trim().is_empty())
+                .unwrap_or("git exited with an error");
+            Err(GhTuiError::Git(message.trim().to_string()))
+        }
+    }
+}
+
+impl GitOperations for GitCli {
+    fn clone_repo(&self, url: &str, dest: &Path) -> Result<()> {
+        self.run(&["clone", "--quiet", url, &dest.to_string_lossy()]).map(|_| ())
+    }
+
+    fn fetch(&self, repo_dir: &Path) -> Result<()> {
+        self.run(&["-C", &repo_dir.to_string_lossy(), "fetch", "--quiet", "--all", "--prune"]).map(|_| ())
+    }
+
+    fn remote_url(&self, repo_dir: &Path, remote: &str) -> Result<String> {
+        self.run(&["-C", &repo_dir.to_string_lossy(), "remote", "get-url", remote])
+    }
+
+    fn fetch_ref(&self, repo_dir: &Path, remote: &str, refspec: &str) -> Result<()> {
+        self.run(&["-C", &repo_dir.to_string_lossy(), "fetch", "--quiet", remote, refspec]).map(|_| ())
+    }
+
+    fn checkout_branch(&self, repo_dir: &Path, branch: &str, start_point: &str) -> Result<()> {
+        let dir = repo_dir.to_string_lossy();
+        let local_ref = format!("refs/heads/{}", branch);
+        if self.run(&["-C", &dir, "show-ref", "--verify", "--quiet", &local_ref]).is_err() {
+            return self.run(&["-C", &dir, "checkout", "--quiet", "-b", branch, start_point]).map(|_| ());
+        }
+        self.run(&["-C", &dir, "checkout", "--quiet", branch])?;
+        self.run(&["-C", &dir, "merge", "--quiet", "--ff-only", start_point])
+            .map(|_| ())
+            .map_err(|_| GhTuiError::Git(format!("local branch '{}' has diverged from {}, pick another name with --branch", branch, start_point)))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "ghr")
+            .env("GIT_AUTHOR_EMAIL", "ghr@example.com")
+            .env("GIT_COMMITTER_NAME", "ghr")
+            .env("GIT_COMMITTER_EMAIL", "ghr@example.com")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// Create `root/{owner}/{name}.git` as a bare repository with one commit.
+    pub(crate) fn bare_repo(root: &Path, owner: &str, name: &str) -> PathBuf {
+        let bare = root.join(owner).join(format!("{}.git", name));
+        let work = root.join("work").join(owner).join(name);
+
+        std::fs::create_dir_all(&bare).unwrap();
+        std::fs::create_dir_all(&work).unwrap();
+        git(&bare, &["init", "--quiet", "--bare", "--initial-branch=main"]);
+        git(&work, &["init", "--quiet"]);
+        std::fs::write(work.join("README.md"), format!("# {}\n", name)).unwrap();
+        git(&work, &["add", "README.md"]);
+        git(&work, &["commit", "--quiet", "-m", "Initial commit"]);
+        git(&work, &["push", "--quiet", &bare.to_string_lossy(), "HEAD:refs/heads/main"]);
+        bare
+    }
+
+    #[test]
+    fn test_clone_and_fetch_local_bare_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let bare = bare_repo(dir.path(), "acme", "api");
+        let dest = dir.path().join("clones").join("api");
+
+        let git = GitCli::new();
+        git.clone_repo(&format!("file://{}", bare.display()), &dest).unwrap();
+        assert!(dest.join("README.md").exists());
+        git.fetch(&dest).unwrap();
+
+        let err = git.clone_repo(&format!("file://{}", dir.path().join("missing.git").display()), &dir.path().join("x")).unwrap_err();
+        assert!(matches!(err, GhTuiError::Git(_)));
+    }
+
+    #[test]
+    fn test_checkout_pull_request_ref() {
+        let dir = tempfile::tempdir().unwrap();
+        let bare = bare_repo(dir.path(), "acme", "api");
+        let work = dir.path().join("work").join("acme").join("api");
+        let push_pr_commit = |message: &str| {
+            git(&work, &["commit", "--quiet", "--allow-empty", "-m", message]);
+            git(&work, &["push", "--quiet", "--force", &bare.to_string_lossy(), "HEAD:refs/pull/1/head"]);
+            git(&work, &["rev-parse", "HEAD"])
+        };
+        let first = push_pr_commit("Add retries");
+
+        let clone = dir.path().join("clone");
+        let cli = GitCli::new();
+        cli.clone_repo(&format!("file://{}", bare.display()), &clone).unwrap();
+        assert!(cli.remote_url(&clone, "origin").unwrap().ends_with("api.git"));
+
+        cli.fetch_ref(&clone, "origin", "refs/pull/1/head").unwrap();
+        cli.checkout_branch(&clone, "retries", "FETCH_HEAD").unwrap();
+        assert_eq!(git(&clone, &["rev-parse", "HEAD"]), first);
+        assert_eq!(git(&clone, &["branch", "--show-current"]), "retries");
+
+        // Checking out again fast-forwards the existing branch
+        let second = push_pr_commit("Address review");
+        git(&clone, &["checkout", "--quiet", "main"]);
+        cli.fetch_ref(&clone, "origin", "refs/pull/1/head").unwrap();
+        cli.checkout_branch(&clone, "retries", "FETCH_HEAD").unwrap();
+        assert_eq!(git(&clone, &["rev-parse", "HEAD"]), second);
+
+        assert!(matches!(cli.remote_url(&clone, "upstream"), Err(GhTuiError::Git(_))));
+    }
+}
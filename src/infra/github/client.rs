@@ -1,12 +1,24 @@
+use std::path::Path;
 use async_trait::async_trait;
 use octocrab::{Octocrab, Page};
 use crate::domain::artifact::Artifact;
-use crate::domain::repo::Repository;
+use crate::domain::branch::{Branch, BranchPullRequest, PullRequestState};
+use crate::domain::issue::{CloseReason, Issue, IssueComment, IssueQuery, IssueState, NewIssue};
+use crate::domain::repo::{NewRepository, OwnerRepoType, ReleaseSummary, RepoRef, Repository, WorkflowRunSummary};
+use crate::domain::protection::{BranchProtection, Ruleset};
+use crate::domain::settings::RepoSettings;
+use crate::domain::search::{CodeHit, IssueHit, SearchPage, SEARCH_RESULT_CAP};
+use crate::domain::pull_request::{MergeMethod, NewReview, PrListQuery, PrStatus, PrStatusQuery, PullRequest, PullRequestDetails};
+use crate::domain::release::{AssetUpload, NewRelease, Release, ReleaseAsset, TransferProgress};
+use crate::infra::github::{issue, protection, pull_request, release, search};
 use crate::domain::user::User;
 use crate::domain::github::GithubRepository;
-use crate::domain::error::Result;
+use crate::domain::error::{GhTuiError, Result};
 use crate::domain::storage::StorageUsageReport;
+use crate::domain::retention::RetentionPolicy;
+use crate::domain::billing::{AccountStorage, AccountType, ActionsBilling, SharedStorageBilling};
 
+#[derive(Clone)]
 pub struct GitHubClient {
     client: Octocrab,
 }
@@ -19,6 +31,179 @@ impl GitHubClient {
         
         Ok(Self { client })
     }
+
+    /// Fetch the billing data of a user or organization. Each endpoint
+    /// requires its own scope (`user` or `admin:org`), so missing data is left
+    /// empty rather than failing the whole report.
+    async fn account_storage(&self, account: &str, account_type: AccountType) -> AccountStorage {
+        let prefix = match account_type {
+            AccountType::User => "users",
+            AccountType::Organization => "orgs",
+        };
+        let mut storage = AccountStorage::new(account, account_type);
+
+        let route = format!("/{}/{}/settings/billing/shared-storage", prefix, account);
+        storage.shared_storage = self.client.get::<SharedStorageBilling, _, _>(route, None::<&()>).await.ok();
+
+        let route = format!("/{}/{}/settings/billing/actions", prefix, account);
+        storage.actions = self.client.get::<ActionsBilling, _, _>(route, None::<&()>).await.ok();
+
+        storage
+    }
+
+    /// Fetch up to `limit` results of a search endpoint, 100 per page.
+    async fn search(
+        &self,
+        endpoint: &str,
+        query: &str,
+        limit: usize,
+        headers: Option<reqwest::header::HeaderMap>,
+    ) -> Result<SearchPage<serde_json::Value>> {
+        let limit = limit.min(SEARCH_RESULT_CAP);
+        let per_page = limit.clamp(1, 100).to_string();
+        let mut results = SearchPage { total_count: 0, incomplete_results: false, items: Vec::new() };
+        let mut page_num = 1u32;
+        while results.items.len() < limit {
+            let page = page_num.to_string();
+            let params = [("q", query), ("per_page", per_page.as_str()), ("page", page.as_str())];
+            let page: serde_json::Value = self.client.get_with_headers(endpoint, Some(&params), headers.clone()).await?;
+            let items = page["items"].as_array().cloned().unwrap_or_default();
+            results.total_count = page["total_count"].as_u64().unwrap_or_default();
+            results.incomplete_results |= page["incomplete_results"] == true;
+            let done = items.is_empty() || results.items.len() + items.len() >= results.total_count as usize;
+            results.items.extend(items);
+            if done {
+                break;
+            }
+            page_num += 1;
+        }
+        results.items.truncate(limit);
+        Ok(results)
+    }
+
+    /// The `pullRequest` object returned by a GraphQL `query` taking `owner`,
+    /// `name` and `number`.
+    async fn pull_request_node(&self, owner: &str, repo: &str, number: u64, query: &str) -> Result<serde_json::Value> {
+        let not_found = || GhTuiError::NotFound(format!("pull request #{} of {}/{}", number, owner, repo));
+        let mut data: serde_json::Value = self
+            .client
+            .graphql(&serde_json::json!({
+                "query": query,
+                "variables": { "owner": owner, "name": repo, "number": number },
+            }))
+            .await
+            .map_err(|e| if is_unresolved(&e) { not_found() } else { e.into() })?;
+        match data["repository"]["pullRequest"].take() {
+            serde_json::Value::Null => Err(not_found()),
+            node => Ok(node),
+        }
+    }
+
+    /// Whether `owner` is a user or an organization, resolved from `/users/{owner}`
+    /// which answers for both.
+    async fn account_type(&self, owner: &str) -> Result<AccountType> {
+        #[derive(serde::Deserialize)]
+        struct Owner {
+            #[serde(rename = "type")]
+            kind: String,
+        }
+
+        let route = format!("/users/{}", owner);
+        match self.client.get::<Owner, _, _>(route, None::<&()>).await {
+            Ok(o) if o.kind == "Organization" => Ok(AccountType::Organization),
+            // Bots and mannequins list their repositories like users
+            Ok(_) => Ok(AccountType::User),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("no user or organization named '{}'", owner))),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Bytes read from a file at a time while uploading a release asset
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Branches with their last commit, comparison with the default branch
+/// (`$default`) and latest pull request, 50 at a time
+const BRANCHES_QUERY: &str = r#"
+query($owner: String!, $name: String!, $default: String!, $cursor: String) {
+  repository(owner: $owner, name: $name) {
+    refs(refPrefix: "refs/heads/", first: 50, after: $cursor) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        name
+        target { ... on Commit { oid committedDate author { name user { login } } } }
+        compare(headRef: $default) { aheadBy behindBy }
+        associatedPullRequests(first: 1, orderBy: {field: UPDATED_AT, direction: DESC}) {
+          nodes { number state isDraft headRefOid }
+        }
+      }
+    }
+  }
+}"#;
+
+/// The three sections of `ghr pr status`, each a search for up to `$first`
+/// pull requests (followed by `pull_request::PULL_REQUEST_FIELDS`)
+const PR_STATUS_QUERY: &str = r#"
+query($authored: String!, $reviewRequested: String!, $assigned: String!, $first: Int!) {
+  authored: search(query: $authored, type: ISSUE, first: $first) { nodes { ...PullRequestFields } }
+  reviewRequested: search(query: $reviewRequested, type: ISSUE, first: $first) { nodes { ...PullRequestFields } }
+  assigned: search(query: $assigned, type: ISSUE, first: $first) { nodes { ...PullRequestFields } }
+}"#;
+
+/// Pull requests matching a search (followed by `pull_request::PULL_REQUEST_FIELDS`)
+const PULL_REQUEST_SEARCH_QUERY: &str = r#"
+query($query: String!, $first: Int!) {
+  search(query: $query, type: ISSUE, first: $first) { nodes { ...PullRequestFields } }
+}"#;
+
+/// One pull request (followed by `pull_request::PULL_REQUEST_FIELDS`)
+const PULL_REQUEST_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) { ...PullRequestFields }
+  }
+}"#;
+
+/// One pull request with its details (followed by both fragments)
+const PULL_REQUEST_DETAILS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) { ...PullRequestDetailsFields }
+  }
+}"#;
+
+const ENABLE_AUTO_MERGE_MUTATION: &str = r#"
+mutation($id: ID!, $method: PullRequestMergeMethod!) {
+  enablePullRequestAutoMerge(input: { pullRequestId: $id, mergeMethod: $method }) { clientMutationId }
+}"#;
+
+fn branch_from_graphql(node: &serde_json::Value, default_branch: &str, protected: &[String]) -> Branch {
+    let name = node["name"].as_str().unwrap_or_default().to_string();
+    let commit = &node["target"];
+    let author = &commit["author"];
+    let pull_request = node["associatedPullRequests"]["nodes"][0].as_object().map(|pr| BranchPullRequest {
+        number: pr["number"].as_u64().unwrap_or_default(),
+        state: match (pr["state"].as_str(), pr["isDraft"].as_bool()) {
+            (Some("MERGED"), _) => PullRequestState::Merged,
+            (Some("CLOSED"), _) => PullRequestState::Closed,
+            (_, Some(true)) => PullRequestState::Draft,
+            _ => PullRequestState::Open,
+        },
+        head_sha: pr["headRefOid"].as_str().map(String::from),
+    });
+
+    Branch {
+        is_default: name == default_branch,
+        protected: protected.contains(&name),
+        last_commit_sha: commit["oid"].as_str().map(String::from),
+        last_commit_at: commit["committedDate"].as_str().and_then(|d| d.parse().ok()),
+        last_commit_author: author["user"]["login"].as_str().or(author["name"].as_str()).map(String::from),
+        // The branch is the base of the comparison, the default branch its head
+        ahead: node["compare"]["behindBy"].as_u64().unwrap_or_default() as u32,
+        behind: node["compare"]["aheadBy"].as_u64().unwrap_or_default() as u32,
+        pull_request,
+        name,
+    }
 }
 
 #[async_trait]
@@ -53,46 +238,726 @@ impl GithubRepository for GitHubClient {
         Ok(repos)
     }
 
-    async fn list_repos_for(&self, owner: &str) -> Result<Vec<Repository>> {
-        let mut repos = Vec::new();
-        let mut page_num = 1;
-        
+    async fn list_repos_for(&self, owner: &str, repo_type: OwnerRepoType) -> Result<Vec<Repository>> {
+        let (route, type_param) = match self.account_type(owner).await? {
+            AccountType::Organization => {
+                let type_param = match repo_type {
+                    OwnerRepoType::All => "all",
+                    OwnerRepoType::Member => "member",
+                    OwnerRepoType::Sources => "sources",
+                };
+                (format!("/orgs/{}/repos", owner), type_param)
+            }
+            AccountType::User => {
+                // Users have no "sources" type: list owned repos and drop forks below
+                let type_param = match repo_type {
+                    OwnerRepoType::All => "all",
+                    OwnerRepoType::Member => "member",
+                    OwnerRepoType::Sources => "owner",
+                };
+                (format!("/users/{}/repos", owner), type_param)
+            }
+        };
+
+        let params = [("type", type_param), ("per_page", "100")];
+        let page: Page<octocrab::models::Repository> = self.client.get(route, Some(&params)).await?;
+        let mut repos: Vec<Repository> = self
+            .client
+            .all_pages(page)
+            .await?
+            .into_iter()
+            .map(Repository::from)
+            .collect();
+
+        if repo_type == OwnerRepoType::Sources {
+            repos.retain(|r| !r.fork);
+        }
+        Ok(repos)
+    }
+
+    async fn get_repo(&self, owner: &str, repo: &str) -> Result<Repository> {
+        match self.client.repos(owner, repo).get().await {
+            Ok(r) => Ok(Repository::from(r)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("repository '{}/{}'", owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn get_repo_languages(&self, owner: &str, repo: &str) -> Result<Vec<(String, u64)>> {
+        let route = format!("/repos/{}/{}/languages", owner, repo);
+        let languages: serde_json::Map<String, serde_json::Value> = self.client.get(route, None::<&()>).await?;
+        Ok(languages
+            .into_iter()
+            .map(|(name, bytes)| (name, bytes.as_u64().unwrap_or(0)))
+            .collect())
+    }
+
+    async fn count_open_pull_requests(&self, owner: &str, repo: &str) -> Result<u32> {
+        #[derive(serde::Deserialize)]
+        struct SearchCount {
+            total_count: u32,
+        }
+
+        let query = format!("repo:{}/{} type:pr state:open", owner, repo);
+        let params = [("q", query.as_str()), ("per_page", "1")];
+        let result: SearchCount = self.client.get("/search/issues", Some(&params)).await?;
+        Ok(result.total_count)
+    }
+
+    async fn get_latest_release(&self, owner: &str, repo: &str) -> Result<Option<ReleaseSummary>> {
+        match self.client.repos(owner, repo).releases().get_latest().await {
+            Ok(release) => Ok(Some(ReleaseSummary {
+                tag_name: release.tag_name,
+                name: release.name.filter(|n| !n.is_empty()),
+                published_at: release.published_at,
+                html_url: release.html_url.to_string(),
+            })),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn get_last_workflow_run(&self, owner: &str, repo: &str) -> Result<Option<WorkflowRunSummary>> {
+        #[derive(serde::Deserialize)]
+        struct Runs {
+            workflow_runs: Vec<Run>,
+        }
+        #[derive(serde::Deserialize)]
+        struct Run {
+            name: Option<String>,
+            head_branch: Option<String>,
+            status: Option<String>,
+            conclusion: Option<String>,
+            created_at: chrono::DateTime<chrono::Utc>,
+            html_url: String,
+        }
+
+        let route = format!("/repos/{}/{}/actions/runs", owner, repo);
+        let runs: Runs = self.client.get(route, Some(&[("per_page", "1")])).await?;
+        Ok(runs.workflow_runs.into_iter().next().map(|run| WorkflowRunSummary {
+            name: run.name.unwrap_or_default(),
+            branch: run.head_branch.unwrap_or_default(),
+            status: run.status.unwrap_or_default(),
+            conclusion: run.conclusion,
+            created_at: run.created_at,
+            html_url: run.html_url,
+        }))
+    }
+
+    async fn get_readme(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        match self.client.repos(owner, repo).get_readme().send().await {
+            Ok(content) => Ok(content.decoded_content()),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn create_repo(&self, new_repo: &NewRepository) -> Result<Repository> {
+        let mut body = serde_json::json!({
+            "name": new_repo.name,
+            "private": new_repo.private,
+        });
+        if let Some(ref description) = new_repo.description {
+            body["description"] = description.as_str().into();
+        }
+
+        let route = match new_repo.template {
+            Some(ref template) => {
+                if let Some(ref org) = new_repo.org {
+                    body["owner"] = org.as_str().into();
+                }
+                format!("/repos/{}/{}/generate", template.owner, template.name)
+            }
+            None => {
+                if let Some(ref gitignore) = new_repo.gitignore_template {
+                    body["gitignore_template"] = gitignore.as_str().into();
+                }
+                if let Some(ref license) = new_repo.license_template {
+                    body["license_template"] = license.as_str().into();
+                }
+                match new_repo.org {
+                    Some(ref org) => format!("/orgs/{}/repos", org),
+                    None => "/user/repos".to_string(),
+                }
+            }
+        };
+
+        let created: octocrab::models::Repository = match self.client.post(route, Some(&body)).await {
+            Ok(r) => r,
+            Err(e) if is_not_found(&e) => {
+                return Err(GhTuiError::NotFound(match new_repo.template {
+                    Some(ref t) => format!("template repository '{}'", t),
+                    None => format!("organization '{}'", new_repo.org.as_deref().unwrap_or_default()),
+                }))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Repository::from(created))
+    }
+
+    async fn fork_repo(&self, owner: &str, repo: &str, org: Option<String>) -> Result<Repository> {
+        let route = format!("/repos/{owner}/{repo}/forks");
+        let body = match org {
+            Some(ref org) => serde_json::json!({ "organization": org }),
+            None => serde_json::json!({}),
+        };
+        // 202 Accepted: the fork is returned right away while GitHub copies the git data
+        match self.client.post::<_, octocrab::models::Repository>(route, Some(&body)).await {
+            Ok(r) => Ok(Repository::from(r)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("repository '{}/{}'", owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn get_repo_settings(&self, owner: &str, repo: &str) -> Result<RepoSettings> {
+        let route = format!("/repos/{owner}/{repo}");
+        let value: serde_json::Value = match self.client.get(route, None::<&()>).await {
+            Ok(value) => value,
+            Err(e) if is_not_found(&e) => return Err(GhTuiError::NotFound(format!("repository '{}/{}'", owner, repo))),
+            Err(e) => return Err(e.into()),
+        };
+        let mut settings = RepoSettings::from(serde_json::from_value::<octocrab::models::Repository>(value.clone())?);
+        // Absent without admin rights; without a `secret_scanning` entry the feature is off
+        settings.secret_scanning = value["security_and_analysis"]
+            .as_object()
+            .map(|security| security.get("secret_scanning").and_then(|s| s["status"].as_str()) == Some("enabled"));
+        Ok(settings)
+    }
+
+    async fn update_repo_settings(&self, owner: &str, repo: &str, settings: &RepoSettings) -> Result<()> {
+        // Topics have their own endpoint and are ignored by the repository update
+        let mut body = serde_json::to_value(RepoSettings { topics: None, ..settings.clone() })?;
+        if let serde_json::Value::Object(ref mut fields) = body {
+            fields.retain(|_, v| !v.is_null());
+            if !fields.is_empty() {
+                let route = format!("/repos/{owner}/{repo}");
+                let resp = self.client._patch(route, Some(&body)).await?;
+                octocrab::map_github_error(resp).await?;
+            }
+        }
+
+        if let Some(ref topics) = settings.topics {
+            let route = format!("/repos/{owner}/{repo}/topics");
+            let resp = self.client._put(route, Some(&serde_json::json!({ "names": topics }))).await?;
+            octocrab::map_github_error(resp).await?;
+        }
+        Ok(())
+    }
+
+    async fn is_branch_protected(&self, owner: &str, repo: &str, branch: &str) -> Result<bool> {
+        let route = format!("/repos/{owner}/{repo}/branches/{}", path_segment(branch));
+        let branch: serde_json::Value = self.client.get(route, None::<&()>).await?;
+        Ok(branch["protected"].as_bool().unwrap_or(false))
+    }
+
+    async fn get_branch_protection(&self, owner: &str, repo: &str, branch: &str) -> Result<Option<BranchProtection>> {
+        let route = format!("/repos/{owner}/{repo}/branches/{}/protection", path_segment(branch));
+        match self.client.get::<serde_json::Value, _, _>(route, None::<&()>).await {
+            Ok(value) => Ok(Some(protection::protection_from_json(&value))),
+            // "Branch not protected"
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn set_branch_protection(&self, owner: &str, repo: &str, branch: &str, rules: &BranchProtection) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}/branches/{}/protection", path_segment(branch));
+        // The PUT replaces everything, so start from what is there
+        let current = match self.client.get::<serde_json::Value, _, _>(&route, None::<&()>).await {
+            Ok(value) => Some(value),
+            Err(e) if is_not_found(&e) => None,
+            Err(e) => return Err(e.into()),
+        };
+        let resp = self.client._put(route, Some(&protection::protection_to_json(rules, current.as_ref()))).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+
+    async fn list_rulesets(&self, owner: &str, repo: &str) -> Result<Vec<Ruleset>> {
+        let route = format!("/repos/{owner}/{repo}/rulesets");
+        let params = [("includes_parents", "false"), ("per_page", "100")];
+        let page: Page<serde_json::Value> = self.client.get(route, Some(&params)).await?;
+        let summaries = self.client.all_pages(page).await?;
+
+        // The list has no rules, they come with each ruleset
+        let mut rulesets = Vec::new();
+        for summary in summaries.iter().filter(|r| r["target"] == "branch") {
+            if let Some(id) = summary["id"].as_u64() {
+                let route = format!("/repos/{owner}/{repo}/rulesets/{id}");
+                let value: serde_json::Value = self.client.get(route, None::<&()>).await?;
+                rulesets.push(protection::ruleset_from_json(&value));
+            }
+        }
+        Ok(rulesets)
+    }
+
+    async fn save_ruleset(&self, owner: &str, repo: &str, ruleset: &Ruleset) -> Result<()> {
+        let resp = match ruleset.id {
+            Some(id) => {
+                let route = format!("/repos/{owner}/{repo}/rulesets/{id}");
+                let current: serde_json::Value = self.client.get(&route, None::<&()>).await?;
+                self.client._put(route, Some(&protection::ruleset_to_json(ruleset, Some(&current)))).await?
+            }
+            None => {
+                let body = protection::ruleset_to_json(ruleset, None);
+                self.client._post(format!("/repos/{owner}/{repo}/rulesets"), Some(&body)).await?
+            }
+        };
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+
+    async fn list_branches(&self, owner: &str, repo: &str) -> Result<Vec<Branch>> {
+        let default_branch = self.get_repo(owner, repo).await?.default_branch;
+
+        // `protected` also covers rulesets, which GraphQL does not report per branch
+        let route = format!("/repos/{owner}/{repo}/branches");
+        let params = [("protected", "true"), ("per_page", "100")];
+        let page: Page<serde_json::Value> = self.client.get(route, Some(&params)).await?;
+        let protected: Vec<String> = self
+            .client
+            .all_pages(page)
+            .await?
+            .iter()
+            .filter_map(|b| b["name"].as_str().map(String::from))
+            .collect();
+
+        let mut branches = Vec::new();
+        let mut cursor: Option<String> = None;
         loop {
-            let result = self.client.users(owner).repos().per_page(100).page(page_num as u32).send().await;
-            match result {
-                Ok(page) => {
-                    let len = page.items.len();
-                    repos.extend(page.items.into_iter().map(Repository::from));
-                    if len < 100 { break; }
+            let data: serde_json::Value = self
+                .client
+                .graphql(&serde_json::json!({
+                    "query": BRANCHES_QUERY,
+                    "variables": { "owner": owner, "name": repo, "default": default_branch, "cursor": cursor },
+                }))
+                .await?;
+            let refs = &data["repository"]["refs"];
+            if let Some(nodes) = refs["nodes"].as_array() {
+                branches.extend(nodes.iter().map(|n| branch_from_graphql(n, &default_branch, &protected)));
+            }
+            match refs["pageInfo"]["endCursor"].as_str() {
+                Some(end) if refs["pageInfo"]["hasNextPage"] == true => cursor = Some(end.to_string()),
+                _ => break,
+            }
+        }
+        Ok(branches)
+    }
+
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}/git/refs/heads/{}", path_segment(branch));
+        let resp = self.client._delete(route, None::<&()>).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+
+    async fn list_issues(&self, owner: &str, repo: &str, query: &IssueQuery) -> Result<Vec<Issue>> {
+        let limit = query.limit.unwrap_or(usize::MAX);
+        let mut issues = Vec::new();
+
+        if query.needs_search() {
+            let target = RepoRef { owner: owner.to_string(), name: repo.to_string() };
+            let terms = query.search_terms(&target);
+            // Search returns at most 1000 results, 10 pages of 100
+            for page in 1..=10u32 {
+                let page = page.to_string();
+                let params = [("q", terms.as_str()), ("sort", "created"), ("per_page", "100"), ("page", page.as_str())];
+                let result: serde_json::Value = self.client.get("/search/issues", Some(&params)).await?;
+                let items = result["items"].as_array().cloned().unwrap_or_default();
+                issues.extend(items.iter().map(issue::issue_from_json));
+                if items.len() < 100 || issues.len() >= limit {
+                    break;
                 }
-                Err(_) => {
-                    // Try as org
-                    let result = self
-                        .client
-                        .orgs(owner)
-                        .list_repos()
-                        .per_page(100)
-                        .page(page_num as u32)
-                        .send()
-                        .await;
-                    
-                    if let Ok(page) = result {
-                        let len = page.items.len();
-                        repos.extend(page.items.into_iter().map(Repository::from));
-                        if len < 100 { break; }
-                    } else {
-                        break;
+            }
+        } else {
+            let route = format!("/repos/{owner}/{repo}/issues");
+            let labels = query.labels.join(",");
+            let mut page_num = 1u32;
+            loop {
+                let page = page_num.to_string();
+                let mut params = vec![("state", query.state.as_api_str()), ("per_page", "100"), ("page", page.as_str())];
+                if !labels.is_empty() {
+                    params.push(("labels", labels.as_str()));
+                }
+                if let Some(ref assignee) = query.assignee {
+                    params.push(("assignee", assignee.as_str()));
+                }
+                if let Some(ref author) = query.author {
+                    params.push(("creator", author.as_str()));
+                }
+                let items: Vec<serde_json::Value> = match self.client.get(&route, Some(&params)).await {
+                    Ok(items) => items,
+                    Err(e) if is_not_found(&e) => {
+                        return Err(GhTuiError::NotFound(format!("repository '{}/{}'", owner, repo)))
                     }
+                    Err(e) => return Err(e.into()),
+                };
+                issues.extend(items.iter().filter(|i| !issue::is_pull_request(i)).map(issue::issue_from_json));
+                if items.len() < 100 || issues.len() >= limit {
+                    break;
                 }
+                page_num += 1;
             }
-            page_num += 1;
         }
-        Ok(repos)
+
+        issues.truncate(limit);
+        Ok(issues)
     }
 
-    async fn get_repo(&self, owner: &str, repo: &str) -> Result<Repository> {
-        let r = self.client.repos(owner, repo).get().await?;
-        Ok(Repository::from(r))
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let route = format!("/repos/{owner}/{repo}/issues/{number}");
+        match self.client.get::<serde_json::Value, _, _>(route, None::<&()>).await {
+            Ok(value) if issue::is_pull_request(&value) => {
+                Err(GhTuiError::InvalidInput(format!("#{} of {}/{} is a pull request", number, owner, repo)))
+            }
+            Ok(value) => Ok(issue::issue_from_json(&value)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("issue #{} of {}/{}", number, owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list_issue_comments(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<IssueComment>> {
+        let route = format!("/repos/{owner}/{repo}/issues/{number}/comments");
+        let page: Page<serde_json::Value> = self.client.get(route, Some(&[("per_page", "100")])).await?;
+        Ok(self.client.all_pages(page).await?.iter().map(issue::comment_from_json).collect())
+    }
+
+    async fn create_issue(&self, owner: &str, repo: &str, new_issue: &NewIssue) -> Result<Issue> {
+        let route = format!("/repos/{owner}/{repo}/issues");
+        let body = serde_json::json!({
+            "title": new_issue.title,
+            "body": new_issue.body,
+            "labels": new_issue.labels,
+            "assignees": new_issue.assignees,
+        });
+        match self.client.post::<_, serde_json::Value>(route, Some(&body)).await {
+            Ok(value) => Ok(issue::issue_from_json(&value)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("repository '{}/{}'", owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn set_issue_state(&self, owner: &str, repo: &str, number: u64, state: IssueState, reason: Option<CloseReason>) -> Result<Issue> {
+        let route = format!("/repos/{owner}/{repo}/issues/{number}");
+        let mut body = serde_json::json!({ "state": state.to_string() });
+        if let Some(reason) = reason {
+            body["state_reason"] = reason.as_api_str().into();
+        }
+        match self.client.patch::<serde_json::Value, _, _>(route, Some(&body)).await {
+            Ok(value) => Ok(issue::issue_from_json(&value)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("issue #{} of {}/{}", number, owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn add_issue_comment(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<IssueComment> {
+        let route = format!("/repos/{owner}/{repo}/issues/{number}/comments");
+        match self.client.post::<_, serde_json::Value>(route, Some(&serde_json::json!({ "body": body }))).await {
+            Ok(value) => Ok(issue::comment_from_json(&value)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("issue #{} of {}/{}", number, owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn search_issues(&self, query: &str, limit: usize) -> Result<SearchPage<IssueHit>> {
+        let page = self.search("/search/issues", query, limit, None).await?;
+        Ok(SearchPage {
+            total_count: page.total_count,
+            incomplete_results: page.incomplete_results,
+            items: page.items.iter().map(search::issue_hit_from_json).collect(),
+        })
+    }
+
+    async fn search_repos(&self, query: &str, limit: usize) -> Result<SearchPage<Repository>> {
+        let page = self.search("/search/repositories", query, limit, None).await?;
+        let mut items = Vec::with_capacity(page.items.len());
+        for item in page.items {
+            items.push(Repository::from(serde_json::from_value::<octocrab::models::Repository>(item)?));
+        }
+        Ok(SearchPage { total_count: page.total_count, incomplete_results: page.incomplete_results, items })
+    }
+
+    async fn search_code(&self, query: &str, limit: usize) -> Result<SearchPage<CodeHit>> {
+        // Ask for the matching fragments along with each file
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::ACCEPT,
+            reqwest::header::HeaderValue::from_static("application/vnd.github.text-match+json"),
+        );
+        let page = self.search("/search/code", query, limit, Some(headers)).await?;
+        Ok(SearchPage {
+            total_count: page.total_count,
+            incomplete_results: page.incomplete_results,
+            items: page.items.iter().map(search::code_hit_from_json).collect(),
+        })
+    }
+
+    async fn pull_request_status(&self, query: &PrStatusQuery) -> Result<PrStatus> {
+        let [authored, review_requested, assigned] = query.search_queries();
+        let data: serde_json::Value = self
+            .client
+            .graphql(&serde_json::json!({
+                "query": format!("{}{}", PR_STATUS_QUERY, pull_request::PULL_REQUEST_FIELDS),
+                "variables": {
+                    "authored": authored,
+                    "reviewRequested": review_requested,
+                    "assigned": assigned,
+                    "first": query.limit.clamp(1, 100),
+                },
+            }))
+            .await?;
+        let section = |key: &str| -> Vec<_> {
+            data[key]["nodes"]
+                .as_array()
+                .map(|nodes| {
+                    nodes
+                        .iter()
+                        // Search may match items the token cannot read, returned as empty objects
+                        .filter(|n| n["number"].is_u64())
+                        .map(pull_request::pull_request_from_graphql)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Ok(PrStatus {
+            authored: section("authored"),
+            review_requested: section("reviewRequested"),
+            assigned: section("assigned"),
+        })
+    }
+
+    async fn list_pull_requests(&self, query: &PrListQuery) -> Result<Vec<PullRequest>> {
+        let data: serde_json::Value = self
+            .client
+            .graphql(&serde_json::json!({
+                "query": format!("{}{}", PULL_REQUEST_SEARCH_QUERY, pull_request::PULL_REQUEST_FIELDS),
+                "variables": { "query": query.search_query(), "first": query.limit.clamp(1, 100) },
+            }))
+            .await?;
+        Ok(data["search"]["nodes"]
+            .as_array()
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .filter(|n| n["number"].is_u64())
+                    .map(pull_request::pull_request_from_graphql)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
+        let query = format!("{}{}", PULL_REQUEST_QUERY, pull_request::PULL_REQUEST_FIELDS);
+        let node = self.pull_request_node(owner, repo, number, &query).await?;
+        Ok(pull_request::pull_request_from_graphql(&node))
+    }
+
+    async fn get_pull_request_details(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequestDetails> {
+        let query = format!(
+            "{}{}{}",
+            PULL_REQUEST_DETAILS_QUERY,
+            pull_request::PULL_REQUEST_DETAILS_FIELDS,
+            pull_request::PULL_REQUEST_FIELDS
+        );
+        let node = self.pull_request_node(owner, repo, number, &query).await?;
+        Ok(pull_request::pull_request_details_from_graphql(&node))
+    }
+
+    async fn get_pull_request_diff(&self, owner: &str, repo: &str, number: u64) -> Result<String> {
+        let route = format!("/repos/{owner}/{repo}/pulls/{number}");
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::ACCEPT, reqwest::header::HeaderValue::from_static("application/vnd.github.diff"));
+        let resp = self.client._get_with_headers(route, Some(headers)).await?;
+        match octocrab::map_github_error(resp).await {
+            Ok(resp) => Ok(self.client.body_to_string(resp).await?),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("pull request #{} of {}/{}", number, owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn submit_review(&self, owner: &str, repo: &str, number: u64, review: &NewReview) -> Result<String> {
+        let route = format!("/repos/{owner}/{repo}/pulls/{number}/reviews");
+        let mut body = serde_json::json!({ "event": review.event.as_api_str() });
+        if let Some(ref text) = review.body {
+            body["body"] = text.as_str().into();
+        }
+        match self.client.post::<_, serde_json::Value>(route, Some(&body)).await {
+            Ok(value) => Ok(value["html_url"].as_str().unwrap_or_default().to_string()),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("pull request #{} of {}/{}", number, owner, repo))),
+            Err(e) => Err(rejection(e)),
+        }
+    }
+
+    async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64, method: MergeMethod, head_sha: &str) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}/pulls/{number}/merge");
+        let body = serde_json::json!({ "merge_method": method.as_api_str(), "sha": head_sha });
+        let resp = self.client._put(route, Some(&body)).await?;
+        octocrab::map_github_error(resp).await.map_err(rejection)?;
+        Ok(())
+    }
+
+    async fn enable_auto_merge(&self, id: &str, method: MergeMethod) -> Result<()> {
+        self.client
+            .graphql::<serde_json::Value>(&serde_json::json!({
+                "query": ENABLE_AUTO_MERGE_MUTATION,
+                "variables": { "id": id, "method": method.as_api_str().to_uppercase() },
+            }))
+            .await?;
+        Ok(())
+    }
+
+    async fn set_repo_archived(&self, owner: &str, repo: &str, archived: bool) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}");
+        let resp = self.client._patch(route, Some(&serde_json::json!({ "archived": archived }))).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+
+    async fn delete_repo(&self, owner: &str, repo: &str) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}");
+        // 204 No Content, see delete_artifact
+        let resp = self.client._delete(route, None::<&()>).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+
+    async fn list_releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        let route = format!("/repos/{owner}/{repo}/releases");
+        let page: Page<serde_json::Value> = match self.client.get(route, Some(&[("per_page", "100")])).await {
+            Ok(page) => page,
+            Err(e) if is_not_found(&e) => return Err(GhTuiError::NotFound(format!("repository '{}/{}'", owner, repo))),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(self.client.all_pages(page).await?.iter().map(release::release_from_json).collect())
+    }
+
+    async fn get_release_by_tag(&self, owner: &str, repo: &str, tag: &str) -> Result<Release> {
+        let route = format!("/repos/{owner}/{repo}/releases/tags/{}", path_segment(tag));
+        match self.client.get::<serde_json::Value, _, _>(route, None::<&()>).await {
+            Ok(value) => Ok(release::release_from_json(&value)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("release '{}' of {}/{}", tag, owner, repo))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn create_release(&self, owner: &str, repo: &str, new_release: &NewRelease) -> Result<Release> {
+        let route = format!("/repos/{owner}/{repo}/releases");
+        let mut body = serde_json::json!({
+            "tag_name": new_release.tag,
+            "draft": new_release.draft,
+            "prerelease": new_release.prerelease,
+            // GitHub prepends `body` to the generated notes
+            "generate_release_notes": new_release.generate_notes,
+        });
+        if let Some(ref target) = new_release.target {
+            body["target_commitish"] = target.as_str().into();
+        }
+        if let Some(ref name) = new_release.name {
+            body["name"] = name.as_str().into();
+        }
+        if let Some(ref notes) = new_release.notes {
+            body["body"] = notes.as_str().into();
+        }
+        match self.client.post::<_, serde_json::Value>(route, Some(&body)).await {
+            Ok(value) => Ok(release::release_from_json(&value)),
+            Err(e) if is_not_found(&e) => Err(GhTuiError::NotFound(format!("repository '{}/{}'", owner, repo))),
+            Err(e) => Err(rejection(e)),
+        }
+    }
+
+    async fn upload_release_asset(&self, owner: &str, repo: &str, release_id: u64, upload: &AssetUpload, progress: TransferProgress) -> Result<ReleaseAsset> {
+        use futures::stream::{self, StreamExt};
+        use http_body_util::BodyExt;
+        use tokio::io::AsyncReadExt;
+
+        // Uploads go to their own host, given by the release
+        let route = format!("/repos/{owner}/{repo}/releases/{release_id}");
+        let value: serde_json::Value = self.client.get(route, None::<&()>).await?;
+        let upload_url = value["upload_url"].as_str().unwrap_or_default().replace("{?name,label}", "");
+        let name: String = url::form_urlencoded::byte_serialize(upload.name.as_bytes()).collect();
+
+        // The file is sent a chunk at a time rather than read into memory
+        let file = tokio::fs::File::open(&upload.path).await?;
+        let chunks = stream::unfold(file, |mut file| async move {
+            let mut chunk = vec![0; UPLOAD_CHUNK_SIZE];
+            match file.read(&mut chunk).await {
+                Ok(0) => None,
+                Ok(n) => {
+                    chunk.truncate(n);
+                    Some((Ok(bytes::Bytes::from(chunk)), file))
+                }
+                Err(e) => Some((Err(e), file)),
+            }
+        });
+        let mut sent = 0;
+        let frames = chunks.map(move |chunk: std::io::Result<bytes::Bytes>| {
+            let chunk = chunk?;
+            sent += chunk.len() as u64;
+            progress(sent);
+            Ok::<_, std::io::Error>(http_body::Frame::data(chunk))
+        });
+
+        let request = http::Request::post(format!("{upload_url}?name={name}"))
+            .header(http::header::CONTENT_TYPE, "application/octet-stream")
+            .header(http::header::CONTENT_LENGTH, upload.size_in_bytes)
+            .body(octocrab::OctoBody::new(http_body_util::StreamBody::new(frames)))
+            .map_err(|e| GhTuiError::InvalidInput(format!("cannot upload {}: {}", upload.name, e)))?;
+        let resp = self.client.execute(request).await?;
+        let resp = octocrab::map_github_error(resp).await.map_err(rejection)?;
+        let body = resp.into_body().collect().await?.to_bytes();
+        Ok(release::asset_from_json(&serde_json::from_slice(&body)?))
+    }
+
+    async fn download_release_asset(&self, owner: &str, repo: &str, asset_id: u64, path: &Path, progress: TransferProgress) -> Result<()> {
+        use http_body_util::BodyExt;
+        use tokio::io::AsyncWriteExt;
+
+        // Answered with a redirect to the storage backend, which the client follows
+        let route = format!("/repos/{owner}/{repo}/releases/assets/{asset_id}");
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::ACCEPT, reqwest::header::HeaderValue::from_static("application/octet-stream"));
+        let resp = self.client._get_with_headers(route, Some(headers)).await?;
+        let resp = match octocrab::map_github_error(resp).await {
+            Ok(resp) => resp,
+            Err(e) if is_not_found(&e) => return Err(GhTuiError::NotFound(format!("release asset {} of {}/{}", asset_id, owner, repo))),
+            Err(e) => return Err(e.into()),
+        };
+
+        // Written as it arrives rather than collected in memory
+        let mut body = resp.into_body();
+        let mut file = tokio::fs::File::create(path).await?;
+        let mut received = 0;
+        while let Some(frame) = body.frame().await {
+            if let Ok(data) = frame?.into_data() {
+                file.write_all(&data).await?;
+                received += data.len() as u64;
+                progress(received);
+            }
+        }
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn rename_release_asset(&self, owner: &str, repo: &str, asset_id: u64, name: &str) -> Result<ReleaseAsset> {
+        let route = format!("/repos/{owner}/{repo}/releases/assets/{asset_id}");
+        let body = serde_json::json!({ "name": name });
+        let value: serde_json::Value = self.client.patch(route, Some(&body)).await.map_err(rejection)?;
+        Ok(release::asset_from_json(&value))
+    }
+
+    async fn delete_release(&self, owner: &str, repo: &str, release_id: u64) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}/releases/{release_id}");
+        // 204 No Content, see delete_artifact
+        let resp = self.client._delete(route, None::<&()>).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+
+    async fn delete_release_asset(&self, owner: &str, repo: &str, asset_id: u64) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}/releases/assets/{asset_id}");
+        let resp = self.client._delete(route, None::<&()>).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
     }
 
     async fn list_artifacts_for_repo(&self, owner: &str, repo: &str) -> Result<Vec<Artifact>> {
@@ -137,63 +1002,28 @@ impl GithubRepository for GitHubClient {
         Ok(())
     }
 
-    async fn get_storage_usage(&self) -> Result<StorageUsageReport> {
-        let mut total_used_billing = 0;
-        // Default to 2GB (Pro) as it's safer to overestimate, or 500MB if we can confirm Free
-        let mut total_max: u64 = 2 * 1024 * 1024 * 1024; 
-        let mut items = Vec::new();
+    async fn get_storage_usage(&self, include_release_assets: bool) -> Result<StorageUsageReport> {
+        use crate::domain::storage::{StorageItem, StorageItemType};
 
-        // 1. Check Personal Billing
-        let personal_billing_route = "/user/billing/shared-storage";
-        if let Ok(resp) = self.client.get::<serde_json::Value, _, _>(personal_billing_route, None::<&()>).await {
-            total_used_billing = resp.get("estimated_storage_for_month")
-                .and_then(|v| v.as_u64())
-                .map(|gb| gb * 1024 * 1024 * 1024)
-                .unwrap_or(0);
-            
-            if let Some(included) = resp.get("included_gigabytes_bandwidth_used").and_then(|v| v.as_u64()) {
-                if included == 0 {
-                    // Likely Free plan if 0 included GB
-                    total_max = 500 * 1024 * 1024;
-                }
-            }
-        }
+        let mut accounts = Vec::new();
+        let mut items = Vec::new();
 
-        // 2. Determine Plan and Max from /user endpoint (if available)
-        if let Ok(user_val) = self.client.get::<serde_json::Value, _, _>("/user", None::<&()>).await {
-            if let Some(plan) = user_val.get("plan") {
-                if let Some(name) = plan.get("name").and_then(|n| n.as_str()) {
-                    let plan_name = name.to_lowercase();
-                    total_max = match plan_name.as_str() {
-                        "free" => 500 * 1024 * 1024,
-                        "pro" => 2 * 1024 * 1024 * 1024,
-                        "team" => 2 * 1024 * 1024 * 1024,
-                        "enterprise" | "enterprise_cloud" => 50 * 1024 * 1024 * 1024,
-                        _ => 2 * 1024 * 1024 * 1024, // Default to Pro/2GB for unknown
-                    };
-                }
-            }
-        }
+        // 1. Billing for the authenticated user and each of their organizations
+        let user = self.get_current_user().await?;
+        accounts.push(self.account_storage(&user.login, AccountType::User).await);
 
-        // 3. Check Organizations Billing (for total_used)
         if let Ok(orgs_val) = self.client.get::<serde_json::Value, _, _>("/user/orgs", None::<&()>).await {
             if let Some(orgs_array) = orgs_val.as_array() {
                 for org_obj in orgs_array {
                     if let Some(org_login) = org_obj.get("login").and_then(|l| l.as_str()) {
-                        let org_billing_route = format!("/orgs/{}/billing/shared-storage", org_login);
-                        if let Ok(resp) = self.client.get::<serde_json::Value, _, _>(org_billing_route, None::<&()>).await {
-                            total_used_billing += resp.get("total_usage_in_bytes")
-                                .and_then(|v| v.as_u64())
-                                .unwrap_or(0);
-                        }
+                        accounts.push(self.account_storage(org_login, AccountType::Organization).await);
                     }
                 }
             }
         }
 
-        // 4. Scan all repos for details
+        // 2. Scan all repos for details
         if let Ok(repos) = self.list_user_repos().await {
-            use crate::domain::storage::{StorageItem, StorageItemType};
             for r in repos {
                 // Fetch Artifacts
                 if let Ok(artifacts) = self.list_artifacts_for_repo(&r.owner, &r.name).await {
@@ -205,6 +1035,8 @@ impl GithubRepository for GitHubClient {
                             repo: r.name.clone(),
                             size_in_bytes: a.size_in_bytes,
                             item_type: StorageItemType::Artifact,
+                            created_at: Some(a.created_at),
+                            expires_at: Some(a.expires_at),
                         });
                     }
                 }
@@ -222,23 +1054,107 @@ impl GithubRepository for GitHubClient {
                                     repo: r.name.clone(),
                                     size_in_bytes: c.get("size_in_bytes").and_then(|s| s.as_u64()).unwrap_or(0),
                                     item_type: StorageItemType::Cache,
+                                    created_at: c.get("created_at")
+                                        .and_then(|d| d.as_str())
+                                        .and_then(|d| d.parse().ok()),
+                                    expires_at: None,
                                 });
                             }
                         }
                     }
                 }
+
+                // Fetch Release assets
+                if include_release_assets {
+                    if let Ok(releases) = self.list_releases(&r.owner, &r.name).await {
+                        for asset in releases.into_iter().flat_map(|release| release.assets) {
+                            items.push(StorageItem {
+                                id: asset.id,
+                                name: format!("Release: {}", asset.name),
+                                owner: r.owner.clone(),
+                                repo: r.name.clone(),
+                                size_in_bytes: asset.size_in_bytes,
+                                item_type: StorageItemType::ReleaseAsset,
+                                created_at: Some(asset.created_at),
+                                expires_at: None,
+                            });
+                        }
+                    }
+                }
             }
         }
 
-        // If billing returned 0 but we found stuff, or vice versa, we use the max of both
-        // Actually the billing might include Packages which we don't list yet.
-        let scanned_total: u64 = items.iter().map(|i| i.size_in_bytes).sum();
-        let total_used = std::cmp::max(total_used_billing, scanned_total);
+        Ok(StorageUsageReport::new(accounts, items))
+    }
 
-        Ok(StorageUsageReport {
-            total_used,
-            max_allowed: total_max,
-            items,
-        })
+    async fn get_repo_retention(&self, owner: &str, repo: &str) -> Result<RetentionPolicy> {
+        let route = format!("/repos/{owner}/{repo}/actions/permissions/artifact-and-log-retention");
+        Ok(self.client.get(route, None::<&()>).await?)
+    }
+
+    async fn set_repo_retention(&self, owner: &str, repo: &str, days: u32) -> Result<()> {
+        let route = format!("/repos/{owner}/{repo}/actions/permissions/artifact-and-log-retention");
+        // 204 No Content, see delete_artifact
+        let resp = self.client._put(route, Some(&serde_json::json!({ "days": days }))).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+
+    async fn get_org_retention(&self, org: &str) -> Result<RetentionPolicy> {
+        let route = format!("/orgs/{org}/actions/permissions/artifact-and-log-retention");
+        Ok(self.client.get(route, None::<&()>).await?)
+    }
+
+    async fn set_org_retention(&self, org: &str, days: u32) -> Result<()> {
+        let route = format!("/orgs/{org}/actions/permissions/artifact-and-log-retention");
+        let resp = self.client._put(route, Some(&serde_json::json!({ "days": days }))).await?;
+        octocrab::map_github_error(resp).await?;
+        Ok(())
+    }
+}
+
+/// `value` percent-encoded to fit in one segment of a route, e.g. a tag with
+/// a `/` or a `#`
+fn path_segment(value: &str) -> String {
+    // Form encoding only differs from path encoding for spaces
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect::<String>().replace('+', "%20")
+}
+
+fn is_not_found(err: &octocrab::Error) -> bool {
+    matches!(err, octocrab::Error::GitHub { source, .. } if source.status_code.as_u16() == 404)
+}
+
+/// GraphQL reports missing objects as errors rather than with a 404
+fn is_unresolved(err: &octocrab::Error) -> bool {
+    matches!(err, octocrab::Error::Graphql { .. }) && err.to_string().contains("Could not resolve")
+}
+
+/// GitHub refusing an action on the pull request's current state (not
+/// mergeable, head changed, own pull request approved) becomes an
+/// `InvalidInput` with GitHub's explanation.
+fn rejection(err: octocrab::Error) -> GhTuiError {
+    match err {
+        octocrab::Error::GitHub { ref source, .. } if [405, 409, 422].contains(&source.status_code.as_u16()) => {
+            GhTuiError::InvalidInput(source.message.clone())
+        }
+        e => e.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_segment_keeps_names_whole() {
+        // `#` and `?` would otherwise end the path, `/` add a segment
+        assert_eq!(path_segment("fix#12"), "fix%2312");
+        assert_eq!(path_segment("what?now"), "what%3Fnow");
+        assert_eq!(path_segment("feature/login page"), "feature%2Flogin%20page");
+        assert_eq!(path_segment("v1.2.0-rc.1"), "v1.2.0-rc.1");
+
+        let route: http::Uri = format!("/repos/acme/api/git/refs/heads/{}", path_segment("fix#12?x")).parse().unwrap();
+        assert_eq!(route.path(), "/repos/acme/api/git/refs/heads/fix%2312%3Fx");
+        assert_eq!(route.query(), None);
     }
 }
@@ -0,0 +1,96 @@
+//! Mapping from the JSON of the issues and issue search APIs to `Issue`.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::domain::issue::{Issue, IssueComment, IssueState};
+
+/// An item of `GET /repos/{owner}/{repo}/issues` or `GET /search/issues`
+pub fn issue_from_json(value: &Value) -> Issue {
+    let logins = |items: &Value| -> Vec<String> {
+        items
+            .as_array()
+            .map(|items| items.iter().filter_map(|i| i["login"].as_str().map(String::from)).collect())
+            .unwrap_or_default()
+    };
+
+    Issue {
+        number: value["number"].as_u64().unwrap_or_default(),
+        title: value["title"].as_str().unwrap_or_default().to_string(),
+        state: if value["state"] == "closed" { IssueState::Closed } else { IssueState::Open },
+        state_reason: value["state_reason"].as_str().map(String::from),
+        author: value["user"]["login"].as_str().unwrap_or("ghost").to_string(),
+        assignees: logins(&value["assignees"]),
+        labels: value["labels"]
+            .as_array()
+            .map(|labels| labels.iter().filter_map(|l| l["name"].as_str().map(String::from)).collect())
+            .unwrap_or_default(),
+        milestone: value["milestone"]["title"].as_str().map(String::from),
+        comments: value["comments"].as_u64().unwrap_or_default() as u32,
+        body: value["body"].as_str().filter(|b| !b.is_empty()).map(String::from),
+        created_at: date(&value["created_at"]).unwrap_or_else(Utc::now),
+        updated_at: date(&value["updated_at"]).unwrap_or_else(Utc::now),
+        closed_at: date(&value["closed_at"]),
+        html_url: value["html_url"].as_str().unwrap_or_default().to_string(),
+    }
+}
+
+/// Pull requests are issues too; the issues endpoints return them with a `pull_request` key
+pub fn is_pull_request(value: &Value) -> bool {
+    value.get("pull_request").is_some_and(|pr| !pr.is_null())
+}
+
+/// An item of `GET /repos/{owner}/{repo}/issues/{number}/comments`
+pub fn comment_from_json(value: &Value) -> IssueComment {
+    IssueComment {
+        author: value["user"]["login"].as_str().unwrap_or("ghost").to_string(),
+        body: value["body"].as_str().unwrap_or_default().to_string(),
+        created_at: date(&value["created_at"]).unwrap_or_else(Utc::now),
+        html_url: value["html_url"].as_str().unwrap_or_default().to_string(),
+    }
+}
+
+fn date(value: &Value) -> Option<DateTime<Utc>> {
+    value.as_str().and_then(|d| d.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_issue_from_github() {
+        let value = json!({
+            "number": 42,
+            "title": "Crash on start",
+            "state": "closed",
+            "state_reason": "completed",
+            "user": { "login": "octocat" },
+            "assignees": [{ "login": "hubot" }],
+            "labels": [{ "name": "bug" }, { "name": "p1" }],
+            "milestone": { "title": "v1.0" },
+            "comments": 3,
+            "body": "",
+            "created_at": "2024-03-01T10:00:00Z",
+            "updated_at": "2024-03-02T10:00:00Z",
+            "closed_at": "2024-03-02T10:00:00Z",
+            "html_url": "https://github.com/acme/api/issues/42"
+        });
+        let issue = issue_from_json(&value);
+        assert_eq!(issue.state, IssueState::Closed);
+        assert_eq!(issue.assignees, vec!["hubot"]);
+        assert_eq!(issue.labels, vec!["bug", "p1"]);
+        assert_eq!(issue.milestone.as_deref(), Some("v1.0"));
+        assert_eq!(issue.body, None);
+        assert_eq!(issue.closed_at.unwrap().to_rfc3339(), "2024-03-02T10:00:00+00:00");
+        assert!(!is_pull_request(&value));
+        assert!(is_pull_request(&json!({ "pull_request": { "url": "" } })));
+    }
+
+    #[test]
+    fn test_deleted_author() {
+        let comment = comment_from_json(&json!({ "user": null, "body": "+1", "created_at": "2024-03-01T10:00:00Z" }));
+        assert_eq!(comment.author, "ghost");
+        assert_eq!(comment.body, "+1");
+    }
+}
@@ -1,2 +1,7 @@
 pub mod auth;
 pub mod client;
+pub mod protection;
+pub mod issue;
+pub mod search;
+pub mod pull_request;
+pub mod release;
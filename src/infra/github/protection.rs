@@ -0,0 +1,367 @@
+//! Mapping between `BranchProtection`/`Ruleset` and the JSON of the branch
+//! protection and repository rulesets APIs.
+//!
+//! Both APIs replace the whole protection on update, so the bodies are built
+//! on top of the current protection: whatever `BranchProtection` does not
+//! model is sent back unchanged.
+
+use serde_json::{json, Value};
+use crate::domain::protection::{BranchProtection, Ruleset};
+
+/// Repository admins may bypass a ruleset that is not enforced for admins
+const REPOSITORY_ADMIN_ROLE_ID: u64 = 5;
+/// Ruleset rule types mapped to `BranchProtection`; other rules are kept as they are
+const MODELED_RULES: [&str; 5] = ["pull_request", "required_status_checks", "required_linear_history", "non_fast_forward", "deletion"];
+
+/// `GET /repos/{owner}/{repo}/branches/{branch}/protection`
+pub fn protection_from_json(value: &Value) -> BranchProtection {
+    let enabled = |key: &str| value[key]["enabled"].as_bool().unwrap_or(false);
+    let reviews = &value["required_pull_request_reviews"];
+    let checks = &value["required_status_checks"];
+
+    BranchProtection {
+        required_approving_reviews: reviews
+            .is_object()
+            .then(|| reviews["required_approving_review_count"].as_u64().unwrap_or(0) as u32),
+        dismiss_stale_reviews: reviews["dismiss_stale_reviews"].as_bool().unwrap_or(false),
+        require_code_owner_reviews: reviews["require_code_owner_reviews"].as_bool().unwrap_or(false),
+        required_status_checks: checks.is_object().then(|| {
+            // `contexts` is deprecated in favour of `checks` but still returned
+            let contexts: Vec<String> = checks["checks"]
+                .as_array()
+                .map(|c| c.iter().filter_map(|c| c["context"].as_str().map(String::from)).collect())
+                .unwrap_or_default();
+            if contexts.is_empty() { strings(&checks["contexts"]) } else { contexts }
+        }),
+        strict_status_checks: checks["strict"].as_bool().unwrap_or(false),
+        enforce_admins: enabled("enforce_admins"),
+        required_linear_history: enabled("required_linear_history"),
+        allow_force_pushes: enabled("allow_force_pushes"),
+        allow_deletions: enabled("allow_deletions"),
+        required_conversation_resolution: enabled("required_conversation_resolution"),
+    }
+}
+
+/// Body of `PUT /repos/{owner}/{repo}/branches/{branch}/protection`, keeping
+/// from `current` (the response of the GET, if the branch is protected) the
+/// push restrictions, last-push approval, dismissal restrictions, review
+/// bypass allowances, status check apps, branch lock, fork syncing and
+/// creation blocking.
+pub fn protection_to_json(protection: &BranchProtection, current: Option<&Value>) -> Value {
+    let current = current.unwrap_or(&Value::Null);
+    let current_reviews = &current["required_pull_request_reviews"];
+    let current_checks = current["required_status_checks"]["checks"].as_array().cloned().unwrap_or_default();
+    let enabled = |key: &str| current[key]["enabled"].as_bool().unwrap_or(false);
+
+    let reviews = protection.required_approving_reviews.map(|count| {
+        let mut reviews = json!({
+            "required_approving_review_count": count,
+            "dismiss_stale_reviews": protection.dismiss_stale_reviews,
+            "require_code_owner_reviews": protection.require_code_owner_reviews,
+            "require_last_push_approval": current_reviews["require_last_push_approval"].as_bool().unwrap_or(false),
+        });
+        for key in ["dismissal_restrictions", "bypass_pull_request_allowances"] {
+            if let Some(actors) = actors(&current_reviews[key]) {
+                reviews[key] = actors;
+            }
+        }
+        reviews
+    });
+    let checks = protection.required_status_checks.as_ref().map(|contexts| {
+        let checks: Vec<Value> = contexts
+            .iter()
+            .map(|context| {
+                // Without an app_id GitHub picks the app that last reported the check
+                match current_checks.iter().find(|c| c["context"] == context.as_str()).map(|c| &c["app_id"]) {
+                    Some(app_id) if !app_id.is_null() => json!({ "context": context, "app_id": app_id }),
+                    _ => json!({ "context": context }),
+                }
+            })
+            .collect();
+        json!({ "strict": protection.strict_status_checks, "checks": checks })
+    });
+
+    json!({
+        "required_status_checks": checks,
+        "enforce_admins": protection.enforce_admins,
+        "required_pull_request_reviews": reviews,
+        "restrictions": actors(&current["restrictions"]),
+        "required_linear_history": protection.required_linear_history,
+        "allow_force_pushes": protection.allow_force_pushes,
+        "allow_deletions": protection.allow_deletions,
+        "required_conversation_resolution": protection.required_conversation_resolution,
+        "lock_branch": enabled("lock_branch"),
+        "allow_fork_syncing": enabled("allow_fork_syncing"),
+        "block_creations": enabled("block_creations"),
+    })
+}
+
+/// Users, teams and apps as returned by the GET, in the form the PUT expects
+fn actors(value: &Value) -> Option<Value> {
+    let names = |key: &str, field: &str| -> Vec<Value> {
+        value[key]
+            .as_array()
+            .map(|items| items.iter().filter_map(|i| i[field].as_str().map(|n| json!(n))).collect())
+            .unwrap_or_default()
+    };
+    value.is_object().then(|| {
+        json!({
+            "users": names("users", "login"),
+            "teams": names("teams", "slug"),
+            "apps": names("apps", "slug"),
+        })
+    })
+}
+
+/// Whether a ruleset bypass actor stands for the repository or organization admins
+fn is_admin_actor(actor: &Value) -> bool {
+    (actor["actor_type"] == "RepositoryRole" && actor["actor_id"] == REPOSITORY_ADMIN_ROLE_ID)
+        || actor["actor_type"] == "OrganizationAdmin"
+}
+
+/// `GET /repos/{owner}/{repo}/rulesets/{id}`
+pub fn ruleset_from_json(value: &Value) -> Ruleset {
+    let rules = value["rules"].as_array().cloned().unwrap_or_default();
+    let rule = |kind: &str| rules.iter().find(|r| r["type"] == kind);
+    let pull_request = rule("pull_request").map(|r| &r["parameters"]);
+    let status_checks = rule("required_status_checks").map(|r| &r["parameters"]);
+
+    let protection = BranchProtection {
+        required_approving_reviews: pull_request
+            .map(|p| p["required_approving_review_count"].as_u64().unwrap_or(0) as u32),
+        dismiss_stale_reviews: pull_request.is_some_and(|p| p["dismiss_stale_reviews_on_push"] == true),
+        require_code_owner_reviews: pull_request.is_some_and(|p| p["require_code_owner_review"] == true),
+        required_status_checks: status_checks.map(|p| {
+            p["required_status_checks"]
+                .as_array()
+                .map(|c| c.iter().filter_map(|c| c["context"].as_str().map(String::from)).collect())
+                .unwrap_or_default()
+        }),
+        strict_status_checks: status_checks.is_some_and(|p| p["strict_required_status_checks_policy"] == true),
+        enforce_admins: !value["bypass_actors"].as_array().is_some_and(|actors| actors.iter().any(is_admin_actor)),
+        required_linear_history: rule("required_linear_history").is_some(),
+        allow_force_pushes: rule("non_fast_forward").is_none(),
+        allow_deletions: rule("deletion").is_none(),
+        required_conversation_resolution: pull_request.is_some_and(|p| p["required_review_thread_resolution"] == true),
+    };
+
+    Ruleset {
+        id: value["id"].as_u64(),
+        name: value["name"].as_str().unwrap_or_default().to_string(),
+        enforcement: value["enforcement"].as_str().unwrap_or("disabled").to_string(),
+        branches: strings(&value["conditions"]["ref_name"]["include"]),
+        protection,
+    }
+}
+
+/// Body of `POST /repos/{owner}/{repo}/rulesets` and `PUT .../rulesets/{id}`.
+/// When updating, `current` is the existing ruleset: its other rule types,
+/// rule parameters, non-admin bypass actors and excluded refs are kept.
+pub fn ruleset_to_json(ruleset: &Ruleset, current: Option<&Value>) -> Value {
+    let protection = &ruleset.protection;
+    let current = current.unwrap_or(&Value::Null);
+    let current_rules = current["rules"].as_array().cloned().unwrap_or_default();
+    let parameters = |kind: &str| {
+        current_rules
+            .iter()
+            .find(|r| r["type"] == kind)
+            .map(|r| r["parameters"].clone())
+            .filter(Value::is_object)
+            .unwrap_or_else(|| json!({}))
+    };
+
+    let mut rules: Vec<Value> = current_rules
+        .iter()
+        .filter(|r| !r["type"].as_str().is_some_and(|kind| MODELED_RULES.contains(&kind)))
+        .cloned()
+        .collect();
+    // Rulesets only resolve conversations on pull requests, so that needs one too
+    if protection.required_approving_reviews.is_some() || protection.required_conversation_resolution {
+        let mut pull_request = parameters("pull_request");
+        pull_request["required_approving_review_count"] = json!(protection.required_approving_reviews.unwrap_or(0));
+        pull_request["dismiss_stale_reviews_on_push"] = json!(protection.dismiss_stale_reviews);
+        pull_request["require_code_owner_review"] = json!(protection.require_code_owner_reviews);
+        pull_request["required_review_thread_resolution"] = json!(protection.required_conversation_resolution);
+        if pull_request["require_last_push_approval"].is_null() {
+            pull_request["require_last_push_approval"] = json!(false);
+        }
+        rules.push(json!({ "type": "pull_request", "parameters": pull_request }));
+    }
+    if let Some(ref checks) = protection.required_status_checks {
+        let mut status_checks = parameters("required_status_checks");
+        let current_checks = status_checks["required_status_checks"].as_array().cloned().unwrap_or_default();
+        // Keep the app each existing check must come from
+        let checks: Vec<Value> = checks
+            .iter()
+            .map(|context| {
+                current_checks
+                    .iter()
+                    .find(|c| c["context"] == context.as_str())
+                    .cloned()
+                    .unwrap_or_else(|| json!({ "context": context }))
+            })
+            .collect();
+        status_checks["strict_required_status_checks_policy"] = json!(protection.strict_status_checks);
+        status_checks["required_status_checks"] = json!(checks);
+        rules.push(json!({ "type": "required_status_checks", "parameters": status_checks }));
+    }
+    if protection.required_linear_history {
+        rules.push(json!({ "type": "required_linear_history" }));
+    }
+    if !protection.allow_force_pushes {
+        rules.push(json!({ "type": "non_fast_forward" }));
+    }
+    if !protection.allow_deletions {
+        rules.push(json!({ "type": "deletion" }));
+    }
+
+    let current_actors = current["bypass_actors"].as_array().cloned().unwrap_or_default();
+    let admins: Vec<Value> = current_actors.iter().filter(|a| is_admin_actor(a)).cloned().collect();
+    let mut bypass_actors: Vec<Value> = current_actors.into_iter().filter(|a| !is_admin_actor(a)).collect();
+    if !protection.enforce_admins {
+        if admins.is_empty() {
+            bypass_actors.push(json!({ "actor_id": REPOSITORY_ADMIN_ROLE_ID, "actor_type": "RepositoryRole", "bypass_mode": "always" }));
+        } else {
+            bypass_actors.extend(admins);
+        }
+    }
+
+    let mut conditions = if current["conditions"].is_object() { current["conditions"].clone() } else { json!({}) };
+    conditions["ref_name"]["include"] = json!(ruleset.branches);
+    if !conditions["ref_name"]["exclude"].is_array() {
+        conditions["ref_name"]["exclude"] = json!([]);
+    }
+
+    json!({
+        "name": ruleset.name,
+        "target": "branch",
+        "enforcement": ruleset.enforcement,
+        "conditions": conditions,
+        "rules": rules,
+        "bypass_actors": bypass_actors,
+    })
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| items.iter().filter_map(|i| i.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protection() -> BranchProtection {
+        BranchProtection {
+            required_approving_reviews: Some(2),
+            dismiss_stale_reviews: true,
+            required_status_checks: Some(vec!["build".into()]),
+            strict_status_checks: true,
+            required_linear_history: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_classic_protection_from_github() {
+        let value = json!({
+            "required_status_checks": { "strict": true, "contexts": ["build"], "checks": [{ "context": "build", "app_id": null }] },
+            "required_pull_request_reviews": { "dismiss_stale_reviews": true, "require_code_owner_reviews": false, "required_approving_review_count": 2 },
+            "enforce_admins": { "enabled": false },
+            "required_linear_history": { "enabled": true },
+            "allow_force_pushes": { "enabled": false },
+            "allow_deletions": { "enabled": false },
+            "required_conversation_resolution": { "enabled": false }
+        });
+        assert_eq!(protection_from_json(&value), protection());
+
+        let body = protection_to_json(&protection(), None);
+        assert_eq!(body["required_pull_request_reviews"]["required_approving_review_count"], 2);
+        assert_eq!(body["required_status_checks"]["checks"], json!([{ "context": "build" }]));
+        assert!(body["restrictions"].is_null());
+        assert!(protection_to_json(&BranchProtection::default(), None)["required_status_checks"].is_null());
+    }
+
+    #[test]
+    fn test_classic_protection_keeps_unmodeled_settings() {
+        let current = json!({
+            "required_status_checks": { "strict": false, "contexts": ["build"], "checks": [{ "context": "build", "app_id": 15368 }] },
+            "required_pull_request_reviews": {
+                "required_approving_review_count": 1,
+                "require_last_push_approval": true,
+                "dismissal_restrictions": { "users": [{ "login": "octocat" }], "teams": [], "apps": [] }
+            },
+            "restrictions": { "users": [], "teams": [{ "slug": "release" }], "apps": [{ "slug": "deployer" }] },
+            "lock_branch": { "enabled": true },
+            "block_creations": { "enabled": true }
+        });
+        let body = protection_to_json(&protection(), Some(&current));
+
+        assert_eq!(body["restrictions"], json!({ "users": [], "teams": ["release"], "apps": ["deployer"] }));
+        let reviews = &body["required_pull_request_reviews"];
+        assert_eq!(reviews["required_approving_review_count"], 2);
+        assert_eq!(reviews["require_last_push_approval"], true);
+        assert_eq!(reviews["dismissal_restrictions"]["users"], json!(["octocat"]));
+        assert_eq!(body["required_status_checks"]["checks"], json!([{ "context": "build", "app_id": 15368 }]));
+        assert_eq!((body["lock_branch"].clone(), body["block_creations"].clone()), (json!(true), json!(true)));
+    }
+
+    #[test]
+    fn test_ruleset_round_trip() {
+        let ruleset = Ruleset {
+            id: Some(42),
+            name: "main protection".into(),
+            enforcement: "active".into(),
+            branches: vec!["~DEFAULT_BRANCH".into()],
+            protection: protection(),
+        };
+        let mut value = ruleset_to_json(&ruleset, None);
+        assert_eq!(value["bypass_actors"][0]["actor_type"], "RepositoryRole");
+        value["id"] = json!(42);
+        assert_eq!(ruleset_from_json(&value), ruleset);
+    }
+
+    #[test]
+    fn test_ruleset_keeps_unmodeled_rules_and_actors() {
+        let current = json!({
+            "id": 42,
+            "name": "main protection",
+            "enforcement": "active",
+            "conditions": { "ref_name": { "include": ["~DEFAULT_BRANCH"], "exclude": ["refs/heads/legacy"] } },
+            "rules": [
+                { "type": "required_signatures" },
+                { "type": "pull_request", "parameters": { "required_approving_review_count": 1, "require_last_push_approval": true } },
+                { "type": "required_status_checks", "parameters": {
+                    "strict_required_status_checks_policy": false,
+                    "required_status_checks": [{ "context": "build", "integration_id": 15368 }]
+                } }
+            ],
+            "bypass_actors": [
+                { "actor_id": 7, "actor_type": "Team", "bypass_mode": "pull_request" },
+                { "actor_id": 5, "actor_type": "RepositoryRole", "bypass_mode": "always" }
+            ]
+        });
+        let ruleset = Ruleset {
+            id: Some(42),
+            name: "main protection".into(),
+            enforcement: "active".into(),
+            branches: vec!["~DEFAULT_BRANCH".into()],
+            protection: BranchProtection { enforce_admins: true, ..protection() },
+        };
+        let body = ruleset_to_json(&ruleset, Some(&current));
+
+        let types: Vec<&str> = body["rules"].as_array().unwrap().iter().filter_map(|r| r["type"].as_str()).collect();
+        assert!(types.contains(&"required_signatures"));
+        let pull_request = body["rules"].as_array().unwrap().iter().find(|r| r["type"] == "pull_request").unwrap();
+        assert_eq!(pull_request["parameters"]["require_last_push_approval"], true);
+        assert_eq!(pull_request["parameters"]["required_approving_review_count"], 2);
+        let checks = body["rules"].as_array().unwrap().iter().find(|r| r["type"] == "required_status_checks").unwrap();
+        assert_eq!(checks["parameters"]["required_status_checks"][0]["integration_id"], 15368);
+        // Enforcing for admins only drops the admin bypass
+        assert_eq!(body["bypass_actors"], json!([{ "actor_id": 7, "actor_type": "Team", "bypass_mode": "pull_request" }]));
+        assert_eq!(body["conditions"]["ref_name"]["exclude"], json!(["refs/heads/legacy"]));
+        assert!(ruleset_from_json(&body).protection.enforce_admins);
+    }
+}
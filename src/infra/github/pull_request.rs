@@ -0,0 +1,228 @@
+//! Mapping from the GraphQL `PullRequest` object to `PullRequest` and
+//! `PullRequestDetails`.
+
+use chrono::Utc;
+use serde_json::Value;
+use crate::domain::branch::PullRequestState;
+use crate::domain::pull_request::{
+    ChangedFile, CheckRun, CheckStatus, MergeStateStatus, MergeableState, PullRequest, PullRequestDetails, Review,
+    ReviewDecision, ReviewState,
+};
+
+/// Fields of a pull request read by `pull_request_from_graphql`
+pub const PULL_REQUEST_FIELDS: &str = r#"
+fragment PullRequestFields on PullRequest {
+  id number title url state isDraft createdAt updatedAt
+  headRefName headRefOid baseRefName
+  mergeable mergeStateStatus reviewDecision
+  author { login }
+  headRepository { nameWithOwner }
+  repository { nameWithOwner }
+  commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+}"#;
+
+/// Fields read by `pull_request_details_from_graphql` besides `PullRequestFields`
+pub const PULL_REQUEST_DETAILS_FIELDS: &str = r#"
+fragment PullRequestDetailsFields on PullRequest {
+  ...PullRequestFields
+  body changedFiles
+  commits(last: 1) {
+    nodes { commit { statusCheckRollup { contexts(first: 100) { nodes {
+      __typename
+      ... on CheckRun { name status conclusion }
+      ... on StatusContext { context state }
+    } } } } }
+  }
+  reviews(last: 50) { nodes { author { login } state submittedAt } }
+  files(first: 100) { nodes { path additions deletions } }
+}"#;
+
+pub fn pull_request_from_graphql(node: &Value) -> PullRequest {
+    let text = |key: &str| node[key].as_str().unwrap_or_default().to_string();
+    let date = |key: &str| node[key].as_str().and_then(|d| d.parse().ok()).unwrap_or_else(Utc::now);
+
+    PullRequest {
+        id: text("id"),
+        repository: node["repository"]["nameWithOwner"].as_str().unwrap_or_default().to_string(),
+        number: node["number"].as_u64().unwrap_or_default(),
+        title: text("title"),
+        // Deleted accounts have no author
+        author: node["author"]["login"].as_str().unwrap_or("ghost").to_string(),
+        state: match node["state"].as_str() {
+            Some("MERGED") => PullRequestState::Merged,
+            Some("CLOSED") => PullRequestState::Closed,
+            _ => PullRequestState::Open,
+        },
+        draft: node["isDraft"] == true,
+        head_ref: text("headRefName"),
+        head_sha: text("headRefOid"),
+        head_repository: node["headRepository"]["nameWithOwner"].as_str().map(String::from),
+        base_ref: text("baseRefName"),
+        checks: match node["commits"]["nodes"][0]["commit"]["statusCheckRollup"]["state"].as_str() {
+            Some("SUCCESS") => CheckStatus::Success,
+            Some("FAILURE" | "ERROR") => CheckStatus::Failure,
+            Some("PENDING" | "EXPECTED") => CheckStatus::Pending,
+            _ => CheckStatus::None,
+        },
+        review_decision: match node["reviewDecision"].as_str() {
+            Some("APPROVED") => Some(ReviewDecision::Approved),
+            Some("CHANGES_REQUESTED") => Some(ReviewDecision::ChangesRequested),
+            Some("REVIEW_REQUIRED") => Some(ReviewDecision::ReviewRequired),
+            _ => None,
+        },
+        mergeable: match node["mergeable"].as_str() {
+            Some("MERGEABLE") => MergeableState::Mergeable,
+            Some("CONFLICTING") => MergeableState::Conflicting,
+            _ => MergeableState::Unknown,
+        },
+        merge_state: match node["mergeStateStatus"].as_str() {
+            Some("CLEAN") => MergeStateStatus::Clean,
+            Some("UNSTABLE") => MergeStateStatus::Unstable,
+            Some("HAS_HOOKS") => MergeStateStatus::HasHooks,
+            Some("BEHIND") => MergeStateStatus::Behind,
+            Some("BLOCKED") => MergeStateStatus::Blocked,
+            Some("DIRTY") => MergeStateStatus::Dirty,
+            Some("DRAFT") => MergeStateStatus::Draft,
+            _ => MergeStateStatus::Unknown,
+        },
+        created_at: date("createdAt"),
+        updated_at: date("updatedAt"),
+        html_url: text("url"),
+    }
+}
+
+pub fn pull_request_details_from_graphql(node: &Value) -> PullRequestDetails {
+    let nodes = |value: &Value| value["nodes"].as_array().cloned().unwrap_or_default();
+    let contexts = nodes(&node["commits"]["nodes"][0]["commit"]["statusCheckRollup"]["contexts"]);
+
+    PullRequestDetails {
+        pull_request: pull_request_from_graphql(node),
+        body: node["body"].as_str().filter(|b| !b.trim().is_empty()).map(String::from),
+        checks: contexts.iter().map(check_run_from_graphql).collect(),
+        reviews: nodes(&node["reviews"])
+            .iter()
+            .map(|r| Review {
+                author: r["author"]["login"].as_str().unwrap_or("ghost").to_string(),
+                state: match r["state"].as_str() {
+                    Some("APPROVED") => ReviewState::Approved,
+                    Some("CHANGES_REQUESTED") => ReviewState::ChangesRequested,
+                    Some("DISMISSED") => ReviewState::Dismissed,
+                    Some("PENDING") => ReviewState::Pending,
+                    _ => ReviewState::Commented,
+                },
+                submitted_at: r["submittedAt"].as_str().and_then(|d| d.parse().ok()),
+            })
+            .collect(),
+        files: nodes(&node["files"])
+            .iter()
+            .map(|f| ChangedFile {
+                path: f["path"].as_str().unwrap_or_default().to_string(),
+                additions: f["additions"].as_u64().unwrap_or_default() as u32,
+                deletions: f["deletions"].as_u64().unwrap_or_default() as u32,
+            })
+            .collect(),
+        changed_files: node["changedFiles"].as_u64().unwrap_or_default() as u32,
+    }
+}
+
+/// A check run of a GitHub App or a commit status set through the statuses API
+fn check_run_from_graphql(context: &Value) -> CheckRun {
+    if context["__typename"] == "StatusContext" {
+        return CheckRun {
+            name: context["context"].as_str().unwrap_or_default().to_string(),
+            status: match context["state"].as_str() {
+                Some("SUCCESS") => CheckStatus::Success,
+                Some("FAILURE" | "ERROR") => CheckStatus::Failure,
+                _ => CheckStatus::Pending,
+            },
+        };
+    }
+    CheckRun {
+        name: context["name"].as_str().unwrap_or_default().to_string(),
+        status: match (context["status"].as_str(), context["conclusion"].as_str()) {
+            (Some("COMPLETED"), Some("SUCCESS" | "NEUTRAL" | "SKIPPED")) => CheckStatus::Success,
+            (Some("COMPLETED"), _) => CheckStatus::Failure,
+            _ => CheckStatus::Pending,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pull_request_from_graphql() {
+        let pr = pull_request_from_graphql(&json!({
+            "number": 12,
+            "title": "Add retries",
+            "url": "https://github.com/acme/api/pull/12",
+            "isDraft": false,
+            "createdAt": "2026-01-02T03:04:05Z",
+            "updatedAt": "2026-01-03T03:04:05Z",
+            "headRefName": "retries",
+            "baseRefName": "main",
+            "state": "OPEN",
+            "mergeable": "CONFLICTING",
+            "mergeStateStatus": "DIRTY",
+            "headRepository": { "nameWithOwner": "hubot/api" },
+            "reviewDecision": "CHANGES_REQUESTED",
+            "author": null,
+            "repository": { "nameWithOwner": "acme/api" },
+            "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "state": "ERROR" } } }] }
+        }));
+        assert_eq!(pr.repository, "acme/api");
+        assert_eq!(pr.author, "ghost");
+        assert_eq!(pr.checks, CheckStatus::Failure);
+        assert_eq!(pr.review_decision, Some(ReviewDecision::ChangesRequested));
+        assert_eq!(pr.mergeable, MergeableState::Conflicting);
+        assert_eq!(pr.merge_state, MergeStateStatus::Dirty);
+        assert!(pr.is_from_fork());
+        assert_eq!(pr.created_at.to_rfc3339(), "2026-01-02T03:04:05+00:00");
+
+        let pr = pull_request_from_graphql(&json!({
+            "number": 13,
+            "state": "MERGED",
+            "mergeable": "UNKNOWN",
+            "reviewDecision": null,
+            "commits": { "nodes": [{ "commit": { "statusCheckRollup": null } }] }
+        }));
+        assert_eq!(pr.checks, CheckStatus::None);
+        assert_eq!(pr.review_decision, None);
+        assert_eq!(pr.mergeable, MergeableState::Unknown);
+        assert_eq!(pr.state, PullRequestState::Merged);
+    }
+
+    #[test]
+    fn test_pull_request_details_from_graphql() {
+        let details = pull_request_details_from_graphql(&json!({
+            "number": 12,
+            "body": "  ",
+            "changedFiles": 140,
+            "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "state": "PENDING", "contexts": { "nodes": [
+                { "__typename": "CheckRun", "name": "test", "status": "COMPLETED", "conclusion": "SKIPPED" },
+                { "__typename": "CheckRun", "name": "lint", "status": "COMPLETED", "conclusion": "TIMED_OUT" },
+                { "__typename": "CheckRun", "name": "build", "status": "IN_PROGRESS", "conclusion": null },
+                { "__typename": "StatusContext", "context": "ci/legacy", "state": "ERROR" }
+            ] } } } }] },
+            "reviews": { "nodes": [{ "author": { "login": "hubot" }, "state": "CHANGES_REQUESTED", "submittedAt": "2026-01-02T03:04:05Z" }] },
+            "files": { "nodes": [{ "path": "src/main.rs", "additions": 10, "deletions": 2 }] }
+        }));
+        assert_eq!(details.body, None);
+        assert_eq!(details.pull_request.checks, CheckStatus::Pending);
+        let checks: Vec<_> = details.checks.iter().map(|c| (c.name.as_str(), c.status)).collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("test", CheckStatus::Success),
+                ("lint", CheckStatus::Failure),
+                ("build", CheckStatus::Pending),
+                ("ci/legacy", CheckStatus::Failure),
+            ]
+        );
+        assert_eq!(details.reviews[0].state, ReviewState::ChangesRequested);
+        assert_eq!(details.files[0].additions, 10);
+        assert_eq!(details.changed_files, 140);
+    }
+}
@@ -0,0 +1,81 @@
+//! Mapping from the JSON of the releases API to `Release` and `ReleaseAsset`.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::domain::release::{Release, ReleaseAsset};
+
+/// An item of `GET /repos/{owner}/{repo}/releases`
+pub fn release_from_json(value: &Value) -> Release {
+    Release {
+        id: value["id"].as_u64().unwrap_or_default(),
+        tag_name: value["tag_name"].as_str().unwrap_or_default().to_string(),
+        name: value["name"].as_str().filter(|n| !n.is_empty()).map(String::from),
+        body: value["body"].as_str().filter(|b| !b.trim().is_empty()).map(String::from),
+        draft: value["draft"] == true,
+        prerelease: value["prerelease"] == true,
+        author: value["author"]["login"].as_str().unwrap_or("ghost").to_string(),
+        created_at: date(&value["created_at"]),
+        published_at: date(&value["published_at"]),
+        html_url: value["html_url"].as_str().unwrap_or_default().to_string(),
+        assets: value["assets"]
+            .as_array()
+            .map(|assets| assets.iter().map(asset_from_json).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// An asset of a release, or the response of an upload
+pub fn asset_from_json(value: &Value) -> ReleaseAsset {
+    ReleaseAsset {
+        id: value["id"].as_u64().unwrap_or_default(),
+        name: value["name"].as_str().unwrap_or_default().to_string(),
+        size_in_bytes: value["size"].as_u64().unwrap_or_default(),
+        download_count: value["download_count"].as_u64().unwrap_or_default(),
+        content_type: value["content_type"].as_str().unwrap_or_default().to_string(),
+        created_at: date(&value["created_at"]).unwrap_or_else(Utc::now),
+        browser_download_url: value["browser_download_url"].as_str().unwrap_or_default().to_string(),
+    }
+}
+
+fn date(value: &Value) -> Option<DateTime<Utc>> {
+    value.as_str().and_then(|d| d.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_release_from_json() {
+        let release = release_from_json(&json!({
+            "id": 42,
+            "tag_name": "v1.2.0-rc.1",
+            "name": "",
+            "body": "Fixes\n",
+            "draft": false,
+            "prerelease": true,
+            "author": { "login": "octocat" },
+            "created_at": "2026-01-02T03:04:05Z",
+            "published_at": null,
+            "html_url": "https://github.com/acme/cli/releases/tag/v1.2.0-rc.1",
+            "assets": [{
+                "id": 7,
+                "name": "ghr-linux.tar.gz",
+                "size": 2048,
+                "download_count": 12,
+                "content_type": "application/gzip",
+                "created_at": "2026-01-02T03:05:00Z",
+                "browser_download_url": "https://github.com/acme/cli/releases/download/v1.2.0-rc.1/ghr-linux.tar.gz"
+            }]
+        }));
+
+        assert_eq!(release.title(), "v1.2.0-rc.1");
+        assert!(release.prerelease);
+        assert_eq!(release.published_at, None);
+        assert_eq!(release.date().map(|d| d.to_rfc3339()), Some("2026-01-02T03:04:05+00:00".to_string()));
+        assert_eq!(release.assets.len(), 1);
+        assert_eq!(release.assets[0].size_in_bytes, 2048);
+        assert_eq!(release.assets[0].download_count, 12);
+    }
+}
@@ -0,0 +1,61 @@
+//! Mapping from the JSON of the search API to search hits.
+
+use serde_json::Value;
+use crate::domain::search::{CodeHit, IssueHit};
+use crate::infra::github::issue::{is_pull_request, issue_from_json};
+
+/// An item of `GET /search/issues`
+pub fn issue_hit_from_json(value: &Value) -> IssueHit {
+    // Search items have no repository object, only https://api.github.com/repos/{owner}/{repo}
+    let repository = value["repository_url"]
+        .as_str()
+        .and_then(|url| url.split_once("/repos/"))
+        .map(|(_, repo)| repo.to_string())
+        .unwrap_or_default();
+    IssueHit {
+        repository,
+        pull_request: is_pull_request(value),
+        issue: issue_from_json(value),
+    }
+}
+
+/// An item of `GET /search/code` requested with text matches
+pub fn code_hit_from_json(value: &Value) -> CodeHit {
+    CodeHit {
+        repository: value["repository"]["full_name"].as_str().unwrap_or_default().to_string(),
+        path: value["path"].as_str().unwrap_or_default().to_string(),
+        html_url: value["html_url"].as_str().unwrap_or_default().to_string(),
+        fragment: value["text_matches"][0]["fragment"].as_str().map(String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hits_from_github() {
+        let hit = issue_hit_from_json(&json!({
+            "number": 7,
+            "title": "Add search",
+            "state": "open",
+            "repository_url": "https://api.github.com/repos/acme/api",
+            "pull_request": { "url": "https://api.github.com/repos/acme/api/pulls/7" },
+            "created_at": "2024-03-01T10:00:00Z",
+            "updated_at": "2024-03-01T10:00:00Z"
+        }));
+        assert_eq!(hit.repository, "acme/api");
+        assert!(hit.pull_request);
+        assert_eq!(hit.issue.number, 7);
+
+        let code = code_hit_from_json(&json!({
+            "path": "src/main.rs",
+            "html_url": "https://github.com/acme/api/blob/abc/src/main.rs",
+            "repository": { "full_name": "acme/api" },
+            "text_matches": [{ "fragment": "fn main() {" }]
+        }));
+        assert_eq!(code.repository, "acme/api");
+        assert_eq!(code.fragment.as_deref(), Some("fn main() {"));
+    }
+}
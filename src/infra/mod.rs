@@ -1,3 +1,4 @@
 pub mod github;
 pub mod config;
+pub mod git;
 pub mod utils;
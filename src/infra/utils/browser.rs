@@ -0,0 +1,24 @@
+use std::process::{Command, Stdio};
+use crate::domain::error::{GhTuiError, Result};
+
+/// Open `url` with the desktop's default browser, without waiting for it.
+pub fn open_url(url: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        // Browsers log to the terminal, which would garble the TUI
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| GhTuiError::Unknown(format!("cannot open a browser: {}", e)))
+}
@@ -3,15 +3,28 @@ mod infra;
 mod app;
 mod presenter;
 
+use std::process::ExitCode;
 use clap::Parser;
-pub use presenter::cli::{Cli, Commands, AuthCommands, ArtifactCommands};
-use domain::error::Result;
+pub use presenter::cli::{Cli, Commands, AuthCommands, ArtifactCommands, DfCommands};
+use domain::error::{GhTuiError, Result};
 use tracing_subscriber::{fmt, EnvFilter};
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(GhTuiError::Exit(code)) => ExitCode::from(code),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run() -> Result<()> {
     dotenvy::dotenv().ok();
     let cli = Cli::parse();
+    let output = cli.output();
 
     match cli.command {
         Commands::Tui => {
@@ -36,18 +49,48 @@ async fn main() -> Result<()> {
                             presenter::cli::auth::logout()?;
                         }
                         AuthCommands::Status => {
-                            presenter::cli::auth::status().await?;
+                            presenter::cli::auth::status(&output).await?;
                         }
                     }
                 }
-                Commands::Ls { owner, private, public, all } => {
-                    presenter::cli::repos::list_repos(owner, private, public, all).await?;
+                Commands::Ls { args } => {
+                    presenter::cli::repos::list_repos(args, &output).await?;
+                }
+                Commands::Repo { action } => {
+                    presenter::cli::repos::handle_repo(action, &output).await?;
                 }
                 Commands::Artifacts { action } => {
-                    presenter::cli::artifacts::handle_artifacts(action).await?;
+                    presenter::cli::artifacts::handle_artifacts(action, &output).await?;
+                }
+                Commands::Df { action: Some(DfCommands::History { repo, limit, json }), .. } => {
+                    presenter::cli::df::show_history(repo, limit, json, &output)?;
+                }
+                Commands::Df { action: None, args } => {
+                    presenter::cli::df::handle_df(args, &output).await?;
+                }
+                Commands::Retention { action } => {
+                    presenter::cli::retention::handle_retention(action, &output).await?;
+                }
+                Commands::Issue { action } => {
+                    presenter::cli::issues::handle_issue(action, &output).await?;
+                }
+                Commands::Pr { action } => {
+                    presenter::cli::prs::handle_pr(action, &output).await?;
+                }
+                Commands::Release { action } => {
+                    presenter::cli::releases::handle_release(action, &output).await?;
+                }
+                Commands::Search { action } => {
+                    presenter::cli::search::handle_search(action, &output).await?;
+                }
+                Commands::Branches { action } => {
+                    presenter::cli::branches::handle_branches(action, &output).await?;
+                }
+                Commands::Protect { action } => {
+                    presenter::cli::protect::handle_protect(action, &output).await?;
                 }
-                Commands::Df => {
-                    presenter::cli::df::handle_df().await?;
+                Commands::Audit { action } => {
+                    presenter::cli::audit::handle_audit(action, &output).await?;
                 }
                 Commands::Tui => unreachable!(),
             }
@@ -6,21 +6,30 @@ use crate::domain::error::Result;
 use crate::domain::artifact::Artifact;
 use crate::infra::github::auth::AuthManager;
 use crate::app::artifact_use_case::ArtifactUseCase;
+use crate::presenter::output::Output;
 
-pub async fn handle_artifacts(command: ArtifactCommands) -> Result<()> {
+/// Default fields of an artifact when `--columns` is not given, e.g. with
+/// `--format csv`
+pub const ARTIFACT_COLUMNS: &[&str] = &["id", "name", "repository_owner", "repository_name", "size_in_bytes", "created_at", "expires_at"];
+
+pub async fn handle_artifacts(command: ArtifactCommands, output: &Output) -> Result<()> {
     let auth = AuthManager::new()?;
     let client = Arc::new(auth.get_client()?);
     let use_case = ArtifactUseCase::new(client);
 
     match command {
         ArtifactCommands::List { owner, repo } => {
-            println!("{}", "📚 Fetching artifacts...".cyan());
+            output.status("📚 Fetching artifacts...".cyan());
             let artifacts = if let (Some(o), Some(r)) = (owner, repo) {
                 use_case.list_repo_artifacts(&o, &r).await?
             } else {
                 use_case.list_all_artifacts().await?
             };
 
+            if !output.is_styled_table() {
+                return output.print(&artifacts, ARTIFACT_COLUMNS);
+            }
+
             if artifacts.is_empty() {
                 println!("{}", "No artifacts found".yellow());
                 return Ok(());
@@ -0,0 +1,176 @@
+use std::sync::Arc;
+use colored::*;
+use comfy_table::*;
+use serde_json::{Map, Value};
+use crate::app::audit_use_case::AuditUseCase;
+use crate::domain::audit::{AuditReport, AuditRule, AuditRules, CheckOutcome};
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::repo::RepoQuery;
+use crate::infra::config;
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::AuditCommands;
+use crate::presenter::output::Output;
+
+/// Exit code used when at least one check fails
+const VIOLATIONS_EXIT_CODE: u8 = 2;
+/// Exit code used when no check fails but some could not be read
+const UNREADABLE_EXIT_CODE: u8 = 3;
+
+pub async fn handle_audit(command: AuditCommands, output: &Output) -> Result<()> {
+    match command {
+        AuditCommands::Repos { org, rules, filter, filters, json } => {
+            let rules = match rules {
+                Some(ref path) => config::load_audit_rules(path)?,
+                None => AuditRules::default(),
+            };
+            let auth = AuthManager::new()?;
+            let use_case = AuditUseCase::new(Arc::new(auth.get_client()?));
+
+            output.status(format!("🔍 Auditing repositories of {}...", org).cyan());
+            let query = RepoQuery { text: filter, ..filters.query() };
+            let report = use_case.audit_repos(&org, &rules, &query).await?;
+
+            if json || output.is_json() {
+                output.print_json(&report)?;
+            } else if !output.is_styled_table() {
+                let columns = audit_columns(&report);
+                let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+                output.print(&audit_rows(&report), &columns)?;
+            } else {
+                display_report(&report);
+            }
+
+            if report.violations > 0 {
+                return Err(GhTuiError::Exit(VIOLATIONS_EXIT_CODE));
+            }
+            if report.unknown > 0 {
+                return Err(GhTuiError::Exit(UNREADABLE_EXIT_CODE));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Name of a rule's column in the flattened rows, e.g. `secret_scanning`
+fn rule_column(rule: AuditRule) -> String {
+    serde_json::to_value(rule).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default()
+}
+
+/// Default fields of the flattened rows: the repository, each rule checked
+/// and the number of checks passed
+fn audit_columns(report: &AuditReport) -> Vec<String> {
+    let mut columns = vec!["repo".to_string()];
+    columns.extend(report.rules.iter().map(|r| rule_column(*r)));
+    columns.push("passed".into());
+    columns
+}
+
+/// One row per repository for `--format csv|tsv|ndjson`, `--columns` and
+/// `--template`, with `pass`, `fail` or `unknown` per rule.
+fn audit_rows(report: &AuditReport) -> Vec<Map<String, Value>> {
+    report
+        .repos
+        .iter()
+        .map(|audit| {
+            let mut row = Map::new();
+            row.insert("repo".into(), audit.repo.clone().into());
+            for rule in &report.rules {
+                let outcome = audit.outcome(*rule).unwrap_or(CheckOutcome::Unknown);
+                row.insert(rule_column(*rule), serde_json::to_value(outcome).unwrap_or(Value::Null));
+            }
+            row.insert("passed".into(), audit.count(CheckOutcome::Pass).into());
+            row.insert("checks".into(), audit.checks.len().into());
+            row
+        })
+        .collect()
+}
+
+fn display_report(report: &AuditReport) {
+    if report.repos.is_empty() {
+        println!("{}", "No repositories match the filters".yellow());
+        return;
+    }
+
+    let mut header = vec!["Repo".to_string()];
+    header.extend(report.rules.iter().map(|r| r.label().to_string()));
+    header.push("Passed".into());
+
+    let mut table = Table::new();
+    table
+        .set_header(header)
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for audit in &report.repos {
+        let mut row = vec![audit.repo.clone()];
+        for rule in &report.rules {
+            let cell = match audit.outcome(*rule) {
+                Some(CheckOutcome::Pass) => "✓".green(),
+                Some(CheckOutcome::Fail) => "✗".red(),
+                _ => "?".yellow(),
+            };
+            row.push(cell.to_string());
+        }
+        row.push(format!("{}/{}", audit.count(CheckOutcome::Pass), audit.checks.len()));
+        table.add_row(row);
+    }
+    println!("\n{}", table);
+
+    if report.unknown > 0 {
+        println!(
+            "{}",
+            format!("? {} checks could not be read and count as not passed (they usually need admin access)", report.unknown).yellow()
+        );
+    }
+
+    let score = format!("Score: {:.0}%", report.score);
+    let score = if report.score >= 90.0 {
+        score.green()
+    } else if report.score >= 70.0 {
+        score.yellow()
+    } else {
+        score.red()
+    };
+    println!("{}", score.bold());
+
+    let failing = report.repos.iter().filter(|r| r.count(CheckOutcome::Fail) > 0).count();
+    if report.violations == 0 {
+        println!("{}", format!("✓ All {} repositories pass", report.repos.len()).green());
+    } else {
+        println!(
+            "{}",
+            format!(
+                "✗ {} violations in {} of {} repositories",
+                report.violations,
+                failing,
+                report.repos.len()
+            )
+            .red()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::audit::{RepoAudit, RuleCheck};
+
+    #[test]
+    fn test_audit_rows_have_a_column_per_rule() {
+        let check = |rule, outcome| RuleCheck { rule, outcome };
+        let report = AuditReport::new(
+            "acme".into(),
+            vec![AuditRule::License, AuditRule::SecretScanning],
+            vec![RepoAudit {
+                repo: "acme/api".into(),
+                checks: vec![check(AuditRule::License, CheckOutcome::Pass), check(AuditRule::SecretScanning, CheckOutcome::Unknown)],
+            }],
+        );
+
+        assert_eq!(audit_columns(&report), vec!["repo", "license", "secret_scanning", "passed"]);
+        let rows = audit_rows(&report);
+        assert_eq!(rows[0]["license"], "pass");
+        assert_eq!(rows[0]["secret_scanning"], "unknown");
+        assert_eq!((rows[0]["passed"].clone(), rows[0]["checks"].clone()), (1.into(), 2.into()));
+    }
+}
@@ -1,7 +1,6 @@
-use std::sync::Arc;
-use crate::infra::github::auth::AuthManager;
-use crate::domain::error::Result;
+use crate::domain::error::{GhTuiError, Result};
 use crate::app::auth_use_case::AuthUseCase;
+use crate::presenter::output::Output;
 use colored::*;
 use dialoguer::Password;
 
@@ -30,7 +29,13 @@ pub fn logout() -> Result<()> {
     auth.logout()
 }
 
-pub async fn status() -> Result<()> {
+pub async fn status(output: &Output) -> Result<()> {
+    // The status is only meant to be read by a human
+    if output.is_machine_readable() || !output.columns.is_empty() {
+        return Err(GhTuiError::InvalidInput(
+            "ghr auth status does not support --format, --columns, --template or --jq".into(),
+        ));
+    }
     let auth = AuthUseCase::new()?;
     auth.status().await
 }
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+use chrono::Utc;
+use colored::*;
+use comfy_table::*;
+use dialoguer::Confirm;
+use serde::Serialize;
+use crate::app::branch_use_case::BranchUseCase;
+use crate::domain::branch::{Branch, BranchFilter, PruneCandidate, PruneReason, PullRequestState};
+use crate::domain::error::Result;
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::BranchCommands;
+use crate::presenter::output::Output;
+use crate::presenter::template::relative_time;
+
+/// Default fields of a branch in `ghr branches list` when `--columns` is not
+/// given; the JSON formats also keep its pull request
+pub const BRANCH_COLUMNS: &[&str] = &["name", "protected", "last_commit_at", "last_commit_author", "ahead", "behind"];
+/// Default fields of `ghr branches prune` when `--columns` is not given
+pub const PRUNE_COLUMNS: &[&str] = &["name", "reason", "status"];
+
+/// A pruned (or, with `--dry-run`, prunable) branch, as printed with `--format`
+#[derive(Debug, Serialize)]
+struct PruneRow {
+    name: String,
+    reason: PruneReason,
+    status: RepoActionStatus,
+}
+
+fn prune_rows(candidates: &[PruneCandidate], status: impl Fn(&str) -> RepoActionStatus) -> Vec<PruneRow> {
+    candidates
+        .iter()
+        .map(|c| PruneRow { name: c.branch.name.clone(), reason: c.reason, status: status(&c.branch.name) })
+        .collect()
+}
+
+pub async fn handle_branches(command: BranchCommands, output: &Output) -> Result<()> {
+    let auth = AuthManager::new()?;
+    let use_case = BranchUseCase::new(Arc::new(auth.get_client()?));
+
+    match command {
+        BranchCommands::List { repo, merged, stale_days } => {
+            output.status(format!("🌿 Fetching branches of {}...", repo).cyan());
+            let branches = use_case.list(&repo, &BranchFilter { merged, stale_days }).await?;
+            if !output.is_styled_table() {
+                return output.print(&branches, BRANCH_COLUMNS);
+            }
+            if branches.is_empty() {
+                println!("{}", "No branches match the filters".yellow());
+                return Ok(());
+            }
+            println!("\n{}", branch_table(&branches, None));
+            println!("{}", format!("{} branches", branches.len()).cyan());
+            Ok(())
+        }
+        BranchCommands::Prune { repo, merged, stale_days, dry_run, yes } => {
+            output.status(format!("🌿 Fetching branches of {}...", repo).cyan());
+            let candidates = use_case.prune_candidates(&repo, merged, stale_days).await?;
+            if !output.is_styled_table() && (candidates.is_empty() || dry_run) {
+                return output.print(&prune_rows(&candidates, |_| RepoActionStatus::Pending), PRUNE_COLUMNS);
+            }
+            if candidates.is_empty() {
+                println!("{}", "✓ No branches to prune".green());
+                return Ok(());
+            }
+
+            let branches: Vec<Branch> = candidates.iter().map(|c| c.branch.clone()).collect();
+            let reasons: Vec<String> = candidates.iter().map(|c| c.reason.to_string()).collect();
+            if output.is_styled_table() {
+                println!("\n{}", branch_table(&branches, Some(&reasons)));
+            }
+
+            if dry_run {
+                println!("{}", format!("Dry run: {} branches would be deleted", candidates.len()).cyan());
+                return Ok(());
+            }
+            let confirmed = yes
+                || Confirm::new()
+                    .with_prompt(format!("Delete {} branches of {}?", candidates.len(), repo))
+                    .default(false)
+                    .interact()?;
+            if !confirmed {
+                if !output.is_styled_table() {
+                    return output.print(&prune_rows(&candidates, |_| RepoActionStatus::Skipped), PRUNE_COLUMNS);
+                }
+                println!("{}", "Cancelled".yellow());
+                return Ok(());
+            }
+
+            let names: Vec<String> = branches.into_iter().map(|b| b.name).collect();
+            let results = use_case.delete(&repo, &names).await;
+            if !output.is_styled_table() {
+                let status = |name: &str| {
+                    results.iter().find(|(n, _)| n == name).map(|(_, s)| s.clone()).unwrap_or(RepoActionStatus::Skipped)
+                };
+                return output.print(&prune_rows(&candidates, status), PRUNE_COLUMNS);
+            }
+
+            let mut table = Table::new();
+            table
+                .set_header(vec!["Branch", "Status"])
+                .load_preset(presets::UTF8_FULL)
+                .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+            for (branch, status) in &results {
+                let status = match status {
+                    RepoActionStatus::Failed(e) => format!("✗ {}", e).red(),
+                    _ => "✓ deleted".green(),
+                };
+                table.add_row(vec![branch.clone(), status.to_string()]);
+            }
+            println!("\n{}", table);
+
+            let failed = results.iter().filter(|(_, s)| matches!(s, RepoActionStatus::Failed(_))).count();
+            let summary = format!("{} deleted, {} failed", results.len() - failed, failed);
+            if failed > 0 {
+                println!("{}", summary.yellow());
+            } else {
+                println!("{}", summary.green());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Branches with an extra "Reason" column when pruning.
+fn branch_table(branches: &[Branch], reasons: Option<&[String]>) -> Table {
+    let now = Utc::now();
+    let mut header = vec!["Branch", "Last commit", "Author", "Ahead/Behind", "Pull request"];
+    if reasons.is_some() {
+        header.push("Reason");
+    }
+
+    let mut table = Table::new();
+    table
+        .set_header(header)
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for (i, branch) in branches.iter().enumerate() {
+        let mut name = branch.name.clone();
+        if branch.is_default {
+            name.push_str(" (default)");
+        }
+        if branch.protected {
+            name.push_str(" 🔒");
+        }
+        let pull_request = match branch.pull_request {
+            Some(ref pr) => {
+                let label = format!("#{} {}", pr.number, pr.state);
+                match pr.state {
+                    PullRequestState::Open => label.green(),
+                    PullRequestState::Draft => label.dimmed(),
+                    PullRequestState::Merged => label.magenta(),
+                    PullRequestState::Closed => label.red(),
+                }
+                .to_string()
+            }
+            None => "-".into(),
+        };
+
+        let mut row = vec![
+            if branch.is_default { name.bold().to_string() } else { name },
+            branch.last_commit_at.map(|at| relative_time(at, now)).unwrap_or_else(|| "-".into()),
+            branch.last_commit_author.clone().unwrap_or_else(|| "-".into()),
+            if branch.is_default { "-".into() } else { format!("↑{} ↓{}", branch.ahead, branch.behind) },
+            pull_request,
+        ];
+        if let Some(reasons) = reasons {
+            row.push(reasons[i].clone());
+        }
+        table.add_row(row);
+    }
+    table
+}
@@ -1,81 +1,359 @@
+use std::io::IsTerminal;
 use std::sync::Arc;
 use colored::*;
-use crate::domain::error::Result;
+use serde::Serialize;
+use crate::domain::error::{GhTuiError, Result};
 use crate::infra::github::auth::AuthManager;
 use crate::app::artifact_use_case::ArtifactUseCase;
+use comfy_table::{modifiers, presets, Table};
 use dialoguer::{MultiSelect, Select};
-use crate::domain::storage::StorageItemType;
+use crate::domain::storage::{group_items, StorageGroup, StorageGrouping, StorageItem, StorageItemType, StorageUsageReport};
+use crate::domain::billing::{AccountType, QuotaSource};
+use crate::domain::forecast::{ForecastBasis, StorageForecast};
+use crate::app::storage_history_use_case::StorageHistoryUseCase;
+use crate::infra::config::ConfigManager;
+use crate::infra::config::storage::FileStorageHistory;
+use crate::presenter::cli::DfArgs;
+use crate::presenter::output::{Output, OutputFormat};
+use crate::presenter::template::human_size;
 
-pub async fn handle_df() -> Result<()> {
+/// Exit code used when `--fail-above` is exceeded
+const THRESHOLD_EXIT_CODE: u8 = 2;
+
+pub async fn handle_df(mut args: DfArgs, output: &Output) -> Result<()> {
+    // The CSV layout of items and groups is fixed
+    if !output.columns.is_empty() {
+        return Err(GhTuiError::InvalidInput(
+            "ghr df does not support --columns; use --format json with --jq to pick fields".into(),
+        ));
+    }
     let auth = AuthManager::new()?;
     let client = Arc::new(auth.get_client()?);
     let use_case = ArtifactUseCase::new(client);
 
-    println!("{}", "📊 Calculating storage usage...".cyan());
-
-    let report = use_case.get_storage_usage().await?;
-    let used = report.total_used;
-    let max = report.max_allowed;
+    match output.format {
+        OutputFormat::Table => {}
+        OutputFormat::Json => args.json = true,
+        OutputFormat::Csv => args.csv = true,
+        other => {
+            return Err(GhTuiError::InvalidInput(format!(
+                "ghr df does not support --format {:?}; use json or csv",
+                other
+            ).to_lowercase()));
+        }
+    }
+    if output.jq.is_some() {
+        if args.csv {
+            return Err(GhTuiError::InvalidInput("--jq cannot be combined with CSV output".into()));
+        }
+        args.json = true;
+    }
 
-    let used_mo = used as f64 / 1_048_576.0;
-    let max_mo = max as f64 / 1_048_576.0;
-    let percentage = if max > 0 { (used as f64 / max as f64) * 100.0 } else { 0.0 };
+    // Machine-readable output must keep stdout clean
+    let machine_output = args.json || args.csv || output.template.is_some();
+    let interactive = !args.no_interactive
+        && !machine_output
+        && std::io::stdout().is_terminal()
+        && std::io::stdin().is_terminal();
 
-    println!("\n{}", "Actions Storage Usage (Artifacts, Packages, Caches):".bold());
-    
-    let bar_width = 40;
-    let filled_width = if max > 0 {
-        ((percentage / 100.0) * bar_width as f64) as usize
+    if machine_output {
+        eprintln!("{}", "📊 Calculating storage usage...".cyan());
     } else {
-        0
-    };
-    let filled_width = std::cmp::min(filled_width, bar_width);
-    
-    let bar_color = if percentage > 90.0 {
-        Color::Red
-    } else if percentage > 70.0 {
-        Color::Yellow
+        println!("{}", "📊 Calculating storage usage...".cyan());
+    }
+
+    let config = ConfigManager::new()?.load()?;
+    let include_releases = args.include_releases || config.storage.include_release_assets;
+    let report = use_case.get_storage_usage(&config.storage.quota_overrides(), include_releases).await?;
+
+    if args.record || config.storage.record_history {
+        let history = StorageHistoryUseCase::new(Arc::new(FileStorageHistory::new()?));
+        history.record(&report)?;
+        if !machine_output {
+            println!("{}", "📈 Usage snapshot saved (see `ghr df history`)".cyan());
+        }
+    }
+
+    let forecasts = FileStorageHistory::new()
+        .and_then(|history| StorageHistoryUseCase::new(Arc::new(history)).forecast(&report))
+        .ok();
+
+    // Filter out small items and sort by size descending
+    let mut items: Vec<_> = report.items.iter()
+        .filter(|i| i.size_in_bytes >= args.min_size)
+        .cloned()
+        .collect();
+    items.sort_by_key(|i| std::cmp::Reverse(i.size_in_bytes));
+
+    let groups = args.group_by.map(|grouping| {
+        let mut groups = group_items(&items, grouping);
+        if let Some(top) = args.top {
+            groups.truncate(top);
+        }
+        groups
+    });
+    if groups.is_none() {
+        if let Some(top) = args.top {
+            items.truncate(top);
+        }
+    }
+
+    if output.template.is_some() {
+        match groups {
+            Some(ref groups) => output.print(groups, &[])?,
+            None => output.print(&items, &[])?,
+        }
+    } else if args.json {
+        let json = JsonReport::new(&report, forecasts.as_deref(), groups.as_deref(), &items);
+        output.print_json(&json)?;
+    } else if args.csv {
+        match groups {
+            Some(ref groups) => print!("{}", groups_to_csv(groups)),
+            None => print!("{}", items_to_csv(&items)),
+        }
     } else {
-        Color::Green
-    };
+        display_account_usage(&report);
+        if let Some(ref forecasts) = forecasts {
+            display_forecasts(forecasts);
+        }
 
-    let bar = format!(
-        "[{}{}]",
-        "▆".repeat(filled_width).color(bar_color),
-        " ".repeat(bar_width - filled_width)
-    );
+        if items.is_empty() {
+            println!("\n{}", format!("No individual items (>{}) found to manage.", human_size(args.min_size as f64)).yellow());
+        } else if let (Some(grouping), Some(groups)) = (args.group_by, groups.as_ref()) {
+            display_group_table(groups, grouping);
 
-    println!("{}", bar);
-    println!(
-        "{:.2} Mo / {:.2} Mo ({:.1}%)",
-        used_mo,
-        max_mo,
-        percentage
-    );
+            if interactive {
+                let options: Vec<String> = groups.iter().map(|g| {
+                    format!("{:<40} {:>10} ({} items)", g.key, human_size(g.total_bytes as f64), g.count)
+                }).collect();
 
-    if percentage > 90.0 {
-        println!("\n{}", "⚠️  Warning: You are almost out of storage space!".red().bold());
+                let choice = Select::new()
+                    .with_prompt("Drill down into a group (Esc to skip)")
+                    .items(&options)
+                    .default(0)
+                    .interact_opt()?;
+
+                if let Some(idx) = choice {
+                    let key = &groups[idx].key;
+                    items.retain(|i| &grouping.key(i) == key);
+                    select_and_delete(&use_case, &items).await?;
+                }
+            }
+        } else if interactive {
+            select_and_delete(&use_case, &items).await?;
+        } else {
+            display_item_table(&items);
+        }
     }
 
-    // Filter out items smaller than 1KB and sort by size descending
-    let mut items: Vec<_> = report.items.into_iter()
-        .filter(|i| i.size_in_bytes >= 1024)
+    if let Some(threshold) = args.fail_above {
+        check_threshold(&report, threshold)?;
+    }
+
+    Ok(())
+}
+
+/// `--fail-above`: accounts without a known quota cannot be checked, so they
+/// are reported, and the check fails when none of the accounts can be.
+fn check_threshold(report: &StorageUsageReport, threshold: f64) -> Result<()> {
+    let unknown: Vec<&str> = report
+        .accounts
+        .iter()
+        .filter(|a| a.usage_percentage().is_none())
+        .map(|a| a.account.as_str())
         .collect();
-    items.sort_by(|a, b| b.size_in_bytes.cmp(&a.size_in_bytes));
+    if unknown.len() == report.accounts.len() {
+        return Err(GhTuiError::Config(
+            "--fail-above cannot be checked: no account has a known quota; set `storage.default_quota_mb` in the ghr config".into(),
+        ));
+    }
+    if !unknown.is_empty() {
+        eprintln!(
+            "{}",
+            format!("⚠ --fail-above ignores accounts with an unknown quota: {}", unknown.join(", ")).yellow()
+        );
+    }
+
+    if let Some(percentage) = report.max_usage_percentage().filter(|p| *p > threshold) {
+        eprintln!(
+            "{}",
+            format!("✗ Storage usage {:.1}% is above the {:.1}% threshold", percentage, threshold).red()
+        );
+        return Err(GhTuiError::Exit(THRESHOLD_EXIT_CODE));
+    }
+    Ok(())
+}
+
+pub fn show_history(repo: Option<String>, limit: usize, json: bool, output: &Output) -> Result<()> {
+    let use_case = StorageHistoryUseCase::new(Arc::new(FileStorageHistory::new()?));
+    let points = use_case.series(repo.as_deref(), Some(limit))?;
 
-    if items.is_empty() {
-        println!("\n{}", "No individual items (>1KB) found to manage.".yellow());
+    if json || output.is_json() {
+        return output.print_json(&points);
+    }
+
+    if points.is_empty() {
+        println!("{}", "No storage snapshots recorded yet.".yellow());
+        println!("Run {} or set {} in the ghr config.", "ghr df --record".cyan(), "storage.record_history = true".cyan());
         return Ok(());
     }
 
+    let title = match repo {
+        Some(ref r) => format!("Storage usage history for {}:", r),
+        None => "Storage usage history:".to_string(),
+    };
+    println!("\n{}", title.bold());
+
+    let values: Vec<u64> = points.iter().map(|p| p.used).collect();
+    println!("{}", sparkline(&values).cyan());
+
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Date", "Used", "Δ", "Quota", "Usage"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for p in &points {
+        let delta = match p.delta {
+            0 => "-".to_string(),
+            d if d > 0 => format!("+{}", human_size(d as f64)).red().to_string(),
+            d => format!("-{}", human_size(d.unsigned_abs() as f64)).green().to_string(),
+        };
+        let quota = p.quota.map(|q| human_size(q as f64)).unwrap_or_else(|| "-".to_string());
+        let usage = p.usage_percentage.map(|u| format!("{:.1}%", u)).unwrap_or_else(|| "-".to_string());
+        table.add_row(vec![
+            p.taken_at.format("%Y-%m-%d %H:%M").to_string(),
+            human_size(p.used as f64),
+            delta,
+            quota,
+            usage,
+        ]);
+    }
+
+    println!("{}", table);
+    Ok(())
+}
+
+/// Render values as a one-line bar chart, scaled between their min and max.
+fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|v| {
+            if range == 0 {
+                BARS[BARS.len() / 2]
+            } else {
+                BARS[((v - min) as f64 / range as f64 * (BARS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+fn display_forecasts(forecasts: &[StorageForecast]) {
+    println!("\n{}", "Forecast:".bold());
+    for forecast in forecasts {
+        println!("\n{}", forecast.account.bold());
+        display_forecast(forecast);
+    }
+}
+
+fn display_forecast(forecast: &StorageForecast) {
+    let basis = match forecast.basis {
+        ForecastBasis::Snapshots => "usage history",
+        ForecastBasis::CreationDates => "artifact creation dates",
+    };
+    let growth = forecast.growth_per_day.max(0.0) as u64;
+    println!("Growth: +{}/day (based on {})", human_size(growth as f64), basis);
+
+    match forecast.days_until_full {
+        Some(days) if days <= 0.0 => println!("{}", "Quota already reached".red().bold()),
+        Some(days) => {
+            let date = chrono::Utc::now() + chrono::Duration::seconds((days * 86_400.0) as i64);
+            let message = format!("Quota reached in ~{:.0} days ({})", days.ceil(), date.format("%Y-%m-%d"));
+            let message = if days < 14.0 {
+                message.red()
+            } else if days < 30.0 {
+                message.yellow()
+            } else {
+                message.green()
+            };
+            println!("{}", message);
+        }
+        None if forecast.quota.is_none() => println!("{}", "Quota unknown, cannot estimate when it will be reached".yellow()),
+        None => println!("{}", "Quota not expected to be reached at the current rate".green()),
+    }
+
+    let expiring: Vec<String> = forecast.expiring.iter()
+        .map(|w| format!("{} within {} days ({} items)", human_size(w.bytes as f64), w.days, w.count))
+        .collect();
+    println!("Freed by expiry: {}", expiring.join(", "));
+}
+
+fn display_account_usage(report: &StorageUsageReport) {
+    println!("\n{}", "Actions Storage Usage (Artifacts, Packages, Caches):".bold());
+
+    let mut almost_full = false;
+    for account in &report.accounts {
+        let kind = match account.account_type {
+            AccountType::User => "user",
+            AccountType::Organization => "org",
+        };
+        println!("\n{} ({})", account.account.bold(), kind);
+
+        let used = human_size(account.used_bytes() as f64);
+        match (account.quota_bytes(), account.usage_percentage()) {
+            (Some(quota), Some(percentage)) => {
+                println!("{}", usage_bar(percentage));
+                let source = match account.quota_source() {
+                    QuotaSource::Config => " [quota from config]",
+                    _ => "",
+                };
+                println!("{} / {} ({:.1}%){}", used, human_size(quota as f64), percentage, source);
+                almost_full |= percentage > 90.0;
+            }
+            _ => {
+                println!("{} / unknown quota", used);
+                println!(
+                    "{}",
+                    format!(
+                        "  Quota not reported by GitHub: set `storage.default_quota_mb` or `storage.quotas_mb.{}` in the ghr config",
+                        account.account
+                    )
+                    .yellow()
+                );
+            }
+        }
+
+        if account.paid_amount() > 0.0 {
+            println!("Estimated paid storage this month: ${:.2}", account.paid_amount());
+        }
+        if let Some(actions) = &account.actions {
+            println!(
+                "Actions minutes: {:.0} / {:.0} included ({:.0} paid)",
+                actions.total_minutes_used, actions.included_minutes, actions.total_paid_minutes_used
+            );
+        }
+    }
+
+    if almost_full {
+        println!("\n{}", "⚠️  Warning: You are almost out of storage space!".red().bold());
+    }
+}
+
+async fn select_and_delete(use_case: &ArtifactUseCase, items: &[StorageItem]) -> Result<()> {
     println!("\n{}", "Detailed Storage Usage:".bold());
     println!("{}", "Select items to delete (Space to toggle, Enter to confirm, Esc to skip):".cyan());
 
     let options: Vec<String> = items.iter().map(|i| {
-        let size = format!("{:.2} MB", i.size_in_bytes as f64 / 1_048_576.0);
+        let size = human_size(i.size_in_bytes as f64);
         let type_label = match i.item_type {
             StorageItemType::Artifact => "ARTIFACT".blue(),
             StorageItemType::Cache => "CACHE   ".magenta(),
+            StorageItemType::ReleaseAsset => "RELEASE ".cyan(),
         };
         format!("{:<10} {:<40} {:>10} [{}/{}]", type_label, i.name, size, i.owner, i.repo)
     }).collect();
@@ -95,7 +373,7 @@ pub async fn handle_df() -> Result<()> {
 
     println!("\n{}", format!("⚠️  Confirm deletion of {} items?", selected_indices.len()).red().bold());
     let confirm = Select::new()
-        .items(&["No, cancel", "Yes, delete them"])
+        .items(["No, cancel", "Yes, delete them"])
         .default(0)
         .interact()?;
 
@@ -110,6 +388,9 @@ pub async fn handle_df() -> Result<()> {
                 StorageItemType::Cache => {
                     use_case.delete_cache(&item.owner, &item.repo, item.id).await?;
                 }
+                StorageItemType::ReleaseAsset => {
+                    use_case.delete_release_asset(&item.owner, &item.repo, item.id).await?;
+                }
             }
             println!("{}", "✓".green());
         }
@@ -120,3 +401,240 @@ pub async fn handle_df() -> Result<()> {
 
     Ok(())
 }
+
+fn usage_bar(percentage: f64) -> String {
+    let bar_width = 40;
+    let filled_width = ((percentage / 100.0) * bar_width as f64) as usize;
+    let filled_width = std::cmp::min(filled_width, bar_width);
+
+    let bar_color = if percentage > 90.0 {
+        Color::Red
+    } else if percentage > 70.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+
+    format!(
+        "[{}{}]",
+        "▆".repeat(filled_width).color(bar_color),
+        " ".repeat(bar_width - filled_width)
+    )
+}
+
+fn display_group_table(groups: &[StorageGroup], grouping: StorageGrouping) {
+    let key_header = match grouping {
+        StorageGrouping::Repo => "Repo",
+        StorageGrouping::Name => "Name",
+        StorageGrouping::Type => "Type",
+        StorageGrouping::Owner => "Owner",
+    };
+
+    let mut table = Table::new();
+    table
+        .set_header(vec![key_header, "Items", "Size", "Share"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for g in groups {
+        table.add_row(vec![
+            g.key.clone(),
+            g.count.to_string(),
+            human_size(g.total_bytes as f64),
+            format!("{:.1}%", g.percentage),
+        ]);
+    }
+
+    println!("\n{}", "Storage Usage by Group:".bold());
+    println!("{}", table);
+}
+
+fn display_item_table(items: &[StorageItem]) {
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Type", "Name", "Repo", "Size"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for i in items {
+        table.add_row(vec![
+            format!("{:?}", i.item_type),
+            i.base_name().to_string(),
+            format!("{}/{}", i.owner, i.repo),
+            human_size(i.size_in_bytes as f64),
+        ]);
+    }
+
+    println!("\n{}", "Detailed Storage Usage:".bold());
+    println!("{}", table);
+}
+
+#[derive(Serialize)]
+struct JsonAccount<'a> {
+    account: &'a str,
+    account_type: AccountType,
+    used_bytes: u64,
+    quota_bytes: Option<u64>,
+    quota_source: QuotaSource,
+    usage_percentage: Option<f64>,
+    estimated_paid_amount: f64,
+    actions_minutes_used: Option<f64>,
+    actions_included_minutes: Option<f64>,
+    actions_paid_minutes: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    total_used: u64,
+    max_allowed: u64,
+    accounts: Vec<JsonAccount<'a>>,
+    forecasts: Option<&'a [StorageForecast]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    groups: Option<&'a [StorageGroup]>,
+    items: &'a [StorageItem],
+}
+
+impl<'a> JsonReport<'a> {
+    fn new(
+        report: &'a StorageUsageReport,
+        forecasts: Option<&'a [StorageForecast]>,
+        groups: Option<&'a [StorageGroup]>,
+        items: &'a [StorageItem],
+    ) -> Self {
+        Self {
+            total_used: report.total_used(),
+            max_allowed: report.max_allowed(),
+            accounts: report.accounts.iter().map(|a| JsonAccount {
+                account: &a.account,
+                account_type: a.account_type,
+                used_bytes: a.used_bytes(),
+                quota_bytes: a.quota_bytes(),
+                quota_source: a.quota_source(),
+                usage_percentage: a.usage_percentage(),
+                estimated_paid_amount: a.paid_amount(),
+                actions_minutes_used: a.actions.as_ref().map(|b| b.total_minutes_used),
+                actions_included_minutes: a.actions.as_ref().map(|b| b.included_minutes),
+                actions_paid_minutes: a.actions.as_ref().map(|b| b.total_paid_minutes_used),
+            }).collect(),
+            forecasts,
+            groups,
+            items,
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn items_to_csv(items: &[StorageItem]) -> String {
+    let mut out = String::from("type,owner,repo,name,id,size_in_bytes\n");
+    for i in items {
+        out.push_str(&format!(
+            "{:?},{},{},{},{},{}\n",
+            i.item_type,
+            csv_field(&i.owner),
+            csv_field(&i.repo),
+            csv_field(i.base_name()),
+            i.id,
+            i.size_in_bytes
+        ));
+    }
+    out
+}
+
+fn groups_to_csv(groups: &[StorageGroup]) -> String {
+    let mut out = String::from("key,count,total_bytes,percentage\n");
+    for g in groups {
+        out.push_str(&format!("{},{},{},{:.2}\n", csv_field(&g.key), g.count, g.total_bytes, g.percentage));
+    }
+    out
+}
+
+/// Parse a threshold such as `90%` or `90` into a percentage.
+pub fn parse_percentage(input: &str) -> std::result::Result<f64, String> {
+    let value: f64 = input
+        .trim()
+        .trim_end_matches('%')
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid percentage '{}'", input))?;
+    if !(0.0..=100.0).contains(&value) {
+        return Err(format!("percentage '{}' must be between 0 and 100", input));
+    }
+    Ok(value)
+}
+
+/// Parse a human size such as `1024`, `500KB`, `10 MB` or `1.5G` into bytes.
+pub fn parse_size(input: &str) -> std::result::Result<u64, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size '{}'", input))?;
+    let multiplier: u64 = match unit.trim().to_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1024,
+        "M" | "MB" | "MIB" => 1024 * 1024,
+        "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+        other => return Err(format!("unknown size unit '{}' (expected B, KB, MB or GB)", other)),
+    };
+
+    Ok((number * multiplier as f64) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024"), Ok(1024));
+        assert_eq!(parse_size("1KB"), Ok(1024));
+        assert_eq!(parse_size("10 mb"), Ok(10 * 1024 * 1024));
+        assert_eq!(parse_size("1.5G"), Ok(1536 * 1024 * 1024));
+        assert!(parse_size("ten MB").is_err());
+        assert!(parse_size("10 TB").is_err());
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[5, 5]), "▅▅");
+        assert_eq!(sparkline(&[0, 50, 100]), "▁▅█");
+    }
+
+    #[test]
+    fn test_parse_percentage() {
+        assert_eq!(parse_percentage("90%"), Ok(90.0));
+        assert_eq!(parse_percentage("75.5"), Ok(75.5));
+        assert!(parse_percentage("150%").is_err());
+        assert!(parse_percentage("high").is_err());
+    }
+
+    #[test]
+    fn test_items_to_csv_escapes_fields() {
+        let items = vec![StorageItem {
+            id: 7,
+            name: "Cache: cargo, linux".into(),
+            owner: "acme".into(),
+            repo: "api".into(),
+            size_in_bytes: 2048,
+            item_type: StorageItemType::Cache,
+            created_at: None,
+            expires_at: None,
+        }];
+        assert_eq!(
+            items_to_csv(&items),
+            "type,owner,repo,name,id,size_in_bytes\nCache,acme,api,\"cargo, linux\",7,2048\n"
+        );
+    }
+}
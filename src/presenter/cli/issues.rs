@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use chrono::Utc;
+use colored::*;
+use comfy_table::*;
+use crate::app::issue_use_case::IssueUseCase;
+use crate::domain::error::Result;
+use crate::domain::issue::{Issue, IssueDetails, IssueQuery, IssueState, NewIssue};
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::IssueCommands;
+use crate::presenter::markdown;
+use crate::presenter::output::{print_count, Output};
+use crate::presenter::template::{relative_time, truncate};
+
+/// Default fields of an issue in `ghr issue list|create` when `--columns` is
+/// not given, e.g. with `--format csv`
+pub const ISSUE_COLUMNS: &[&str] = &["number", "title", "state", "author", "labels", "updated_at"];
+
+pub async fn handle_issue(command: IssueCommands, output: &Output) -> Result<()> {
+    let auth = AuthManager::new()?;
+    let use_case = IssueUseCase::new(Arc::new(auth.get_client()?));
+
+    match command {
+        IssueCommands::List { repo, state, label, assignee, author, milestone, search, limit } => {
+            output.status(format!("📋 Fetching issues of {}...", repo).cyan());
+            let query = IssueQuery { state, labels: label, assignee, author, milestone, search, limit: Some(limit) };
+            let issues = use_case.list(&repo, &query).await?;
+
+            if !output.is_styled_table() {
+                return output.print(&issues, ISSUE_COLUMNS);
+            }
+            if issues.is_empty() {
+                println!("{}", "No issues match the filters".yellow());
+                return Ok(());
+            }
+            println!("\n{}", issue_table(&issues));
+            print_count(output, issues.len(), "issues");
+        }
+        IssueCommands::View { repo, number, comments, json } => {
+            let details = use_case.view(&repo, number, comments).await?;
+            if json || output.is_json() {
+                return output.print_json(&details);
+            }
+            display_issue(&details, comments);
+        }
+        IssueCommands::Create { repo, title, body, label, assignee } => {
+            let new_issue = NewIssue { title, body, labels: label, assignees: assignee };
+            output.status(format!("📋 Opening an issue on {}...", repo).cyan());
+            let issue = use_case.create(&repo, &new_issue).await?;
+            if !output.is_styled_table() {
+                return output.print(std::slice::from_ref(&issue), ISSUE_COLUMNS);
+            }
+            println!("{}", format!("✓ Created issue #{}: {}", issue.number, issue.title).green());
+            println!("{}", issue.html_url.cyan());
+        }
+        IssueCommands::Close { repo, number, reason, comment } => {
+            let (issue, status) = use_case.set_state(&repo, number, IssueState::Closed, reason, comment).await?;
+            print_state_change(&issue, &status, "closed");
+        }
+        IssueCommands::Reopen { repo, number, comment } => {
+            let (issue, status) = use_case.set_state(&repo, number, IssueState::Open, None, comment).await?;
+            print_state_change(&issue, &status, "reopened");
+        }
+        IssueCommands::Comment { repo, number, body } => {
+            let comment = use_case.comment(&repo, number, &body).await?;
+            println!("{}", format!("✓ Commented on issue #{}", number).green());
+            println!("{}", comment.html_url.cyan());
+        }
+    }
+    Ok(())
+}
+
+fn print_state_change(issue: &Issue, status: &RepoActionStatus, verb: &str) {
+    if *status == RepoActionStatus::Unchanged {
+        println!("{}", format!("Issue #{} is already {}", issue.number, issue.state).yellow());
+    } else {
+        println!("{}", format!("✓ {} issue #{}: {}", capitalize(verb), issue.number, issue.title).green());
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub fn state_label(issue: &Issue) -> ColoredString {
+    match issue.state {
+        IssueState::Open => "open".green(),
+        IssueState::Closed if issue.state_reason.as_deref() == Some("not_planned") => "not planned".dimmed(),
+        IssueState::Closed => "closed".magenta(),
+    }
+}
+
+/// The `ghr issue list` table.
+fn issue_table(issues: &[Issue]) -> Table {
+    let now = Utc::now();
+    let mut table = Table::new();
+    table
+        .set_header(vec!["#", "Title", "State", "Labels", "Author", "Assignees", "Updated"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for issue in issues {
+        table.add_row(vec![
+            issue.number.to_string(),
+            truncate(&issue.title, 60),
+            state_label(issue).to_string(),
+            issue.labels.join(", ").yellow().to_string(),
+            issue.author.clone(),
+            if issue.assignees.is_empty() { "-".into() } else { issue.assignees.join(", ") },
+            relative_time(issue.updated_at, now),
+        ]);
+    }
+    table
+}
+
+fn display_issue(details: &IssueDetails, with_comments: bool) {
+    let issue = &details.issue;
+    let now = Utc::now();
+
+    println!("{} {}", issue.title.cyan().bold(), format!("#{}", issue.number).dimmed());
+    println!(
+        "{} · {} opened {} · {} comments\n",
+        state_label(issue),
+        issue.author.bold(),
+        relative_time(issue.created_at, now),
+        issue.comments
+    );
+
+    let mut table = Table::new();
+    table.load_preset(presets::UTF8_FULL).apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    let list = |items: &[String]| if items.is_empty() { "-".to_string() } else { items.join(", ") };
+    table.add_row(vec!["Labels".to_string(), list(&issue.labels)]);
+    table.add_row(vec!["Assignees".to_string(), list(&issue.assignees)]);
+    table.add_row(vec!["Milestone".to_string(), issue.milestone.clone().unwrap_or_else(|| "-".into())]);
+    if let Some(closed_at) = issue.closed_at {
+        table.add_row(vec!["Closed".to_string(), relative_time(closed_at, now)]);
+    }
+    table.add_row(vec!["URL".to_string(), issue.html_url.clone()]);
+    println!("{}", table);
+
+    match issue.body {
+        Some(ref body) => println!("\n{}", markdown::to_plain_text(body)),
+        None => println!("\n{}", "No description provided".dimmed()),
+    }
+
+    if with_comments {
+        for comment in &details.comments {
+            println!(
+                "\n{} {}",
+                comment.author.bold(),
+                format!("commented {}", relative_time(comment.created_at, now)).dimmed()
+            );
+            println!("{}", markdown::to_plain_text(&comment.body));
+        }
+    } else if issue.comments > 0 {
+        println!("\n{}", format!("Use --comments to show the {} comments", issue.comments).cyan());
+    }
+}
@@ -0,0 +1,199 @@
+use std::io::IsTerminal;
+use std::sync::Arc;
+use colored::*;
+use comfy_table::*;
+use dialoguer::{Confirm, Input};
+use crate::app::repo_use_case::RepoUseCase;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::lifecycle::{RepoAction, RepoActionResult, RepoActionStatus};
+use crate::domain::repo::{OwnerRepoType, RepoQuery, Repository};
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::RepoSelection;
+use crate::presenter::output::Output;
+
+/// Default fields of `ghr repo archive|unarchive|delete` results when
+/// `--columns` is not given
+pub const REPO_ACTION_COLUMNS: &[&str] = &["repo", "action", "status"];
+
+/// `ghr repo archive|unarchive|delete` on one repository or a filtered set.
+pub async fn handle_action(action: RepoAction, selection: RepoSelection, dry_run: bool, yes: bool, output: &Output) -> Result<()> {
+    check_selection(&selection)?;
+
+    let auth = AuthManager::new()?;
+    let use_case = RepoUseCase::new(Arc::new(auth.get_client()?));
+
+    if action == RepoAction::Delete && !dry_run && !std::io::stdin().is_terminal() {
+        return Err(GhTuiError::InvalidInput(
+            "deleting repositories requires typing their names in an interactive terminal".into(),
+        ));
+    }
+
+    // A named repository is always a target; an org only yields its archived ones to unarchive
+    let bulk = selection.repo.is_none();
+    let mut repos = selected_repos(&use_case, selection, action.includes_archived(), output).await?;
+    if bulk && action == RepoAction::Unarchive {
+        repos.retain(|r| r.archived);
+    }
+    if repos.is_empty() {
+        if !output.is_styled_table() {
+            return output.print::<RepoActionResult>(&[], REPO_ACTION_COLUMNS);
+        }
+        println!("{}", "No repositories match the filters".yellow());
+        return Ok(());
+    }
+    if output.is_styled_table() {
+        display_targets(&repos, action);
+    }
+
+    if dry_run {
+        let results = use_case.apply_action(&repos, action, true).await;
+        return print_results(&results, true, output);
+    }
+
+    let (confirmed, mut skipped) = confirm(&repos, action, yes, output)?;
+    let mut results = use_case.apply_action(&confirmed, action, false).await;
+    results.append(&mut skipped);
+    results.sort_by(|a, b| a.repo.cmp(&b.repo));
+    print_results(&results, false, output)
+}
+
+fn print_results(results: &[RepoActionResult], dry_run: bool, output: &Output) -> Result<()> {
+    if !output.is_styled_table() {
+        return output.print(results, REPO_ACTION_COLUMNS);
+    }
+    display_results(results, dry_run);
+    Ok(())
+}
+
+/// Reject filters given without `--org`, before authenticating.
+pub fn check_selection(selection: &RepoSelection) -> Result<()> {
+    // clap drops `requires = "org"` once the conflicting positional is present
+    if selection.repo.is_some() && (selection.filter.is_some() || selection.filters.query() != RepoQuery::default()) {
+        return Err(GhTuiError::InvalidInput("repository filters require --org".into()));
+    }
+    Ok(())
+}
+
+/// The repository named on the command line, or the matching repositories of `--org`.
+pub async fn selected_repos(
+    use_case: &RepoUseCase,
+    selection: RepoSelection,
+    include_archived: bool,
+    output: &Output,
+) -> Result<Vec<Repository>> {
+    match (selection.repo, selection.org) {
+        (Some(target), _) => Ok(vec![use_case.get_repo(&target).await?]),
+        (None, Some(org)) => {
+            output.status(format!("📚 Fetching repositories of {}...", org).cyan());
+            let mut query = RepoQuery { text: selection.filter, ..selection.filters.query() };
+            query.include_archived |= include_archived;
+            use_case.list_repos(Some(org), OwnerRepoType::All, &query).await
+        }
+        (None, None) => Err(GhTuiError::InvalidInput("a repository or --org is required".into())),
+    }
+}
+
+/// Confirmed repositories, and the results of those the user declined.
+fn confirm(repos: &[Repository], action: RepoAction, yes: bool, output: &Output) -> Result<(Vec<Repository>, Vec<RepoActionResult>)> {
+    if action != RepoAction::Delete {
+        let confirmed = yes
+            || Confirm::new()
+                .with_prompt(format!("{} {} repositories?", capitalize(&action.to_string()), repos.len()))
+                .default(false)
+                .interact()?;
+        return Ok(if confirmed { (repos.to_vec(), Vec::new()) } else { (Vec::new(), skipped(repos, action)) });
+    }
+
+    output.status("⚠ Deleted repositories cannot be recovered.".yellow().bold());
+    let mut confirmed = Vec::new();
+    let mut declined = Vec::new();
+    for repo in repos {
+        let typed: String = Input::new()
+            .with_prompt(format!("Type {} to delete it (leave empty to skip)", repo.full_name.red()))
+            .allow_empty(true)
+            .interact_text()?;
+        if typed.trim() == repo.full_name {
+            confirmed.push(repo.clone());
+        } else {
+            declined.push(repo.clone());
+        }
+    }
+    Ok((confirmed, skipped(&declined, action)))
+}
+
+fn skipped(repos: &[Repository], action: RepoAction) -> Vec<RepoActionResult> {
+    repos
+        .iter()
+        .map(|r| RepoActionResult {
+            repo: r.full_name.clone(),
+            action,
+            status: RepoActionStatus::Skipped,
+        })
+        .collect()
+}
+
+fn display_targets(repos: &[Repository], action: RepoAction) {
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Repo", "Visibility", "Archived", "Last push"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    for r in repos {
+        table.add_row(vec![
+            r.full_name.clone(),
+            if r.private { "private".into() } else { "public".into() },
+            if r.archived { "yes".into() } else { "no".into() },
+            r.pushed_at.map(|p| p.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".into()),
+        ]);
+    }
+    println!("\n{}", table);
+    println!("{}", format!("{} repositories selected to {}", repos.len(), action).cyan());
+}
+
+fn display_results(results: &[RepoActionResult], dry_run: bool) {
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Repo", "Action", "Status"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    for r in results {
+        let status = match &r.status {
+            RepoActionStatus::Done => "✓ done".green(),
+            RepoActionStatus::Unchanged => "unchanged".normal(),
+            RepoActionStatus::Pending => format!("would {}", r.action).cyan(),
+            RepoActionStatus::Skipped => "skipped".yellow(),
+            RepoActionStatus::Failed(e) => format!("✗ {}", e).red(),
+        };
+        table.add_row(vec![r.repo.clone(), r.action.to_string(), status.to_string()]);
+    }
+    println!("\n{}", table);
+
+    let count = |f: fn(&RepoActionStatus) -> bool| results.iter().filter(|r| f(&r.status)).count();
+    let unchanged = count(|s| *s == RepoActionStatus::Unchanged);
+    let failed = count(|s| matches!(s, RepoActionStatus::Failed(_)));
+    if dry_run {
+        let pending = count(|s| *s == RepoActionStatus::Pending);
+        println!("{}", format!("Dry run: {} would change, {} unchanged", pending, unchanged).cyan());
+    } else {
+        let summary = format!(
+            "{} done, {} unchanged, {} skipped, {} failed",
+            count(|s| *s == RepoActionStatus::Done),
+            unchanged,
+            count(|s| *s == RepoActionStatus::Skipped),
+            failed
+        );
+        if failed > 0 {
+            println!("{}", summary.yellow());
+        } else {
+            println!("{}", summary.green());
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -2,8 +2,28 @@ pub mod auth;
 pub mod repos;
 pub mod artifacts;
 pub mod df;
+pub mod retention;
+pub mod lifecycle;
+pub mod audit;
+pub mod protect;
+pub mod branches;
+pub mod issues;
+pub mod search;
+pub mod prs;
+pub mod releases;
+pub mod settings;
 
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use crate::domain::git::GitProtocol;
+use crate::domain::issue::{CloseReason, IssueStateFilter};
+use crate::domain::repo::{OwnerRepoType, RepoQuery, RepoRef, RepoSort};
+use crate::domain::settings::{RepoSettings, Visibility};
+use crate::presenter::output::{Output, OutputFormat};
+use crate::presenter::jq::JqFilter;
+use crate::presenter::template::Template;
+use crate::domain::storage::StorageGrouping;
 
 #[derive(Parser)]
 #[command(name = "ghr")]
@@ -16,6 +36,31 @@ pub struct Cli {
     /// Enable verbose logging
     #[arg(short, long, global = true)]
     pub verbose: bool,
+
+    /// Output format of list commands
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+
+    /// Comma-separated fields to print (e.g. name,stargazers_count)
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    /// Format each item with a template, e.g. '{{full_name}}\t{{size_in_bytes | size}}'
+    /// (helpers: size, ago, truncate N, upper, lower, default TEXT)
+    #[arg(long, global = true, value_name = "TEMPLATE")]
+    pub template: Option<Template>,
+
+    /// Filter JSON output with a jq expression, e.g. '.[] | select(.private) | .full_name'
+    #[arg(long, global = true, value_name = "EXPR", conflicts_with = "template")]
+    pub jq: Option<JqFilter>,
+}
+
+impl Cli {
+    pub fn output(&self) -> Output {
+        Output::new(self.format, self.columns.clone())
+            .with_template(self.template.clone())
+            .with_jq(self.jq.clone())
+    }
 }
 
 #[derive(Subcommand)]
@@ -29,21 +74,14 @@ pub enum Commands {
     /// List repositories
     #[command(name = "ls")]
     Ls {
-        /// Filter by owner (username or org)
-        #[arg(short, long)]
-        owner: Option<String>,
-        
-        /// Show only private repos
-        #[arg(long)]
-        private: bool,
-        
-        /// Show only public repos
-        #[arg(long)]
-        public: bool,
+        #[command(flatten)]
+        args: LsArgs,
+    },
 
-        /// Show all repositories (including archived)
-        #[arg(short, long)]
-        all: bool,
+    /// Work with a repository
+    Repo {
+        #[command(subcommand)]
+        action: RepoCommands,
     },
 
     /// Manage artifacts
@@ -53,12 +91,406 @@ pub enum Commands {
     },
 
     /// Show Action storage usage
-    Df,
+    Df {
+        #[command(subcommand)]
+        action: Option<DfCommands>,
+
+        #[command(flatten)]
+        args: DfArgs,
+    },
     
+    /// Manage artifact and log retention
+    Retention {
+        #[command(subcommand)]
+        action: RetentionCommands,
+    },
+
+    /// List, view, create and update issues
+    Issue {
+        #[command(subcommand)]
+        action: IssueCommands,
+    },
+
+    /// Track the pull requests you authored, review or are assigned to
+    Pr {
+        #[command(subcommand)]
+        action: PrCommands,
+    },
+
+    /// List, create and clean up releases and their assets
+    Release {
+        #[command(subcommand)]
+        action: ReleaseCommands,
+    },
+
+    /// Search issues, pull requests, repositories and code across GitHub
+    Search {
+        #[command(subcommand)]
+        action: SearchCommands,
+    },
+
+    /// Find and clean up merged or stale branches
+    Branches {
+        #[command(subcommand)]
+        action: BranchCommands,
+    },
+
+    /// View and apply branch protection and rulesets
+    Protect {
+        #[command(subcommand)]
+        action: ProtectCommands,
+    },
+
+    /// Check repositories against compliance rules
+    Audit {
+        #[command(subcommand)]
+        action: AuditCommands,
+    },
+
     /// Launch TUI mode
     Tui,
 }
 
+#[derive(Args)]
+pub struct LsArgs {
+    /// Filter by owner (username or org)
+    #[arg(short, long)]
+    pub owner: Option<String>,
+
+    /// Which repositories of the owner to list: all, member or sources
+    #[arg(long = "type", value_name = "all|member|sources", default_value = "all", requires = "owner")]
+    pub repo_type: OwnerRepoType,
+
+    #[command(flatten)]
+    pub filters: RepoFilterArgs,
+
+    /// Sort by name, stars, updated, pushed or size
+    #[arg(long, value_name = "name|stars|updated|pushed|size")]
+    pub sort: Option<RepoSort>,
+
+    /// Reverse the sort order (largest or most recent first)
+    #[arg(long, requires = "sort")]
+    pub desc: bool,
+
+    /// Show at most N repositories
+    #[arg(short = 'L', long, value_name = "N")]
+    pub limit: Option<usize>,
+}
+
+impl LsArgs {
+    pub fn query(&self) -> RepoQuery {
+        RepoQuery {
+            sort: self.sort,
+            descending: self.desc,
+            limit: self.limit,
+            ..self.filters.query()
+        }
+    }
+}
+
+/// Repository filters shared by the commands that work on many repositories.
+#[derive(Args)]
+pub struct RepoFilterArgs {
+    /// Show only private repos
+    #[arg(long, conflicts_with = "public")]
+    pub private: bool,
+
+    /// Show only public repos
+    #[arg(long)]
+    pub public: bool,
+
+    /// Include archived repositories
+    #[arg(short, long)]
+    pub all: bool,
+
+    /// Only repositories whose primary language is LANG
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<String>,
+
+    /// Only repositories tagged with TOPIC
+    #[arg(long)]
+    pub topic: Option<String>,
+
+    /// Only forks
+    #[arg(long, conflicts_with = "source")]
+    pub fork: bool,
+
+    /// Only source repositories (no forks)
+    #[arg(long)]
+    pub source: bool,
+
+    /// Only repositories last pushed before this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = repos::parse_date)]
+    pub pushed_before: Option<DateTime<Utc>>,
+
+    /// Only repositories last pushed after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = repos::parse_date)]
+    pub pushed_after: Option<DateTime<Utc>>,
+
+    /// Only repositories with at least N stars
+    #[arg(long, value_name = "N")]
+    pub min_stars: Option<u32>,
+}
+
+impl RepoFilterArgs {
+    pub fn query(&self) -> RepoQuery {
+        RepoQuery {
+            include_archived: self.all,
+            private: repos::visibility(self.private, self.public),
+            fork: repos::visibility(self.fork, self.source),
+            language: self.language.clone(),
+            topic: self.topic.clone(),
+            pushed_before: self.pushed_before,
+            pushed_after: self.pushed_after,
+            min_stars: self.min_stars,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct DfArgs {
+    /// Aggregate items into totals: repo, name, type or owner
+    #[arg(long, value_name = "repo|name|type|owner")]
+    pub group_by: Option<StorageGrouping>,
+
+    /// Only show the N largest items (or groups with --group-by)
+    #[arg(long, value_name = "N")]
+    pub top: Option<usize>,
+
+    /// Ignore items smaller than this size (e.g. 500KB, 10MB, 1.5GB)
+    #[arg(long, value_name = "SIZE", default_value = "1KB", value_parser = df::parse_size)]
+    pub min_size: u64,
+
+    /// Never prompt; list items instead (automatic when not run in a terminal)
+    #[arg(long)]
+    pub no_interactive: bool,
+
+    /// Print the report as JSON
+    #[arg(long, conflicts_with = "csv")]
+    pub json: bool,
+
+    /// Print items (or groups with --group-by) as CSV
+    #[arg(long)]
+    pub csv: bool,
+
+    /// Exit with code 2 when an account's usage is above this percentage (e.g. 90%)
+    #[arg(long, value_name = "PERCENT", value_parser = df::parse_percentage)]
+    pub fail_above: Option<f64>,
+
+    /// Save a usage snapshot for `ghr df history` (always on with `storage.record_history`)
+    #[arg(long)]
+    pub record: bool,
+
+    /// Also scan release assets, which do not count towards the Actions quota
+    /// (always on with `storage.include_release_assets`)
+    #[arg(long)]
+    pub include_releases: bool,
+}
+
+#[derive(Subcommand)]
+pub enum DfCommands {
+    /// Show recorded storage usage over time
+    History {
+        /// Only show the usage of this repository (owner/repo)
+        #[arg(short, long)]
+        repo: Option<String>,
+        /// Number of most recent snapshots to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Print the series as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RepoCommands {
+    /// Show a repository's details, activity and README
+    View {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Print the details as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Clone a repository, or every matching repository of an org with --org
+    ///
+    /// Repositories already cloned are fetched instead.
+    Clone {
+        /// Repository as owner/repo
+        #[arg(required_unless_present = "org", conflicts_with = "org")]
+        repo: Option<RepoRef>,
+        /// Directory to clone into (defaults to the repository name)
+        #[arg(conflicts_with = "org")]
+        dir: Option<PathBuf>,
+        /// Clone every repository of this organization (or user)
+        #[arg(long, value_name = "ORG")]
+        org: Option<String>,
+        /// Only repositories whose name or description contains TEXT
+        #[arg(long, value_name = "TEXT", requires = "org")]
+        filter: Option<String>,
+        #[command(flatten)]
+        filters: RepoFilterArgs,
+        /// Parent directory of the clones
+        #[arg(long, value_name = "DIR", default_value = ".", requires = "org")]
+        into: PathBuf,
+        /// Number of repositories cloned at the same time
+        #[arg(short = 'j', long, value_name = "N", default_value_t = 4)]
+        parallel: usize,
+        /// Clone over ssh or https (defaults to `git.protocol` in the config)
+        #[arg(long, value_name = "ssh|https")]
+        protocol: Option<GitProtocol>,
+    },
+    /// Create a repository, empty or from a template repository
+    Create {
+        /// Name of the new repository
+        name: String,
+        /// Create it in this organization instead of your account
+        #[arg(long, value_name = "ORG")]
+        org: Option<String>,
+        /// Make the repository private (public by default)
+        #[arg(long)]
+        private: bool,
+        /// Generate it from this template repository (owner/repo)
+        #[arg(long, value_name = "OWNER/REPO")]
+        template: Option<RepoRef>,
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Add a .gitignore from GitHub's templates, e.g. Rust
+        #[arg(long, value_name = "TEMPLATE", conflicts_with = "template")]
+        gitignore: Option<String>,
+        /// Add a license, e.g. MIT or Apache-2.0
+        #[arg(long, value_name = "LICENSE", conflicts_with = "template")]
+        license: Option<String>,
+    },
+    /// Fork a repository into your account or an organization
+    Fork {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Fork into this organization instead of your account
+        #[arg(long, value_name = "ORG")]
+        org: Option<String>,
+    },
+    /// Change the settings of a repository, or of every matching repository of an org
+    Edit {
+        #[command(flatten)]
+        selection: RepoSelection,
+        #[command(flatten)]
+        settings: SettingsArgs,
+        /// Show the differences without applying them
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Archive a repository, or every matching repository of an org with --org
+    Archive {
+        #[command(flatten)]
+        selection: RepoSelection,
+        /// Show what would change without applying it
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Unarchive a repository, or every matching archived repository of an org with --org
+    Unarchive {
+        #[command(flatten)]
+        selection: RepoSelection,
+        /// Show what would change without applying it
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Permanently delete repositories; each name must be typed to confirm
+    Delete {
+        #[command(flatten)]
+        selection: RepoSelection,
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Desired settings for `ghr repo edit`; flags take precedence over the settings file.
+#[derive(Args)]
+pub struct SettingsArgs {
+    /// TOML file of settings to enforce, e.g. a standard configuration for every repo
+    #[arg(long, value_name = "FILE")]
+    pub settings: Option<PathBuf>,
+    #[arg(long, value_name = "TEXT")]
+    pub description: Option<String>,
+    #[arg(long, value_name = "URL")]
+    pub homepage: Option<String>,
+    /// Replace the topics (comma separated)
+    #[arg(long, value_name = "TOPICS", value_delimiter = ',')]
+    pub topics: Option<Vec<String>>,
+    #[arg(long, value_name = "public|private|internal")]
+    pub visibility: Option<Visibility>,
+    #[arg(long, value_name = "BRANCH")]
+    pub default_branch: Option<String>,
+    /// Allow merge commits
+    #[arg(long, value_name = "BOOL")]
+    pub merge_commit: Option<bool>,
+    /// Allow squash merging
+    #[arg(long, value_name = "BOOL")]
+    pub squash_merge: Option<bool>,
+    /// Allow rebase merging
+    #[arg(long, value_name = "BOOL")]
+    pub rebase_merge: Option<bool>,
+    /// Delete head branches once pull requests are merged
+    #[arg(long, value_name = "BOOL")]
+    pub delete_branch_on_merge: Option<bool>,
+    #[arg(long, value_name = "BOOL")]
+    pub issues: Option<bool>,
+    #[arg(long, value_name = "BOOL")]
+    pub wiki: Option<bool>,
+    #[arg(long, value_name = "BOOL")]
+    pub projects: Option<bool>,
+}
+
+impl SettingsArgs {
+    /// Settings given as flags, without the settings file.
+    pub fn flags(&self) -> RepoSettings {
+        RepoSettings {
+            description: self.description.clone(),
+            homepage: self.homepage.clone(),
+            topics: self.topics.clone(),
+            visibility: self.visibility,
+            default_branch: self.default_branch.clone(),
+            allow_merge_commit: self.merge_commit,
+            allow_squash_merge: self.squash_merge,
+            allow_rebase_merge: self.rebase_merge,
+            delete_branch_on_merge: self.delete_branch_on_merge,
+            has_issues: self.issues,
+            has_wiki: self.wiki,
+            has_projects: self.projects,
+            ..Default::default()
+        }
+    }
+}
+
+/// One repository, or the repositories of an org matching the filters.
+#[derive(Args)]
+pub struct RepoSelection {
+    /// Repository as owner/repo
+    #[arg(required_unless_present = "org", conflicts_with = "org")]
+    pub repo: Option<RepoRef>,
+    /// Every matching repository of this organization (or user)
+    #[arg(long, value_name = "ORG")]
+    pub org: Option<String>,
+    /// Only repositories whose name or description contains TEXT
+    #[arg(long, value_name = "TEXT", requires = "org")]
+    pub filter: Option<String>,
+    #[command(flatten)]
+    pub filters: RepoFilterArgs,
+}
+
 #[derive(Subcommand)]
 pub enum AuthCommands {
     /// Login with Personal Access Token
@@ -102,3 +534,466 @@ pub enum ArtifactCommands {
         owner: Option<String>,
     },
 }
+
+#[derive(Subcommand)]
+pub enum IssueCommands {
+    /// List the issues of a repository
+    List {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Only open, closed or all issues
+        #[arg(short, long, value_name = "open|closed|all", default_value = "open")]
+        state: IssueStateFilter,
+        /// Only issues with these labels (comma separated or repeated)
+        #[arg(short, long, value_name = "LABEL", value_delimiter = ',')]
+        label: Vec<String>,
+        /// Only issues assigned to this user
+        #[arg(short, long, value_name = "USER")]
+        assignee: Option<String>,
+        /// Only issues opened by this user
+        #[arg(short = 'A', long, value_name = "USER")]
+        author: Option<String>,
+        /// Only issues in this milestone (by title)
+        #[arg(short, long, value_name = "TITLE")]
+        milestone: Option<String>,
+        /// Search the issues with GitHub search syntax, e.g. 'crash in:title'
+        #[arg(short = 'S', long, value_name = "QUERY")]
+        search: Option<String>,
+        /// Show at most N issues
+        #[arg(short = 'L', long, value_name = "N", default_value_t = 30)]
+        limit: usize,
+    },
+    /// Show an issue and optionally its comments
+    View {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Issue number
+        number: u64,
+        /// Show the comments too
+        #[arg(short, long)]
+        comments: bool,
+        /// Print the issue as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Open an issue
+    Create {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        #[arg(short, long)]
+        title: String,
+        #[arg(short, long)]
+        body: Option<String>,
+        /// Labels to add (comma separated or repeated)
+        #[arg(short, long, value_name = "LABEL", value_delimiter = ',')]
+        label: Vec<String>,
+        /// Users to assign (comma separated or repeated)
+        #[arg(short, long, value_name = "USER", value_delimiter = ',')]
+        assignee: Vec<String>,
+    },
+    /// Close an issue
+    Close {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Issue number
+        number: u64,
+        /// Why the issue is closed
+        #[arg(short, long, value_name = "completed|not-planned")]
+        reason: Option<CloseReason>,
+        /// Leave a comment before closing
+        #[arg(short, long, value_name = "TEXT")]
+        comment: Option<String>,
+    },
+    /// Reopen a closed issue
+    Reopen {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Issue number
+        number: u64,
+        /// Leave a comment before reopening
+        #[arg(short, long, value_name = "TEXT")]
+        comment: Option<String>,
+    },
+    /// Comment on an issue
+    Comment {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Issue number
+        number: u64,
+        #[arg(short, long)]
+        body: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PrCommands {
+    /// Your open pull requests, those awaiting your review and those assigned
+    /// to you, with checks, review decision and mergeability
+    Status {
+        /// Only pull requests of this repository (owner/repo)
+        #[arg(short = 'R', long, conflicts_with = "org")]
+        repo: Option<RepoRef>,
+        /// Only pull requests of this organization
+        #[arg(long)]
+        org: Option<String>,
+        /// Show at most N pull requests per section
+        #[arg(short = 'L', long, value_name = "N", default_value_t = 20,
+              value_parser = clap::value_parser!(u16).range(1..=100))]
+        limit: u16,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check out a pull request in the current clone, on a local branch named
+    /// after its head branch
+    Checkout {
+        /// Pull request number
+        number: u64,
+        /// Remote of the repository the pull request was opened on
+        #[arg(long, default_value = "origin")]
+        remote: String,
+        /// Local branch to use instead of the head branch name
+        #[arg(short, long)]
+        branch: Option<String>,
+    },
+    /// Approve, request changes on or comment on a pull request
+    #[command(group(ArgGroup::new("event").required(true).args(["approve", "request_changes", "comment"])))]
+    Review {
+        /// Pull request number
+        number: u64,
+        #[command(flatten)]
+        repo: PrRepoArg,
+        #[arg(long)]
+        approve: bool,
+        #[arg(long)]
+        request_changes: bool,
+        #[arg(long)]
+        comment: bool,
+        /// Review text, required to request changes or comment
+        #[arg(short, long)]
+        body: Option<String>,
+    },
+    /// Merge a pull request, or enable auto-merge with --auto
+    #[command(group(ArgGroup::new("method").required(true).args(["merge", "squash", "rebase"])))]
+    Merge {
+        /// Pull request number
+        number: u64,
+        #[command(flatten)]
+        repo: PrRepoArg,
+        /// Create a merge commit
+        #[arg(long)]
+        merge: bool,
+        /// Squash the commits into one
+        #[arg(long)]
+        squash: bool,
+        /// Rebase the commits onto the base branch
+        #[arg(long)]
+        rebase: bool,
+        /// Delete the head branch after merging
+        #[arg(short, long, conflicts_with = "auto")]
+        delete_branch: bool,
+        /// Merge once required checks and reviews pass if it cannot be merged yet
+        #[arg(long)]
+        auto: bool,
+    },
+    /// Print the diff of a pull request
+    Diff {
+        /// Pull request number
+        number: u64,
+        #[command(flatten)]
+        repo: PrRepoArg,
+    },
+}
+
+#[derive(Args)]
+pub struct PrRepoArg {
+    /// Repository as owner/repo (default: the GitHub repository of the
+    /// `origin` remote of the current directory)
+    #[arg(short = 'R', long = "repo")]
+    pub repo: Option<RepoRef>,
+}
+
+#[derive(Subcommand)]
+pub enum ReleaseCommands {
+    /// List the releases of a repository, newest first
+    List {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Show at most N releases
+        #[arg(short = 'L', long, value_name = "N", default_value_t = 30)]
+        limit: usize,
+    },
+    /// Show a release and its assets
+    View {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Tag of the release
+        tag: String,
+        /// Print the release as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Create a release, and its tag when it does not exist yet
+    Create {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Tag of the release
+        tag: String,
+        /// Release title (defaults to the tag)
+        #[arg(short, long)]
+        title: Option<String>,
+        /// Release notes
+        #[arg(short, long, conflicts_with = "notes_file")]
+        notes: Option<String>,
+        /// Read the release notes from a file
+        #[arg(short = 'F', long, value_name = "FILE")]
+        notes_file: Option<PathBuf>,
+        /// Generate the notes from the pull requests merged since the previous
+        /// release (after --notes when both are given)
+        #[arg(short, long)]
+        generate_notes: bool,
+        /// Branch or commit to create the tag from (default: the default branch)
+        #[arg(long, value_name = "BRANCH|SHA")]
+        target: Option<String>,
+        /// Save the release as a draft
+        #[arg(short, long)]
+        draft: bool,
+        /// Mark the release as a pre-release
+        #[arg(short, long)]
+        prerelease: bool,
+    },
+    /// Upload files as assets of a release
+    Upload {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Tag of the release
+        tag: String,
+        /// Files to upload
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Replace assets with the same name
+        #[arg(long)]
+        clobber: bool,
+    },
+    /// Download the assets of a release
+    Download {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Tag of the release
+        tag: String,
+        /// Only assets matching this glob, e.g. '*.tar.gz' (repeatable)
+        #[arg(short, long, value_name = "GLOB")]
+        pattern: Vec<String>,
+        /// Directory to save the assets into
+        #[arg(short = 'D', long, default_value = ".")]
+        dir: PathBuf,
+        /// Overwrite existing files
+        #[arg(long)]
+        clobber: bool,
+    },
+    /// Delete a release, or old pre-releases in bulk (tags are kept)
+    #[command(group(ArgGroup::new("releases").required(true).args(["tag", "prereleases"])))]
+    Delete {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Tag of the release to delete
+        tag: Option<String>,
+        /// Delete published pre-releases instead
+        #[arg(long)]
+        prereleases: bool,
+        /// Spare the N most recent pre-releases
+        #[arg(long, value_name = "N", default_value_t = 0, conflicts_with = "tag")]
+        keep: usize,
+        /// Only pre-releases published more than DAYS days ago
+        #[arg(long, value_name = "DAYS", conflicts_with = "tag")]
+        older_than: Option<i64>,
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SearchCommands {
+    /// Search issues, e.g. 'label:bug state:open org:acme'
+    Issues {
+        #[command(flatten)]
+        args: SearchArgs,
+    },
+    /// Search pull requests, e.g. 'author:@me state:open'
+    Prs {
+        #[command(flatten)]
+        args: SearchArgs,
+    },
+    /// Search repositories, e.g. 'language:rust stars:>100'
+    Repos {
+        #[command(flatten)]
+        args: SearchArgs,
+    },
+    /// Search code, e.g. 'unwrap() org:acme language:rust'
+    Code {
+        #[command(flatten)]
+        args: SearchArgs,
+    },
+}
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Search terms and qualifiers, passed through to GitHub search (put
+    /// exclusions such as -label:wontfix after `--`)
+    #[arg(required = true, value_name = "QUERY")]
+    pub query: Vec<String>,
+    /// Fetch at most N results (GitHub returns 1000 at most)
+    #[arg(short = 'L', long, value_name = "N", default_value_t = 30,
+          value_parser = clap::value_parser!(u16).range(1..=1000))]
+    pub limit: u16,
+}
+
+#[derive(Subcommand)]
+pub enum BranchCommands {
+    /// List branches with their last commit, ahead/behind counts and pull request
+    List {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Only branches merged into the default branch
+        #[arg(long)]
+        merged: bool,
+        /// Only branches without commits for DAYS days
+        #[arg(long, value_name = "DAYS")]
+        stale_days: Option<i64>,
+    },
+    /// Delete merged or stale branches (never the default, protected ones or those with an open pull request)
+    #[command(group(ArgGroup::new("criteria").required(true).multiple(true).args(["merged", "stale_days"])))]
+    Prune {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Delete branches merged into the default branch
+        #[arg(long)]
+        merged: bool,
+        /// Delete branches without commits for DAYS days
+        #[arg(long, value_name = "DAYS")]
+        stale_days: Option<i64>,
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProtectCommands {
+    /// Show the branch protection and rulesets of a branch
+    Show {
+        /// Repository as owner/repo
+        repo: RepoRef,
+        /// Branch to inspect (defaults to the default branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Apply a protection file to a repository, or to every matching repository of an org
+    Apply {
+        #[command(flatten)]
+        selection: RepoSelection,
+        /// TOML file describing the protection
+        #[arg(long, value_name = "FILE")]
+        from: PathBuf,
+        /// Branch to protect (defaults to each repository's default branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+        /// Create or update a repository ruleset with this name instead of branch protection
+        #[arg(long, value_name = "NAME")]
+        ruleset: Option<String>,
+        /// Show what would change without applying it
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// Pass/fail matrix of every repository of an org; exits with code 2 on violations
+    Repos {
+        /// Organization (or user) to audit
+        #[arg(long, value_name = "ORG")]
+        org: String,
+        /// TOML file selecting the rules (all rules are checked by default)
+        #[arg(long, value_name = "FILE")]
+        rules: Option<PathBuf>,
+        /// Only repositories whose name or description contains TEXT
+        #[arg(long, value_name = "TEXT")]
+        filter: Option<String>,
+        #[command(flatten)]
+        filters: RepoFilterArgs,
+        /// Output the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RetentionCommands {
+    /// Show the artifact and log retention period
+    Get {
+        #[command(flatten)]
+        target: RetentionTarget,
+        #[command(flatten)]
+        filter: BulkRepoFilter,
+    },
+    /// Set the artifact and log retention period
+    Set {
+        /// Retention in days
+        #[arg(long)]
+        days: u32,
+        #[command(flatten)]
+        target: RetentionTarget,
+        #[command(flatten)]
+        filter: BulkRepoFilter,
+        /// Show what would change without applying it
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt in bulk mode
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+pub struct RetentionTarget {
+    /// Repository (owner/repo)
+    #[arg(long)]
+    pub repo: Option<RepoRef>,
+    /// Organization default
+    #[arg(long)]
+    pub org: Option<String>,
+    /// Every repository matching the filters (bulk mode)
+    #[arg(long)]
+    pub all_repos: bool,
+}
+
+#[derive(Args)]
+pub struct BulkRepoFilter {
+    /// Bulk mode: repositories of this user or org instead of your own
+    #[arg(long, requires = "all_repos")]
+    pub owner: Option<String>,
+    /// Bulk mode: only repositories whose name contains this text
+    #[arg(long, requires = "all_repos")]
+    pub filter: Option<String>,
+    /// Bulk mode: only private repositories
+    #[arg(long, requires = "all_repos", conflicts_with = "public")]
+    pub private: bool,
+    /// Bulk mode: only public repositories
+    #[arg(long, requires = "all_repos")]
+    pub public: bool,
+}
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+use colored::*;
+use comfy_table::*;
+use dialoguer::Confirm;
+use crate::app::protection_use_case::ProtectionUseCase;
+use crate::app::repo_use_case::RepoUseCase;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::protection::{BranchProtection, ProtectionReport};
+use crate::infra::config;
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::lifecycle::{check_selection, selected_repos};
+use crate::presenter::cli::settings::{print_diff, print_results};
+use crate::presenter::cli::ProtectCommands;
+use crate::presenter::output::Output;
+
+/// Default fields of `ghr protect apply` plans when `--columns` is not given;
+/// the JSON formats also keep each change
+pub const PROTECTION_PLAN_COLUMNS: &[&str] = &["owner", "name", "branch", "status"];
+
+pub async fn handle_protect(command: ProtectCommands, output: &Output) -> Result<()> {
+    match command {
+        ProtectCommands::Show { repo, branch, json } => {
+            let auth = AuthManager::new()?;
+            let use_case = ProtectionUseCase::new(Arc::new(auth.get_client()?));
+            let report = use_case.show(&repo, branch).await?;
+            if let Some(ref e) = report.rulesets_error {
+                eprintln!("{}", format!("⚠ Could not read the rulesets of {}: {}", report.repo, e).yellow());
+            }
+            if json || output.is_json() {
+                output.print_json(&report)
+            } else {
+                display_report(&report);
+                Ok(())
+            }
+        }
+        ProtectCommands::Apply { selection, from, branch, ruleset, dry_run, yes } => {
+            check_selection(&selection)?;
+            let desired = config::load_branch_protection(&from)?;
+            desired.validate().map_err(GhTuiError::InvalidInput)?;
+
+            let client = Arc::new(AuthManager::new()?.get_client()?);
+            let repos = selected_repos(&RepoUseCase::new(client.clone()), selection, false, output).await?;
+            if repos.is_empty() && output.is_styled_table() {
+                println!("{}", "No repositories match the filters".yellow());
+                return Ok(());
+            }
+
+            let use_case = ProtectionUseCase::new(client);
+            output.status(format!("🔍 Reading the protection of {} repositories...", repos.len()).cyan());
+            let mut plans = use_case.plan(&repos, branch.as_deref(), ruleset.as_deref(), &desired).await?;
+            let pending = plans.iter().filter(|p| p.status == RepoActionStatus::Pending).count();
+            if !output.is_styled_table() && (pending == 0 || dry_run) {
+                return output.print(&plans, PROTECTION_PLAN_COLUMNS);
+            }
+            if output.is_styled_table() {
+                print_diff(
+                    &plans
+                        .iter()
+                        .map(|p| (format!("{}/{} ({})", p.owner, p.name, p.branch), &p.status, &p.changes[..]))
+                        .collect::<Vec<_>>(),
+                );
+            }
+
+            if pending == 0 {
+                println!("{}", "✓ Every repository is already protected this way".green());
+                return Ok(());
+            }
+            if dry_run {
+                println!("{}", format!("Dry run: {} repositories would change", pending).cyan());
+                return Ok(());
+            }
+
+            let confirmed = yes
+                || Confirm::new()
+                    .with_prompt(format!("Apply this protection to {} repositories?", pending))
+                    .default(false)
+                    .interact()?;
+            if !confirmed {
+                for plan in plans.iter_mut().filter(|p| p.status == RepoActionStatus::Pending) {
+                    plan.status = RepoActionStatus::Skipped;
+                }
+            }
+
+            use_case.apply(&mut plans, &desired).await;
+            if !output.is_styled_table() {
+                return output.print(&plans, PROTECTION_PLAN_COLUMNS);
+            }
+            print_results(
+                &plans
+                    .iter()
+                    .map(|p| (format!("{}/{}", p.owner, p.name), p.changes.len(), &p.status))
+                    .collect::<Vec<_>>(),
+            );
+            Ok(())
+        }
+    }
+}
+
+fn display_report(report: &ProtectionReport) {
+    println!("{}", format!("🛡 {} ({})", report.repo, report.branch).bold());
+    match report.protection {
+        Some(ref protection) => {
+            println!("\n{}", "Branch protection".cyan().bold());
+            println!("{}", protection_table(protection));
+        }
+        None => println!("\n{}", "No branch protection".yellow()),
+    }
+
+    if report.rulesets_error.is_some() {
+        println!("{}", "Branch rulesets unknown".yellow());
+        return;
+    }
+    if report.rulesets.is_empty() {
+        println!("{}", "No branch rulesets".yellow());
+        return;
+    }
+    for ruleset in &report.rulesets {
+        let enforcement = match ruleset.enforcement.as_str() {
+            "active" => ruleset.enforcement.green(),
+            "evaluate" => ruleset.enforcement.yellow(),
+            _ => ruleset.enforcement.red(),
+        };
+        println!(
+            "\n{} {} ({}) on {}",
+            "Ruleset".cyan().bold(),
+            ruleset.name.bold(),
+            enforcement,
+            ruleset.branches.join(", ")
+        );
+        println!("{}", protection_table(&ruleset.protection));
+    }
+}
+
+fn protection_table(protection: &BranchProtection) -> Table {
+    let on_off = |enabled: bool| if enabled { "✓".green().to_string() } else { "✗".red().to_string() };
+
+    let mut table = Table::new();
+    table
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    table.add_row(vec![
+        "Required approvals".to_string(),
+        protection
+            .required_approving_reviews
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "✗".red().to_string()),
+    ]);
+    table.add_row(vec!["Dismiss stale reviews".to_string(), on_off(protection.dismiss_stale_reviews)]);
+    table.add_row(vec!["Code owner reviews".to_string(), on_off(protection.require_code_owner_reviews)]);
+    table.add_row(vec![
+        "Status checks".to_string(),
+        match protection.required_status_checks {
+            Some(ref checks) if protection.strict_status_checks => format!("{} (up to date)", checks.join(", ")),
+            Some(ref checks) => checks.join(", "),
+            None => "✗".red().to_string(),
+        },
+    ]);
+    table.add_row(vec!["Enforced for admins".to_string(), on_off(protection.enforce_admins)]);
+    table.add_row(vec!["Linear history".to_string(), on_off(protection.required_linear_history)]);
+    table.add_row(vec!["Conversation resolution".to_string(), on_off(protection.required_conversation_resolution)]);
+    // Allowing these is the weaker setting, so it is shown in red
+    let allowed = |allowed: bool| if allowed { "allowed".red().to_string() } else { "blocked".green().to_string() };
+    table.add_row(vec!["Force pushes".to_string(), allowed(protection.allow_force_pushes)]);
+    table.add_row(vec!["Deletions".to_string(), allowed(protection.allow_deletions)]);
+    table
+}
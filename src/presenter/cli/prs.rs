@@ -0,0 +1,183 @@
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::Arc;
+use chrono::Utc;
+use colored::*;
+use comfy_table::*;
+use crate::app::pull_request_use_case::PullRequestUseCase;
+use crate::domain::error::Result;
+use crate::domain::pull_request::{
+    CheckStatus, MergeMethod, MergeOutcome, MergeableState, NewReview, PrStatusQuery, PullRequest, ReviewDecision, ReviewEvent,
+};
+use crate::domain::repo::RepoRef;
+use crate::infra::git::GitCli;
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::{PrCommands, PrRepoArg};
+use crate::presenter::output::Output;
+use crate::presenter::template::{relative_time, truncate};
+
+pub async fn handle_pr(command: PrCommands, output: &Output) -> Result<()> {
+    let auth = AuthManager::new()?;
+    let use_case = PullRequestUseCase::new(Arc::new(auth.get_client()?), Arc::new(GitCli::new()));
+
+    match command {
+        PrCommands::Status { repo, org, limit, json } => {
+            let query = PrStatusQuery { repo, org, limit: limit as usize };
+            output.status("🔀 Fetching your pull requests...".cyan());
+            let status = use_case.status(&query).await?;
+            if json || output.is_json() {
+                return output.print_json(&status);
+            }
+            if status.is_empty() {
+                println!("{}", "No open pull requests involve you".yellow());
+                return Ok(());
+            }
+
+            print_section("Created by you", &status.authored);
+            print_section("Requesting your review", &status.review_requested);
+            print_section("Assigned to you", &status.assigned);
+        }
+        PrCommands::Checkout { number, remote, branch } => {
+            output.status(format!("🔀 Checking out #{}...", number).cyan());
+            let (pr, branch) = use_case.checkout(Path::new("."), &remote, number, branch).await?;
+            println!("{}", format!("✓ Switched to branch '{}' for #{}: {}", branch, pr.number, pr.title).green());
+        }
+        PrCommands::Review { number, repo, approve, request_changes, body, .. } => {
+            let target = target(&use_case, repo)?;
+            let event = if approve {
+                ReviewEvent::Approve
+            } else if request_changes {
+                ReviewEvent::RequestChanges
+            } else {
+                ReviewEvent::Comment
+            };
+            let url = use_case.review(&target, number, &NewReview { event, body }).await?;
+            let verb = match event {
+                ReviewEvent::Approve => "Approved",
+                ReviewEvent::RequestChanges => "Requested changes on",
+                ReviewEvent::Comment => "Reviewed",
+            };
+            println!("{}", format!("✓ {} #{} of {}", verb, number, target).green());
+            println!("{}", url.cyan());
+        }
+        PrCommands::Merge { number, repo, squash, rebase, delete_branch, auto, .. } => {
+            let target = target(&use_case, repo)?;
+            let method = if squash {
+                MergeMethod::Squash
+            } else if rebase {
+                MergeMethod::Rebase
+            } else {
+                MergeMethod::Merge
+            };
+            output.status(format!("🔀 Merging #{} of {}...", number, target).cyan());
+            let (pr, outcome) = use_case.merge(&target, number, method, delete_branch, auto).await?;
+            match outcome {
+                MergeOutcome::Merged { branch_deleted, branch_error } => {
+                    println!("{}", format!("✓ Merged #{}: {}", pr.number, pr.title).green());
+                    if branch_deleted {
+                        println!("{}", format!("✓ Deleted branch {}", pr.head_ref).green());
+                    } else if let Some(e) = branch_error {
+                        println!("{}", format!("⚠ Could not delete branch {}: {}", pr.head_ref, e).yellow());
+                    } else if delete_branch {
+                        let fork = pr.head_repository.as_deref().unwrap_or("a deleted fork");
+                        println!("{}", format!("⚠ Kept branch {}, it belongs to {}", pr.head_ref, fork).yellow());
+                    }
+                }
+                MergeOutcome::AutoMergeEnabled => {
+                    println!("{}", format!("✓ #{} will be merged once it can be: {}", pr.number, pr.merge_blockers().join(", ")).green());
+                }
+            }
+        }
+        PrCommands::Diff { number, repo } => {
+            let target = target(&use_case, repo)?;
+            let diff = use_case.diff(&target, number).await?;
+            // Keep the diff plain when piped, e.g. into `git apply`
+            if std::io::stdout().is_terminal() {
+                diff.lines().for_each(|line| println!("{}", diff_line(line)));
+            } else {
+                print!("{}", diff);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `--repo`, or the repository of the `origin` remote of the current directory.
+fn target(use_case: &PullRequestUseCase, arg: PrRepoArg) -> Result<RepoRef> {
+    match arg.repo {
+        Some(repo) => Ok(repo),
+        None => use_case.repo_from_remote(Path::new("."), "origin"),
+    }
+}
+
+fn diff_line(line: &str) -> ColoredString {
+    if line.starts_with("diff --git") || line.starts_with("+++") || line.starts_with("---") || line.starts_with("index ") {
+        line.bold()
+    } else if line.starts_with("@@") {
+        line.cyan()
+    } else if line.starts_with('+') {
+        line.green()
+    } else if line.starts_with('-') {
+        line.red()
+    } else {
+        line.normal()
+    }
+}
+
+fn print_section(title: &str, prs: &[PullRequest]) {
+    println!("\n{}", title.cyan().bold());
+    if prs.is_empty() {
+        println!("  {}", "No open pull requests".dimmed());
+    } else {
+        println!("{}", pr_table(prs));
+    }
+}
+
+pub fn checks_label(checks: CheckStatus) -> ColoredString {
+    match checks {
+        CheckStatus::Success => "✓ passing".green(),
+        CheckStatus::Failure => "✗ failing".red(),
+        CheckStatus::Pending => "● pending".yellow(),
+        CheckStatus::None => "-".dimmed(),
+    }
+}
+
+pub fn review_label(decision: Option<ReviewDecision>) -> ColoredString {
+    match decision {
+        Some(ReviewDecision::Approved) => "approved".green(),
+        Some(ReviewDecision::ChangesRequested) => "changes requested".red(),
+        Some(ReviewDecision::ReviewRequired) => "review required".yellow(),
+        None => "-".dimmed(),
+    }
+}
+
+pub fn mergeable_label(mergeable: MergeableState) -> ColoredString {
+    match mergeable {
+        MergeableState::Mergeable => "✓".green(),
+        MergeableState::Conflicting => "conflicts".red(),
+        MergeableState::Unknown => "?".dimmed(),
+    }
+}
+
+fn pr_table(prs: &[PullRequest]) -> Table {
+    let now = Utc::now();
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Repository", "#", "Title", "Checks", "Review", "Mergeable", "Age"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for pr in prs {
+        let title = truncate(&pr.title, 50);
+        table.add_row(vec![
+            pr.repository.clone(),
+            pr.number.to_string(),
+            if pr.draft { format!("{} {}", title, "(draft)".dimmed()) } else { title },
+            checks_label(pr.checks).to_string(),
+            review_label(pr.review_decision).to_string(),
+            mergeable_label(pr.mergeable).to_string(),
+            relative_time(pr.created_at, now),
+        ]);
+    }
+    table
+}
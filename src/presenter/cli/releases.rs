@@ -0,0 +1,269 @@
+use std::io::Write;
+use std::sync::Arc;
+use chrono::Utc;
+use colored::*;
+use comfy_table::*;
+use dialoguer::Confirm;
+use crate::app::release_use_case::ReleaseUseCase;
+use crate::domain::error::Result;
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::release::{NewRelease, PrereleaseCleanup, Release, TransferProgress};
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::ReleaseCommands;
+use crate::presenter::markdown;
+use crate::presenter::output::{print_count, Output};
+use crate::presenter::template::{human_size, relative_time, truncate};
+
+/// Default fields of a release in `ghr release list|create` when `--columns` is
+/// not given, e.g. with `--format tsv`
+pub const RELEASE_COLUMNS: &[&str] = &["tag_name", "name", "draft", "prerelease", "published_at", "html_url"];
+
+pub async fn handle_release(command: ReleaseCommands, output: &Output) -> Result<()> {
+    let auth = AuthManager::new()?;
+    let use_case = ReleaseUseCase::new(Arc::new(auth.get_client()?));
+
+    match command {
+        ReleaseCommands::List { repo, limit } => {
+            output.status(format!("🏷️  Fetching releases of {}...", repo).cyan());
+            let releases = use_case.list(&repo, limit).await?;
+            if !output.is_styled_table() {
+                return output.print(&releases, RELEASE_COLUMNS);
+            }
+            if releases.is_empty() {
+                println!("{}", format!("{} has no releases", repo).yellow());
+                return Ok(());
+            }
+            println!("\n{}", release_table(&releases));
+            print_count(output, releases.len(), "releases");
+        }
+        ReleaseCommands::View { repo, tag, json } => {
+            let release = use_case.view(&repo, &tag).await?;
+            if json || output.is_json() {
+                return output.print_json(&release);
+            }
+            display_release(&release);
+        }
+        ReleaseCommands::Create { repo, tag, title, notes, notes_file, generate_notes, target, draft, prerelease } => {
+            let notes = match notes_file {
+                Some(path) => Some(std::fs::read_to_string(path)?),
+                None => notes,
+            };
+            let new_release = NewRelease { tag, target, name: title, notes, generate_notes, draft, prerelease };
+            output.status(format!("🏷️  Creating release {} of {}...", new_release.tag, repo).cyan());
+            let release = use_case.create(&repo, &new_release).await?;
+            if !output.is_styled_table() {
+                return output.print(std::slice::from_ref(&release), RELEASE_COLUMNS);
+            }
+            println!("{}", format!("✓ Created {} {}", kind(&release), release.title()).green());
+            println!("{}", release.html_url.cyan());
+        }
+        ReleaseCommands::Upload { repo, tag, files, clobber } => {
+            let (release, uploads) = use_case.upload_plan(&repo, &tag, &files, clobber).await?;
+            let total: u64 = uploads.iter().map(|u| u.size_in_bytes).sum();
+            println!("{}", format!("⬆ Uploading {} files ({}) to {}...", uploads.len(), human_size(total as f64), tag).cyan());
+
+            let mut done = 0;
+            for (i, upload) in uploads.iter().enumerate() {
+                let progress = progress(i, uploads.len(), done, total, &upload.name, upload.size_in_bytes);
+                progress(0);
+                match use_case.upload(&repo, &release, upload, progress).await {
+                    Ok(_) if upload.replaces.is_some() => println!("{}", "✓ replaced".green()),
+                    Ok(_) => println!("{}", "✓".green()),
+                    Err(e) => {
+                        println!("{}", "✗".red());
+                        return Err(e);
+                    }
+                }
+                done += upload.size_in_bytes;
+            }
+            println!("{}", format!("✓ Uploaded {} files to {}", uploads.len(), tag).green());
+        }
+        ReleaseCommands::Download { repo, tag, pattern, dir, clobber } => {
+            let (_, assets) = use_case.download_plan(&repo, &tag, &pattern).await?;
+            if assets.is_empty() {
+                println!("{}", format!("{} has no assets", tag).yellow());
+                return Ok(());
+            }
+            let total: u64 = assets.iter().map(|a| a.size_in_bytes).sum();
+            println!(
+                "{}",
+                format!("⬇ Downloading {} assets ({}) into {}...", assets.len(), human_size(total as f64), dir.display()).cyan()
+            );
+
+            let mut done = 0;
+            for (i, asset) in assets.iter().enumerate() {
+                let progress = progress(i, assets.len(), done, total, &asset.name, asset.size_in_bytes);
+                progress(0);
+                match use_case.download(&repo, asset, &dir, clobber, progress).await {
+                    Ok(_) => println!("{}", "✓".green()),
+                    Err(e) => {
+                        println!("{}", "✗".red());
+                        return Err(e);
+                    }
+                }
+                done += asset.size_in_bytes;
+            }
+            println!("{}", format!("✓ Downloaded {} assets of {}", assets.len(), tag).green());
+        }
+        ReleaseCommands::Delete { repo, tag: Some(tag), dry_run, yes, .. } => {
+            let release = use_case.view(&repo, &tag).await?;
+            if dry_run {
+                println!("{}", format!("Dry run: {} {} would be deleted", kind(&release), release.title()).cyan());
+                return Ok(());
+            }
+            let confirmed = yes
+                || Confirm::new()
+                    .with_prompt(format!("Delete {} {} of {}?", kind(&release), release.title(), repo))
+                    .default(false)
+                    .interact()?;
+            if !confirmed {
+                println!("{}", "Cancelled".yellow());
+                return Ok(());
+            }
+            use_case.delete(&repo, &tag).await?;
+            println!("{}", format!("✓ Deleted {} {} (the tag is kept)", kind(&release), release.title()).green());
+        }
+        ReleaseCommands::Delete { repo, keep, older_than, dry_run, yes, .. } => {
+            output.status(format!("🏷️  Fetching releases of {}...", repo).cyan());
+            let cleanup = PrereleaseCleanup { keep, older_than_days: older_than };
+            let candidates = use_case.prerelease_cleanup(&repo, &cleanup).await?;
+            if candidates.is_empty() {
+                println!("{}", "✓ No pre-releases to delete".green());
+                return Ok(());
+            }
+            println!("\n{}", release_table(&candidates));
+
+            let assets: u64 = candidates.iter().map(|r| r.assets_size()).sum();
+            if dry_run {
+                println!(
+                    "{}",
+                    format!("Dry run: {} pre-releases ({} of assets) would be deleted", candidates.len(), human_size(assets as f64)).cyan()
+                );
+                return Ok(());
+            }
+            let confirmed = yes
+                || Confirm::new()
+                    .with_prompt(format!("Delete {} pre-releases of {}?", candidates.len(), repo))
+                    .default(false)
+                    .interact()?;
+            if !confirmed {
+                println!("{}", "Cancelled".yellow());
+                return Ok(());
+            }
+
+            let results = use_case.delete_all(&repo, &candidates).await;
+            let mut table = Table::new();
+            table
+                .set_header(vec!["Tag", "Status"])
+                .load_preset(presets::UTF8_FULL)
+                .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+            for (tag, status) in &results {
+                let status = match status {
+                    RepoActionStatus::Failed(e) => format!("✗ {}", e).red(),
+                    _ => "✓ deleted".green(),
+                };
+                table.add_row(vec![tag.clone(), status.to_string()]);
+            }
+            println!("\n{}", table);
+
+            let failed = results.iter().filter(|(_, s)| matches!(s, RepoActionStatus::Failed(_))).count();
+            let summary = format!("{} deleted, {} failed", results.len() - failed, failed);
+            if failed > 0 {
+                println!("{}", summary.yellow());
+            } else {
+                println!("{}", summary.green());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites `[2/5  40%] name (3.50 MB / 12.00 MB)... ` as the bytes of the
+/// file go through, `done` being the bytes of the previous files. The caller
+/// completes the line once the transfer is over.
+fn progress(index: usize, count: usize, done: u64, total: u64, name: &str, size: u64) -> TransferProgress {
+    let name = name.to_string();
+    Arc::new(move |sent| {
+        let percent = ((done + sent) * 100).checked_div(total).unwrap_or(0);
+        print!(
+            "\r{} {} ({:>10} / {})... ",
+            format!("[{}/{} {:>3}%]", index + 1, count, percent).dimmed(),
+            name,
+            human_size(sent as f64),
+            human_size(size as f64)
+        );
+        let _ = std::io::stdout().flush();
+    })
+}
+
+fn kind(release: &Release) -> &'static str {
+    if release.draft {
+        "draft"
+    } else if release.prerelease {
+        "pre-release"
+    } else {
+        "release"
+    }
+}
+
+fn kind_label(release: &Release) -> ColoredString {
+    if release.draft {
+        kind(release).dimmed()
+    } else if release.prerelease {
+        kind(release).yellow()
+    } else {
+        kind(release).green()
+    }
+}
+
+fn release_table(releases: &[Release]) -> Table {
+    let now = Utc::now();
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Tag", "Title", "Type", "Assets", "Published"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for release in releases {
+        let assets = match release.assets.len() {
+            0 => "-".to_string(),
+            n => format!("{} ({})", n, human_size(release.assets_size() as f64)),
+        };
+        table.add_row(vec![
+            release.tag_name.clone(),
+            truncate(release.title(), 50),
+            kind_label(release).to_string(),
+            assets,
+            release.date().map(|d| relative_time(d, now)).unwrap_or_else(|| "-".into()),
+        ]);
+    }
+    table
+}
+
+fn display_release(release: &Release) {
+    let now = Utc::now();
+    println!("{} {}", release.title().cyan().bold(), release.tag_name.dimmed());
+    let date = release.date().map(|d| relative_time(d, now)).unwrap_or_else(|| "-".into());
+    let verb = if release.draft { "created" } else { "published" };
+    println!("{} · {} {} {}\n", kind_label(release), release.author.bold(), verb, date);
+
+    match release.body {
+        Some(ref body) => println!("{}\n", markdown::to_plain_text(body)),
+        None => println!("{}\n", "No release notes".dimmed()),
+    }
+
+    if release.assets.is_empty() {
+        println!("{}", "No assets".dimmed());
+    } else {
+        let mut table = Table::new();
+        table
+            .set_header(vec!["Asset", "Size", "Downloads"])
+            .load_preset(presets::UTF8_FULL)
+            .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+        for asset in &release.assets {
+            table.add_row(vec![asset.name.clone(), human_size(asset.size_in_bytes as f64), asset.download_count.to_string()]);
+        }
+        println!("{}", table);
+    }
+    println!("{}", release.html_url.cyan());
+}
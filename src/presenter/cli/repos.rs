@@ -1,37 +1,60 @@
 use std::sync::Arc;
 use crate::infra::github::auth::AuthManager;
-use crate::domain::error::Result;
+use std::path::{Path, PathBuf};
+use crate::domain::error::{GhTuiError, Result};
+use crate::app::clone_use_case::CloneUseCase;
+use crate::domain::git::CloneStatus;
+use crate::infra::config::ConfigManager;
+use crate::infra::git::GitCli;
 use crate::app::repo_use_case::RepoUseCase;
+use crate::domain::repo::{NewRepository, OwnerRepoType, RepoDetails, RepoQuery, RepoRef, Repository};
+use crate::domain::lifecycle::RepoAction;
+use crate::presenter::cli::{lifecycle, settings, LsArgs, RepoCommands};
+use crate::presenter::markdown;
+use crate::presenter::template::{human_size, relative_time};
+use crate::presenter::output::{print_count, Output};
+use chrono::{DateTime, NaiveDate, Utc};
 use colored::*;
 use comfy_table::*;
 
-pub async fn list_repos(
-    owner: Option<String>,
-    filter_private: bool,
-    filter_public: bool,
-    all: bool,
-) -> Result<()> {
+/// Default fields of a repository when `--columns` is not given, e.g. with
+/// `--format tsv`
+pub const REPO_COLUMNS: &[&str] = &["full_name", "private", "language", "stargazers_count", "description"];
+
+pub async fn list_repos(args: LsArgs, output: &Output) -> Result<()> {
     let auth = AuthManager::new()?;
     let client = Arc::new(auth.get_client()?);
     let use_case = RepoUseCase::new(client);
 
-    println!("{}", "📚 Fetching repositories...".cyan());
+    output.status("📚 Fetching repositories...".cyan());
+
+    let query = args.query();
+    let repos = use_case.list_repos(args.owner, args.repo_type, &query).await?;
 
-    let repos = use_case.list_repos(owner, filter_private, filter_public, all).await?;
+    if !output.is_styled_table() {
+        return output.print(&repos, REPO_COLUMNS);
+    }
 
     if repos.is_empty() {
         println!("{}", "No repositories found".yellow());
         return Ok(());
     }
 
-    // Create table
+    println!("\n{}", repo_table(&repos));
+    print_count(output, repos.len(), "repositories");
+
+    Ok(())
+}
+
+/// The `ghr ls` table.
+fn repo_table(repos: &[Repository]) -> Table {
     let mut table = Table::new();
     table
         .set_header(vec!["Name", "Visibility", "Language", "⭐", "Description"])
         .load_preset(presets::UTF8_FULL)
         .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
 
-    for repo in &repos {
+    for repo in repos {
         let visibility = if repo.private { "Private".red() } else { "Public".green() };
         let language = repo.language.as_deref().unwrap_or("-");
         let stars = repo.stargazers_count.to_string();
@@ -45,9 +68,269 @@ pub async fn list_repos(
             &description,
         ]);
     }
+    table
+}
+
+/// A repository that was just created or forked, in the `ghr ls` format.
+fn print_new_repo(repo: &Repository, message: String, output: &Output) -> Result<()> {
+    if !output.is_styled_table() {
+        return output.print(std::slice::from_ref(repo), REPO_COLUMNS);
+    }
+    println!("{}", message.green());
+    println!("{}", repo_table(std::slice::from_ref(repo)));
+    println!("{}", repo.html_url.cyan());
+    Ok(())
+}
+
+pub async fn handle_repo(command: RepoCommands, output: &Output) -> Result<()> {
+    match command {
+        RepoCommands::View { repo, json } => view_repo(&repo, json, output).await,
+        RepoCommands::Create { name, org, private, template, description, gitignore, license } => {
+            let use_case = RepoUseCase::new(Arc::new(AuthManager::new()?.get_client()?));
+            let new_repo = NewRepository {
+                name,
+                org,
+                description,
+                private,
+                template,
+                gitignore_template: gitignore,
+                // GitHub expects license keywords in lowercase (mit, apache-2.0)
+                license_template: license.map(|l| l.to_lowercase()),
+            };
+            match new_repo.template {
+                Some(ref t) => output.status(format!("📦 Generating {} from {}...", new_repo.name, t).cyan()),
+                None => output.status(format!("📦 Creating {}...", new_repo.name).cyan()),
+            }
+            let repo = use_case.create(&new_repo).await?;
+            print_new_repo(&repo, format!("✓ Created {}", repo.full_name), output)
+        }
+        RepoCommands::Fork { repo, org } => {
+            let use_case = RepoUseCase::new(Arc::new(AuthManager::new()?.get_client()?));
+            output.status(format!("🍴 Forking {}...", repo).cyan());
+            let fork = use_case.fork(&repo, org).await?;
+            print_new_repo(&fork, format!("✓ Forked {} to {}", repo, fork.full_name), output)
+        }
+        RepoCommands::Edit { selection, settings: args, dry_run, yes } => {
+            settings::edit_settings(selection, args, dry_run, yes, output).await
+        }
+        RepoCommands::Archive { selection, dry_run, yes } => {
+            lifecycle::handle_action(RepoAction::Archive, selection, dry_run, yes, output).await
+        }
+        RepoCommands::Unarchive { selection, dry_run, yes } => {
+            lifecycle::handle_action(RepoAction::Unarchive, selection, dry_run, yes, output).await
+        }
+        RepoCommands::Delete { selection, dry_run } => {
+            lifecycle::handle_action(RepoAction::Delete, selection, dry_run, false, output).await
+        }
+        RepoCommands::Clone { repo, dir, org, filter, filters, into, parallel, protocol } => {
+            let config = ConfigManager::new()?.load()?;
+            let auth = AuthManager::new()?;
+            let use_case = CloneUseCase::new(
+                Arc::new(auth.get_client()?),
+                Arc::new(GitCli::new()),
+                config.git.clone_url(protocol),
+            );
 
+            match (repo, org) {
+                (Some(target), _) => {
+                    if filters.query() != RepoQuery::default() {
+                        return Err(GhTuiError::InvalidInput("repository filters require --org".into()));
+                    }
+                    let dest = dir.unwrap_or_else(|| PathBuf::from(&target.name));
+                    clone_one(&use_case, &target, &dest).await
+                }
+                (None, Some(org)) => {
+                    let query = RepoQuery { text: filter, ..filters.query() };
+                    clone_org(&use_case, &org, &query, &into, parallel).await
+                }
+                (None, None) => Err(GhTuiError::InvalidInput("a repository or --org is required".into())),
+            }
+        }
+    }
+}
+
+async fn clone_one(use_case: &CloneUseCase, target: &RepoRef, dest: &Path) -> Result<()> {
+    println!("{}", format!("📥 Cloning {} into {}...", target, dest.display()).cyan());
+    let outcome = use_case.clone_repo(target, dest).await;
+    match outcome.status {
+        CloneStatus::Cloned => println!("{}", format!("✓ Cloned {}", target).green()),
+        CloneStatus::Updated => println!("{}", format!("✓ {} already cloned, fetched latest changes", target).green()),
+        CloneStatus::Failed(e) => return Err(GhTuiError::Git(format!("cannot clone {}: {}", target, e))),
+    }
+    Ok(())
+}
+
+async fn clone_org(use_case: &CloneUseCase, org: &str, query: &RepoQuery, into: &Path, parallel: usize) -> Result<()> {
+    println!(
+        "{}",
+        format!("📥 Cloning repositories of {} into {} ({} at a time)...", org, into.display(), parallel).cyan()
+    );
+    let outcomes = use_case.clone_all(org, OwnerRepoType::All, query, into, parallel).await?;
+    if outcomes.is_empty() {
+        println!("{}", "No repositories matched".yellow());
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Repo", "Path", "Status"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    for o in &outcomes {
+        let status = match o.status {
+            CloneStatus::Cloned => "✓ cloned".green(),
+            CloneStatus::Updated => "✓ updated".green(),
+            CloneStatus::Failed(ref e) => format!("✗ {}", e).red(),
+        };
+        table.add_row(vec![o.repo.clone(), o.path.display().to_string(), status.to_string()]);
+    }
     println!("\n{}", table);
-    println!("\n{} repositories found", repos.len().to_string().cyan());
 
+    let count = |f: fn(&CloneStatus) -> bool| outcomes.iter().filter(|o| f(&o.status)).count();
+    let failed = count(|s| matches!(s, CloneStatus::Failed(_)));
+    let summary = format!(
+        "{} cloned, {} updated, {} failed",
+        count(|s| *s == CloneStatus::Cloned),
+        count(|s| *s == CloneStatus::Updated),
+        failed
+    );
+    if failed > 0 {
+        println!("{}", summary.yellow());
+    } else {
+        println!("{}", summary.green());
+    }
     Ok(())
 }
+
+async fn view_repo(target: &RepoRef, json: bool, output: &Output) -> Result<()> {
+    let auth = AuthManager::new()?;
+    let client = Arc::new(auth.get_client()?);
+    let use_case = RepoUseCase::new(client);
+
+    let details = use_case.view(target).await?;
+    if json || output.is_json() {
+        return output.print_json(&details);
+    }
+
+    display_details(&details);
+    Ok(())
+}
+
+fn display_details(details: &RepoDetails) {
+    let repo = &details.repo;
+    let now = Utc::now();
+    let visibility = if repo.private { "Private".red() } else { "Public".green() };
+
+    println!("{} {}", repo.full_name.cyan().bold(), format!("({})", visibility).dimmed());
+    if let Some(ref description) = repo.description {
+        println!("{}", description);
+    }
+    if !repo.topics.is_empty() {
+        println!("{}", repo.topics.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ").blue());
+    }
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(presets::UTF8_FULL).apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    let mut row = |label: &str, value: String| {
+        table.add_row(vec![label.to_string(), value]);
+    };
+
+    row("Default branch", repo.default_branch.clone());
+    row("License", repo.license.clone().unwrap_or_else(|| "-".into()));
+    if let Some(ref homepage) = repo.homepage {
+        row("Homepage", homepage.clone());
+    }
+    row("Stars / Forks", format!("⭐ {}  🍴 {}", repo.stargazers_count, repo.forks_count));
+    row(
+        "Open issues / PRs",
+        match details.open_pull_requests {
+            Some(prs) => format!("{} / {}", details.open_issues, prs),
+            None => format!("{} (including PRs) / ?", details.open_issues),
+        },
+    );
+    row("Size", human_size(repo.size as f64 * 1024.0));
+    if let Some(pushed_at) = repo.pushed_at {
+        row("Last push", relative_time(pushed_at, now));
+    }
+    row(
+        "Languages",
+        if details.languages.is_empty() {
+            "-".into()
+        } else {
+            details
+                .languages
+                .iter()
+                .map(|l| format!("{} {:.1}%", l.name, l.percentage))
+                .collect::<Vec<_>>()
+                .join(", ")
+        },
+    );
+    row(
+        "Latest release",
+        match details.latest_release {
+            Some(ref release) => format!(
+                "{}{}",
+                release.tag_name,
+                release.published_at.map(|p| format!(" ({})", relative_time(p, now))).unwrap_or_default()
+            ),
+            None => "-".into(),
+        },
+    );
+    row(
+        "Last workflow run",
+        match details.last_workflow_run {
+            Some(ref run) => {
+                let state = run.conclusion.as_deref().unwrap_or(&run.status);
+                let state = match state {
+                    "success" => state.green(),
+                    "failure" | "timed_out" | "startup_failure" => state.red(),
+                    _ => state.yellow(),
+                };
+                format!("{} {} on {} ({})", state, run.name, run.branch, relative_time(run.created_at, now))
+            }
+            None => "-".into(),
+        },
+    );
+    row("URL", repo.html_url.clone());
+    println!("{}", table);
+
+    match details.readme {
+        Some(ref readme) => {
+            println!("\n{}\n", "README".cyan().bold());
+            println!("{}", markdown::to_plain_text(readme));
+        }
+        None => println!("\n{}", "No README".yellow()),
+    }
+}
+
+/// Turn a pair of exclusive flags such as `--private/--public` into a filter.
+pub fn visibility(only: bool, only_not: bool) -> Option<bool> {
+    match (only, only_not) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date (midnight UTC) or a full RFC 3339 timestamp.
+pub fn parse_date(input: &str) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(date) = input.parse::<DateTime<Utc>>() {
+        return Ok(date);
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        .map_err(|_| format!("invalid date '{}' (expected YYYY-MM-DD)", input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("2024-03-01").unwrap().to_rfc3339(), "2024-03-01T00:00:00+00:00");
+        assert_eq!(parse_date("2024-03-01T12:30:00Z").unwrap().to_rfc3339(), "2024-03-01T12:30:00+00:00");
+        assert!(parse_date("01/03/2024").unwrap_err().contains("expected YYYY-MM-DD"));
+    }
+}
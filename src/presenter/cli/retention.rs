@@ -0,0 +1,193 @@
+use std::sync::Arc;
+use colored::*;
+use comfy_table::*;
+use dialoguer::Confirm;
+use serde::Serialize;
+use crate::app::repo_use_case::RepoUseCase;
+use crate::app::retention_use_case::RetentionUseCase;
+use crate::domain::error::Result;
+use crate::domain::repo::{OwnerRepoType, RepoQuery, Repository};
+use crate::domain::retention::{RetentionChange, RetentionChangeStatus};
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::{BulkRepoFilter, RetentionCommands};
+use crate::presenter::cli::repos::visibility;
+use crate::presenter::output::Output;
+
+/// Default fields of `ghr retention get` when `--columns` is not given
+pub const RETENTION_COLUMNS: &[&str] = &["target", "days", "maximum_allowed_days"];
+/// Default fields of `ghr retention set` when `--columns` is not given
+pub const RETENTION_CHANGE_COLUMNS: &[&str] = &["repo", "previous_days", "days", "status"];
+
+/// The retention of a repository or an organization; `days` is empty when it
+/// could not be read
+#[derive(Serialize)]
+struct RetentionRow {
+    target: String,
+    days: Option<u32>,
+    maximum_allowed_days: Option<u32>,
+}
+
+pub async fn handle_retention(command: RetentionCommands, output: &Output) -> Result<()> {
+    let auth = AuthManager::new()?;
+    let client = Arc::new(auth.get_client()?);
+    let use_case = RetentionUseCase::new(client.clone());
+    let repo_use_case = RepoUseCase::new(client);
+
+    match command {
+        RetentionCommands::Get { target, filter } => {
+            if let Some(repo) = target.repo {
+                let policy = use_case.get_repo(&repo).await?;
+                if !output.is_styled_table() {
+                    let row = RetentionRow { target: repo.to_string(), days: Some(policy.days), maximum_allowed_days: policy.maximum_allowed_days };
+                    return output.print(&[row], RETENTION_COLUMNS);
+                }
+                println!("{}: {} days{}", repo.to_string().cyan(), policy.days, max_label(policy.maximum_allowed_days));
+            } else if let Some(org) = target.org {
+                let policy = use_case.get_org(&org).await?;
+                if !output.is_styled_table() {
+                    let row = RetentionRow { target: org, days: Some(policy.days), maximum_allowed_days: policy.maximum_allowed_days };
+                    return output.print(&[row], RETENTION_COLUMNS);
+                }
+                println!("{} (org default): {} days{}", org.cyan(), policy.days, max_label(policy.maximum_allowed_days));
+            } else {
+                let repos = bulk_repos(&repo_use_case, filter, output).await?;
+                if repos.is_empty() && output.is_styled_table() {
+                    println!("{}", "No repositories match the filters".yellow());
+                    return Ok(());
+                }
+
+                output.status(format!("📚 Reading retention of {} repositories...", repos.len()).cyan());
+                let policies = use_case.get_for_repos(&repos).await;
+                if !output.is_styled_table() {
+                    let rows: Vec<RetentionRow> = policies
+                        .into_iter()
+                        .map(|(repo, policy)| RetentionRow {
+                            target: repo,
+                            days: policy.map(|p| p.days),
+                            maximum_allowed_days: policy.and_then(|p| p.maximum_allowed_days),
+                        })
+                        .collect();
+                    return output.print(&rows, RETENTION_COLUMNS);
+                }
+
+                let mut table = Table::new();
+                table
+                    .set_header(vec!["Repo", "Retention", "Maximum"])
+                    .load_preset(presets::UTF8_FULL)
+                    .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+                for (repo, policy) in &policies {
+                    let (days, max) = match policy {
+                        Some(p) => (
+                            format!("{} days", p.days),
+                            p.maximum_allowed_days.map(|m| format!("{} days", m)).unwrap_or_else(|| "-".into()),
+                        ),
+                        None => ("unavailable".red().to_string(), "-".into()),
+                    };
+                    table.add_row(vec![repo.clone(), days, max]);
+                }
+                println!("\n{}", table);
+            }
+        }
+        RetentionCommands::Set { days, target, filter, dry_run, yes } => {
+            if let Some(repo) = target.repo {
+                let previous = use_case.set_repo(&repo, days).await?;
+                if !output.is_styled_table() {
+                    return output.print(&[updated(repo.to_string(), previous.days, days)], RETENTION_CHANGE_COLUMNS);
+                }
+                println!("{}", format!("✓ {}: {} → {} days", repo, previous.days, days).green());
+            } else if let Some(org) = target.org {
+                let previous = use_case.set_org(&org, days).await?;
+                if !output.is_styled_table() {
+                    return output.print(&[updated(org, previous.days, days)], RETENTION_CHANGE_COLUMNS);
+                }
+                println!("{}", format!("✓ {} (org default): {} → {} days", org, previous.days, days).green());
+            } else {
+                let repos = bulk_repos(&repo_use_case, filter, output).await?;
+                if repos.is_empty() {
+                    if !output.is_styled_table() {
+                        return output.print::<RetentionChange>(&[], RETENTION_CHANGE_COLUMNS);
+                    }
+                    println!("{}", "No repositories match the filters".yellow());
+                    return Ok(());
+                }
+
+                if !dry_run && !yes {
+                    let confirmed = Confirm::new()
+                        .with_prompt(format!("Set retention to {} days on {} repositories?", days, repos.len()))
+                        .default(false)
+                        .interact()?;
+                    if !confirmed {
+                        output.status("Cancelled.".yellow());
+                        return Ok(());
+                    }
+                }
+
+                let changes = use_case.set_for_repos(&repos, days, dry_run).await?;
+                if !output.is_styled_table() {
+                    return output.print(&changes, RETENTION_CHANGE_COLUMNS);
+                }
+                display_changes(&changes, dry_run);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn bulk_repos(use_case: &RepoUseCase, filter: BulkRepoFilter, output: &Output) -> Result<Vec<Repository>> {
+    output.status("📚 Fetching repositories...".cyan());
+    let query = RepoQuery {
+        private: visibility(filter.private, filter.public),
+        ..Default::default()
+    };
+    let mut repos = use_case.list_repos(filter.owner, OwnerRepoType::All, &query).await?;
+    if let Some(text) = filter.filter {
+        let text = text.to_lowercase();
+        repos.retain(|r| r.name.to_lowercase().contains(&text));
+    }
+    Ok(repos)
+}
+
+/// A single repository or organization whose retention was set
+fn updated(target: String, previous_days: u32, days: u32) -> RetentionChange {
+    RetentionChange { repo: target, previous_days: Some(previous_days), days, status: RetentionChangeStatus::Updated }
+}
+
+fn max_label(max: Option<u32>) -> String {
+    max.map(|m| format!(" (maximum {} days)", m)).unwrap_or_default()
+}
+
+fn display_changes(changes: &[RetentionChange], dry_run: bool) {
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Repo", "Before", "After", "Status"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for c in changes {
+        let status = match &c.status {
+            RetentionChangeStatus::Updated => "✓ updated".green(),
+            RetentionChangeStatus::Unchanged => "unchanged".normal(),
+            RetentionChangeStatus::Pending => "would update".cyan(),
+            RetentionChangeStatus::Failed(e) => format!("✗ {}", e).red(),
+        };
+        table.add_row(vec![
+            c.repo.clone(),
+            c.previous_days.map(|d| format!("{} days", d)).unwrap_or_else(|| "-".into()),
+            format!("{} days", c.days),
+            status.to_string(),
+        ]);
+    }
+    println!("\n{}", table);
+
+    let count = |f: fn(&RetentionChangeStatus) -> bool| changes.iter().filter(|c| f(&c.status)).count();
+    let failed = count(|s| matches!(s, RetentionChangeStatus::Failed(_)));
+    let unchanged = count(|s| *s == RetentionChangeStatus::Unchanged);
+    if dry_run {
+        let pending = count(|s| *s == RetentionChangeStatus::Pending);
+        println!("{}", format!("Dry run: {} would be updated, {} unchanged, {} failed", pending, unchanged, failed).cyan());
+    } else {
+        let updated = count(|s| *s == RetentionChangeStatus::Updated);
+        println!("{}", format!("{} updated, {} unchanged, {} failed", updated, unchanged, failed).green());
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use chrono::Utc;
+use colored::*;
+use comfy_table::*;
+use crate::app::search_use_case::SearchUseCase;
+use crate::domain::error::Result;
+use crate::domain::search::{highlight_ranges, CodeHit, IssueHit, SearchHits, SearchKind, SearchQuery, SEARCH_RESULT_CAP};
+use crate::domain::repo::Repository;
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::issues::state_label;
+use crate::presenter::cli::repos::REPO_COLUMNS;
+use crate::presenter::cli::{SearchArgs, SearchCommands};
+use crate::presenter::output::Output;
+use crate::presenter::template::{relative_time, snippet, truncate};
+
+/// Default fields of issue and pull request search hits when `--columns` is not given
+pub const ISSUE_HIT_COLUMNS: &[&str] = &["repository", "number", "title", "state", "author", "updated_at"];
+/// Default fields of code search hits; the matched lines are left to `--format json`
+pub const CODE_HIT_COLUMNS: &[&str] = &["repository", "path", "html_url"];
+
+pub async fn handle_search(command: SearchCommands, output: &Output) -> Result<()> {
+    let (kind, args) = match command {
+        SearchCommands::Issues { args } => (SearchKind::Issues, args),
+        SearchCommands::Prs { args } => (SearchKind::PullRequests, args),
+        SearchCommands::Repos { args } => (SearchKind::Repositories, args),
+        SearchCommands::Code { args } => (SearchKind::Code, args),
+    };
+    let query = query(kind, &args);
+
+    let auth = AuthManager::new()?;
+    let use_case = SearchUseCase::new(Arc::new(auth.get_client()?));
+
+    output.status(format!("🔍 Searching {} for '{}'...", kind, query.query).cyan());
+    let results = use_case.search(&query).await?;
+
+    if !output.is_styled_table() {
+        return match results.hits {
+            SearchHits::Issues(ref hits) => output.print(hits, ISSUE_HIT_COLUMNS),
+            SearchHits::Repositories(ref hits) => output.print(hits, REPO_COLUMNS),
+            SearchHits::Code(ref hits) => output.print(hits, CODE_HIT_COLUMNS),
+        };
+    }
+
+    if results.hits.is_empty() {
+        println!("{}", format!("No {} found", kind).yellow());
+        return Ok(());
+    }
+    let table = match results.hits {
+        SearchHits::Issues(ref hits) => issue_table(hits, &results.terms),
+        SearchHits::Repositories(ref hits) => repo_table(hits, &results.terms),
+        SearchHits::Code(ref hits) => code_table(hits, &results.terms),
+    };
+    println!("\n{}", table);
+
+    println!(
+        "\n{}",
+        format!("Showing {} of {} {}", results.hits.len(), results.total_count, kind).cyan()
+    );
+    if results.incomplete_results {
+        println!("{}", "⚠ GitHub timed out, some matches may be missing".yellow());
+    }
+    if results.total_count as usize > SEARCH_RESULT_CAP && results.hits.len() == SEARCH_RESULT_CAP {
+        println!(
+            "{}",
+            format!("⚠ GitHub returns at most {} results, add qualifiers to narrow the search", SEARCH_RESULT_CAP).yellow()
+        );
+    }
+    Ok(())
+}
+
+fn query(kind: SearchKind, args: &SearchArgs) -> SearchQuery {
+    SearchQuery {
+        kind,
+        query: args.query.join(" "),
+        limit: args.limit as usize,
+    }
+}
+
+/// `text` with the parts matching `terms` in bold yellow.
+pub fn highlight(text: &str, terms: &[String]) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for range in highlight_ranges(text, terms) {
+        out.push_str(&text[last..range.start]);
+        out.push_str(&text[range.clone()].yellow().bold().to_string());
+        last = range.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+fn table(header: Vec<&str>) -> Table {
+    let mut table = Table::new();
+    table
+        .set_header(header)
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    table
+}
+
+fn issue_table(hits: &[IssueHit], terms: &[String]) -> Table {
+    let now = Utc::now();
+    let mut table = table(vec!["Repository", "#", "Title", "State", "Author", "Updated"]);
+    for hit in hits {
+        let issue = &hit.issue;
+        table.add_row(vec![
+            hit.repository.clone(),
+            issue.number.to_string(),
+            highlight(&truncate(&issue.title, 60), terms),
+            state_label(issue).to_string(),
+            issue.author.clone(),
+            relative_time(issue.updated_at, now),
+        ]);
+    }
+    table
+}
+
+fn repo_table(repos: &[Repository], terms: &[String]) -> Table {
+    let mut table = table(vec!["Repository", "⭐", "Language", "Description"]);
+    for repo in repos {
+        table.add_row(vec![
+            highlight(&repo.full_name, terms),
+            repo.stargazers_count.to_string(),
+            repo.language.clone().unwrap_or_else(|| "-".into()),
+            highlight(&truncate(repo.description.as_deref().unwrap_or("-"), 60), terms),
+        ]);
+    }
+    table
+}
+
+fn code_table(hits: &[CodeHit], terms: &[String]) -> Table {
+    let mut table = table(vec!["Repository", "Path", "Match"]);
+    for hit in hits {
+        let fragment = hit.fragment.as_deref().map(|f| snippet(f, terms, 70)).unwrap_or_default();
+        table.add_row(vec![hit.repository.clone(), hit.path.clone(), highlight(&fragment, terms)]);
+    }
+    table
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use colored::*;
+use comfy_table::*;
+use dialoguer::Confirm;
+use crate::app::repo_use_case::RepoUseCase;
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::domain::settings::SettingChange;
+use crate::infra::config;
+use crate::infra::github::auth::AuthManager;
+use crate::presenter::cli::lifecycle::{check_selection, selected_repos};
+use crate::presenter::cli::{RepoSelection, SettingsArgs};
+use crate::presenter::output::Output;
+
+/// Default fields of `ghr repo edit` plans when `--columns` is not given; the
+/// JSON formats also keep each setting's current and desired value
+pub const SETTINGS_PLAN_COLUMNS: &[&str] = &["owner", "name", "status"];
+
+/// `ghr repo edit`: preview the differences with the desired settings, then apply them.
+/// With `--format` or `--jq`, the plans are printed once, after being applied
+/// (or as they are with `--dry-run`).
+pub async fn edit_settings(selection: RepoSelection, args: SettingsArgs, dry_run: bool, yes: bool, output: &Output) -> Result<()> {
+    check_selection(&selection)?;
+    let desired = match args.settings {
+        Some(ref path) => config::load_repo_settings(path)?.merge(args.flags()),
+        None => args.flags(),
+    };
+    if desired.is_empty() {
+        return Err(GhTuiError::InvalidInput(
+            "no settings to change (pass flags such as --wiki false, or --settings FILE)".into(),
+        ));
+    }
+    desired.validate().map_err(GhTuiError::InvalidInput)?;
+
+    let auth = AuthManager::new()?;
+    let use_case = RepoUseCase::new(Arc::new(auth.get_client()?));
+
+    // Archived repositories are read-only, so they are only edited when named or with --all
+    let repos = selected_repos(&use_case, selection, false, output).await?;
+    if repos.is_empty() && output.is_styled_table() {
+        println!("{}", "No repositories match the filters".yellow());
+        return Ok(());
+    }
+
+    output.status(format!("🔍 Reading the settings of {} repositories...", repos.len()).cyan());
+    let mut plans = use_case.plan_settings(&repos, &desired).await?;
+    let pending = plans.iter().filter(|p| p.status == RepoActionStatus::Pending).count();
+    if !output.is_styled_table() && (pending == 0 || dry_run) {
+        return output.print(&plans, SETTINGS_PLAN_COLUMNS);
+    }
+    if output.is_styled_table() {
+        print_diff(&plans.iter().map(|p| (format!("{}/{}", p.owner, p.name), &p.status, &p.changes[..])).collect::<Vec<_>>());
+    }
+
+    if pending == 0 {
+        println!("{}", "✓ Every repository already has these settings".green());
+        return Ok(());
+    }
+    if dry_run {
+        println!("{}", format!("Dry run: {} repositories would change", pending).cyan());
+        return Ok(());
+    }
+
+    let confirmed = yes
+        || Confirm::new()
+            .with_prompt(format!("Apply these changes to {} repositories?", pending))
+            .default(false)
+            .interact()?;
+    if !confirmed {
+        for plan in plans.iter_mut().filter(|p| p.status == RepoActionStatus::Pending) {
+            plan.status = RepoActionStatus::Skipped;
+        }
+    }
+
+    use_case.apply_settings(&mut plans).await;
+    if !output.is_styled_table() {
+        return output.print(&plans, SETTINGS_PLAN_COLUMNS);
+    }
+    print_results(&plans.iter().map(|p| (format!("{}/{}", p.owner, p.name), p.changes.len(), &p.status)).collect::<Vec<_>>());
+    Ok(())
+}
+
+/// Current and desired value of every setting that changes, per repository.
+pub fn print_diff(plans: &[(String, &RepoActionStatus, &[SettingChange])]) {
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Repo", "Setting", "Current", "Desired"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+
+    for (repo, status, changes) in plans {
+        match status {
+            RepoActionStatus::Failed(e) => {
+                table.add_row(vec![repo.clone(), "-".into(), format!("✗ {}", e).red().to_string(), "-".into()]);
+            }
+            _ if changes.is_empty() => {
+                table.add_row(vec![repo.clone(), "-".into(), "up to date".green().to_string(), "-".into()]);
+            }
+            _ => {
+                for (i, change) in changes.iter().enumerate() {
+                    table.add_row(vec![
+                        if i == 0 { repo.clone() } else { String::new() },
+                        change.setting.to_string(),
+                        shown(&change.current).red().to_string(),
+                        shown(&change.desired).green().to_string(),
+                    ]);
+                }
+            }
+        }
+    }
+    println!("\n{}", table);
+}
+
+/// Outcome per repository once applied, with totals.
+pub fn print_results(plans: &[(String, usize, &RepoActionStatus)]) {
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Repo", "Changes", "Status"])
+        .load_preset(presets::UTF8_FULL)
+        .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+    for (repo, changes, status) in plans {
+        let status = match status {
+            RepoActionStatus::Done => "✓ updated".green(),
+            RepoActionStatus::Unchanged => "up to date".normal(),
+            RepoActionStatus::Pending => "pending".cyan(),
+            RepoActionStatus::Skipped => "skipped".yellow(),
+            RepoActionStatus::Failed(e) => format!("✗ {}", e).red(),
+        };
+        table.add_row(vec![repo.clone(), changes.to_string(), status.to_string()]);
+    }
+    println!("\n{}", table);
+
+    let count = |f: fn(&RepoActionStatus) -> bool| plans.iter().filter(|(_, _, status)| f(status)).count();
+    let failed = count(|s| matches!(s, RepoActionStatus::Failed(_)));
+    let summary = format!(
+        "{} updated, {} up to date, {} skipped, {} failed",
+        count(|s| *s == RepoActionStatus::Done),
+        count(|s| *s == RepoActionStatus::Unchanged),
+        count(|s| *s == RepoActionStatus::Skipped),
+        failed
+    );
+    if failed > 0 {
+        println!("{}", summary.yellow());
+    } else {
+        println!("{}", summary.green());
+    }
+}
+
+fn shown(value: &str) -> &str {
+    if value.is_empty() { "(empty)" } else { value }
+}
@@ -0,0 +1,174 @@
+use std::str::FromStr;
+use jaq_core::load::{self, lex, Arena, File, Loader};
+use jaq_core::{compile, data, unwrap_valr, Compiler, Ctx, Vars};
+use jaq_json::Val;
+use serde_json::Value;
+use crate::domain::error::{GhTuiError, Result};
+
+type Filter = jaq_core::Filter<data::JustLut<Val>>;
+
+/// A `--jq` filter such as `.[] | select(.private) | .full_name`, applied to
+/// the JSON output of a command.
+///
+/// Filters are evaluated with jaq, so the jq language and its standard
+/// library are available without jq being installed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JqFilter {
+    code: String,
+}
+
+impl FromStr for JqFilter {
+    type Err = String;
+
+    fn from_str(code: &str) -> std::result::Result<Self, Self::Err> {
+        let filter = Self { code: code.to_string() };
+        // Compile up front so typos are reported before any API call
+        filter.compile()?;
+        Ok(filter)
+    }
+}
+
+impl JqFilter {
+    /// Every value produced by the filter for `input`.
+    pub fn apply(&self, input: &Value) -> Result<Vec<Value>> {
+        let filter = self.compile().map_err(GhTuiError::InvalidInput)?;
+        let input = jaq_json::read::parse_single(serde_json::to_string(input)?.as_bytes())
+            .map_err(|e| GhTuiError::InvalidInput(format!("jq: {}", e)))?;
+
+        let ctx = Ctx::<data::JustLut<Val>>::new(&filter.lut, Vars::new([]));
+        filter
+            .id
+            .run((ctx, input))
+            .map(unwrap_valr)
+            .map(|output| {
+                let output = output.map_err(|e| GhTuiError::InvalidInput(format!("jq: {}", e)))?;
+                Ok(serde_json::from_str(&output.to_string())?)
+            })
+            .collect()
+    }
+
+    /// One line per result; strings are printed raw like `gh --jq`.
+    pub fn render(&self, input: &Value) -> Result<String> {
+        let mut out = String::new();
+        for value in self.apply(input)? {
+            match value {
+                Value::String(s) => out.push_str(&s),
+                other => out.push_str(&serde_json::to_string_pretty(&other)?),
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    fn compile(&self) -> std::result::Result<Filter, String> {
+        let defs = jaq_core::defs().chain(jaq_std::defs()).chain(jaq_json::defs());
+        let funs = jaq_core::funs().chain(jaq_std::funs()).chain(jaq_json::funs());
+
+        let arena = Arena::default();
+        let modules = Loader::new(defs)
+            .load(&arena, File { code: self.code.as_str(), path: () })
+            .map_err(|errors| self.invalid(load_errors(errors)))?;
+        Compiler::default()
+            .with_funs(funs)
+            .compile(modules)
+            .map_err(|errors| self.invalid(compile_errors(errors)))
+    }
+
+    fn invalid(&self, messages: Vec<String>) -> String {
+        format!("invalid jq expression '{}': {}", self.code, messages.join("; "))
+    }
+}
+
+fn load_errors(errors: load::Errors<&str, ()>) -> Vec<String> {
+    errors
+        .into_iter()
+        .flat_map(|(_, error)| match error {
+            load::Error::Io(errors) => errors.into_iter().map(|(_, e)| e).collect(),
+            load::Error::Lex(errors) => errors
+                .into_iter()
+                .map(|(expect, found)| expected(lex_expect(&expect), found))
+                .collect(),
+            load::Error::Parse(errors) => errors
+                .into_iter()
+                .map(|(expect, found)| expected(expect.as_str(), found))
+                .collect::<Vec<_>>(),
+        })
+        .collect()
+}
+
+fn compile_errors(errors: compile::Errors<&str, ()>) -> Vec<String> {
+    errors
+        .into_iter()
+        .flat_map(|(_, errors)| errors)
+        .map(|(name, undefined)| format!("undefined {} '{}'", undefined.as_str(), name))
+        .collect()
+}
+
+fn lex_expect(expect: &lex::Expect<&str>) -> &'static str {
+    match expect {
+        // jaq only describes the delimiters it opens itself
+        lex::Expect::Delim(d) if !["(", "[", "{", "\""].contains(d) => "closing delimiter",
+        other => other.as_str(),
+    }
+}
+
+/// `found` is the unparsed rest of the expression.
+fn expected(expect: &str, found: &str) -> String {
+    match found.split_whitespace().next() {
+        Some(token) => format!("expected {}, found '{}'", expect, token),
+        None => format!("expected {}, found end of input", expect),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn repos() -> Value {
+        json!([
+            {"full_name": "acme/api", "private": true, "stargazers_count": 42},
+            {"full_name": "acme/web", "private": false, "stargazers_count": 7}
+        ])
+    }
+
+    #[test]
+    fn test_selection() {
+        let filter: JqFilter = ".[0].full_name".parse().unwrap();
+        assert_eq!(filter.apply(&repos()).unwrap(), vec![json!("acme/api")]);
+
+        let filter: JqFilter = ".[] | select(.private | not) | .full_name".parse().unwrap();
+        assert_eq!(filter.render(&repos()).unwrap(), "acme/web\n");
+    }
+
+    #[test]
+    fn test_mapping() {
+        let filter: JqFilter = "map({name: .full_name, stars: .stargazers_count})".parse().unwrap();
+        assert_eq!(
+            filter.apply(&repos()).unwrap(),
+            vec![json!([{"name": "acme/api", "stars": 42}, {"name": "acme/web", "stars": 7}])]
+        );
+
+        let filter: JqFilter = "map(.stargazers_count) | add".parse().unwrap();
+        assert_eq!(filter.render(&repos()).unwrap(), "49\n");
+    }
+
+    #[test]
+    fn test_bad_expressions() {
+        let err = ".[0".parse::<JqFilter>().unwrap_err();
+        assert!(err.starts_with("invalid jq expression '.[0'"), "{}", err);
+
+        let err = "map(.name) |".parse::<JqFilter>().unwrap_err();
+        assert!(err.contains("expected term, found end of input"), "{}", err);
+
+        let err = "frobnicate".parse::<JqFilter>().unwrap_err();
+        assert!(err.contains("undefined filter 'frobnicate'"), "{}", err);
+    }
+
+    #[test]
+    fn test_runtime_error() {
+        let filter: JqFilter = ".[0] | keys | .foo".parse().unwrap();
+        let err = filter.apply(&repos()).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid input: jq: "), "{}", err);
+    }
+}
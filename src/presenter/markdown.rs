@@ -0,0 +1,186 @@
+/// Render Markdown (as found in READMEs) as plain text for the terminal.
+///
+/// This is deliberately lossy: markup is removed, links and images keep their
+/// text, code blocks are indented and HTML tags are dropped.
+pub fn to_plain_text(markdown: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_code = false;
+
+    for line in strip_html_comments(markdown).lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(format!("    {}", line));
+            continue;
+        }
+        if is_table_separator(trimmed) {
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let (prefix, rest) = if let Some(heading) = heading_text(trimmed) {
+            ("", heading)
+        } else if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")).or_else(|| trimmed.strip_prefix("+ ")) {
+            ("• ", item)
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            ("│ ", quote.trim_start())
+        } else {
+            ("", trimmed)
+        };
+
+        let text = inline(rest);
+        // Lines made only of markup (badges without alt text, HTML) disappear
+        if text.trim().is_empty() && !rest.trim().is_empty() {
+            continue;
+        }
+        lines.push(format!("{}{}{}", indent, prefix, text).trim_end().to_string());
+    }
+
+    // Collapse runs of blank lines left behind by removed markup
+    let mut out = String::new();
+    let mut blank = true;
+    for line in lines {
+        if line.is_empty() {
+            if !blank {
+                out.push('\n');
+            }
+            blank = true;
+        } else {
+            out.push_str(&line);
+            out.push('\n');
+            blank = false;
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn heading_text(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&hashes) && line[hashes..].starts_with(' ') {
+        Some(line[hashes..].trim().trim_end_matches('#').trim_end())
+    } else {
+        None
+    }
+}
+
+fn is_table_separator(line: &str) -> bool {
+    line.starts_with('|') && line.contains("--") && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+fn strip_html_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Inline markup: images, links, HTML tags, emphasis and code spans.
+fn inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '!' && rest[1..].starts_with('[') {
+            if let Some((label, after)) = link(&rest[1..]) {
+                out.push_str(&inline(label));
+                rest = after;
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some((label, after)) = link(rest) {
+                out.push_str(&inline(label));
+                rest = after;
+                continue;
+            }
+        }
+        if c == '<' {
+            if let Some(end) = rest.find('>') {
+                let tag = &rest[1..end];
+                if tag.starts_with('/') || tag.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                    // Autolinks such as <https://example.com> keep their URL
+                    if tag.contains("://") && !tag.contains(' ') {
+                        out.push_str(tag);
+                    }
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        if rest.starts_with("**") || rest.starts_with("__") {
+            rest = &rest[2..];
+            continue;
+        }
+        if c == '`' {
+            rest = &rest[1..];
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// `[label](url)` (or `[label][ref]`) at the start of `text`: the label and what follows.
+fn link(text: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+    let mut label_end = None;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    label_end = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let label_end = label_end?;
+    let after = &text[label_end + 1..];
+    let close = match after.chars().next()? {
+        '(' => ')',
+        '[' => ']',
+        _ => return None,
+    };
+    let target_end = after.find(close)?;
+    Some((&text[1..label_end], &after[target_end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headings_lists_and_emphasis() {
+        let md = "# ghr\n\nA **modern** `GitHub` CLI.\n\n## Install\n- cargo\n* brew\n> Note: beta\n";
+        assert_eq!(
+            to_plain_text(md),
+            "ghr\n\nA modern GitHub CLI.\n\nInstall\n• cargo\n• brew\n│ Note: beta"
+        );
+    }
+
+    #[test]
+    fn test_links_images_and_html() {
+        let md = "[![CI](https://ci/badge.svg)](https://ci) [![](x.svg)](y)\n\n<p align=\"center\"><img src=\"logo.png\"></p>\n\nSee [the docs](https://docs) or <https://example.com>.<!-- hidden -->";
+        assert_eq!(to_plain_text(md), "CI\n\nSee the docs or https://example.com.");
+    }
+
+    #[test]
+    fn test_code_blocks_and_tables() {
+        let md = "Run:\n\n```bash\nghr **ls**\n```\n\n| a | b |\n|---|:-:|\n| 1 | 2 |";
+        assert_eq!(to_plain_text(md), "Run:\n\n    ghr **ls**\n\n| a | b |\n| 1 | 2 |");
+    }
+}
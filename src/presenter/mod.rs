@@ -1,2 +1,6 @@
 pub mod cli;
+pub mod output;
+pub mod template;
+pub mod jq;
+pub mod markdown;
 pub mod tui;
@@ -0,0 +1,292 @@
+use clap::ValueEnum;
+use colored::*;
+use comfy_table::{modifiers, presets, Table};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::domain::error::{GhTuiError, Result};
+use crate::presenter::jq::JqFilter;
+use crate::presenter::template::Template;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable table
+    #[default]
+    Table,
+    /// JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values with a header
+    Csv,
+    /// Tab-separated values with a header
+    Tsv,
+}
+
+/// How list commands print their results, shared by every command.
+#[derive(Debug, Clone, Default)]
+pub struct Output {
+    pub format: OutputFormat,
+    /// Fields to print; empty means the command's defaults
+    pub columns: Vec<String>,
+    /// Per-item template, takes precedence over `format`
+    pub template: Option<Template>,
+    /// Filter applied to the JSON output, takes precedence over `format`
+    pub jq: Option<JqFilter>,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat, columns: Vec<String>) -> Self {
+        Self { format, columns, template: None, jq: None }
+    }
+
+    pub fn with_template(mut self, template: Option<Template>) -> Self {
+        self.template = template;
+        self
+    }
+
+    pub fn with_jq(mut self, jq: Option<JqFilter>) -> Self {
+        self.jq = jq;
+        self
+    }
+
+    /// True when the command should print its own styled table.
+    pub fn is_styled_table(&self) -> bool {
+        self.format == OutputFormat::Table && self.columns.is_empty() && !self.is_machine_readable()
+    }
+
+    /// True when stdout is meant for other programs rather than a human.
+    pub fn is_machine_readable(&self) -> bool {
+        self.format != OutputFormat::Table || self.template.is_some() || self.jq.is_some()
+    }
+
+    /// True when the command should print JSON, possibly through `--jq`.
+    pub fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json || self.jq.is_some()
+    }
+
+    /// Print a whole JSON document, such as a report, filtered by `--jq` if set.
+    pub fn print_json<T: Serialize>(&self, value: &T) -> Result<()> {
+        let value = serde_json::to_value(value)?;
+        match self.jq {
+            Some(ref jq) => print!("{}", jq.render(&value)?),
+            None => println!("{}", serde_json::to_string_pretty(&value)?),
+        }
+        Ok(())
+    }
+
+    /// Print a status message without polluting machine-readable output.
+    pub fn status(&self, message: impl std::fmt::Display) {
+        if !self.is_machine_readable() {
+            println!("{}", message);
+        } else {
+            eprintln!("{}", message);
+        }
+    }
+
+    pub fn print<T: Serialize>(&self, items: &[T], default_columns: &[&str]) -> Result<()> {
+        let rendered = self.render(items, default_columns)?;
+        if !self.is_machine_readable() {
+            println!("\n{}", rendered);
+        } else {
+            print!("{}", rendered);
+        }
+        Ok(())
+    }
+
+    pub fn render<T: Serialize>(&self, items: &[T], default_columns: &[&str]) -> Result<String> {
+        let rows = to_objects(items)?;
+        if let Some(ref template) = self.template {
+            return template.render_all(&rows);
+        }
+        let columns = self.selected_columns(&rows, default_columns)?;
+        if let Some(ref jq) = self.jq {
+            let rows: Vec<Value> = rows.into_iter().map(|r| self.project(r, &columns)).collect();
+            return jq.render(&Value::Array(rows));
+        }
+
+        let out = match self.format {
+            OutputFormat::Json => {
+                let rows: Vec<Value> = rows.into_iter().map(|r| self.project(r, &columns)).collect();
+                format!("{}\n", serde_json::to_string_pretty(&rows)?)
+            }
+            OutputFormat::Ndjson => {
+                let mut out = String::new();
+                for row in rows {
+                    out.push_str(&serde_json::to_string(&self.project(row, &columns))?);
+                    out.push('\n');
+                }
+                out
+            }
+            OutputFormat::Csv => delimited(&rows, &columns, ','),
+            OutputFormat::Tsv => delimited(&rows, &columns, '\t'),
+            OutputFormat::Table => {
+                let mut table = Table::new();
+                table
+                    .set_header(columns.clone())
+                    .load_preset(presets::UTF8_FULL)
+                    .apply_modifier(modifiers::UTF8_ROUND_CORNERS);
+                for row in &rows {
+                    table.add_row(columns.iter().map(|c| cell(row.get(c))).collect::<Vec<_>>());
+                }
+                table.to_string()
+            }
+        };
+        Ok(out)
+    }
+
+    /// JSON keeps every field unless columns were explicitly requested.
+    fn project(&self, row: Map<String, Value>, columns: &[String]) -> Value {
+        if self.columns.is_empty() {
+            return Value::Object(row);
+        }
+        let mut projected = Map::new();
+        for c in columns {
+            projected.insert(c.clone(), row.get(c).cloned().unwrap_or(Value::Null));
+        }
+        Value::Object(projected)
+    }
+
+    fn selected_columns(&self, rows: &[Map<String, Value>], default_columns: &[&str]) -> Result<Vec<String>> {
+        if self.columns.is_empty() {
+            return Ok(default_columns.iter().map(|c| c.to_string()).collect());
+        }
+
+        if let Some(first) = rows.first() {
+            if let Some(unknown) = self.columns.iter().find(|c| !first.contains_key(*c)) {
+                let available: Vec<&str> = first.keys().map(|k| k.as_str()).collect();
+                return Err(GhTuiError::InvalidInput(format!(
+                    "unknown column '{}' (available: {})",
+                    unknown,
+                    available.join(", ")
+                )));
+            }
+        }
+        Ok(self.columns.clone())
+    }
+}
+
+fn to_objects<T: Serialize>(items: &[T]) -> Result<Vec<Map<String, Value>>> {
+    items
+        .iter()
+        .map(|item| match serde_json::to_value(item)? {
+            Value::Object(map) => Ok(map),
+            other => {
+                let mut map = Map::new();
+                map.insert("value".into(), other);
+                Ok(map)
+            }
+        })
+        .collect()
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn delimited(rows: &[Map<String, Value>], columns: &[String], separator: char) -> String {
+    let escape = |field: String| -> String {
+        if separator == '\t' {
+            // TSV has no quoting: replace the characters that would break the layout
+            field.replace(['\t', '\n', '\r'], " ")
+        } else if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field
+        }
+    };
+    let sep = separator.to_string();
+
+    let mut out = columns.iter().map(|c| escape(c.clone())).collect::<Vec<_>>().join(&sep);
+    out.push('\n');
+    for row in rows {
+        let line: Vec<String> = columns.iter().map(|c| escape(cell(row.get(c)))).collect();
+        out.push_str(&line.join(&sep));
+        out.push('\n');
+    }
+    out
+}
+
+/// Footer such as "12 repositories found", only shown in table mode.
+pub fn print_count(output: &Output, count: usize, noun: &str) {
+    if !output.is_machine_readable() {
+        println!("\n{} {} found", count.to_string().cyan(), noun);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        stars: u32,
+        language: Option<String>,
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row { name: "api".into(), stars: 3, language: Some("Rust".into()) },
+            Row { name: "web, legacy".into(), stars: 0, language: None },
+        ]
+    }
+
+    #[test]
+    fn test_csv_with_default_columns() {
+        let output = Output::new(OutputFormat::Csv, vec![]);
+        let csv = output.render(&rows(), &["name", "stars"]).unwrap();
+        assert_eq!(csv, "name,stars\napi,3\n\"web, legacy\",0\n");
+    }
+
+    #[test]
+    fn test_tsv_with_selected_columns() {
+        let output = Output::new(OutputFormat::Tsv, vec!["language".into(), "name".into()]);
+        let tsv = output.render(&rows(), &["name"]).unwrap();
+        assert_eq!(tsv, "language\tname\nRust\tapi\n\tweb, legacy\n");
+    }
+
+    #[test]
+    fn test_json_keeps_all_fields_unless_columns_selected() {
+        let output = Output::new(OutputFormat::Json, vec![]);
+        let json: Value = serde_json::from_str(&output.render(&rows(), &["name"]).unwrap()).unwrap();
+        assert_eq!(json[0]["language"], "Rust");
+
+        let output = Output::new(OutputFormat::Ndjson, vec!["stars".into()]);
+        let ndjson = output.render(&rows(), &["name"]).unwrap();
+        assert_eq!(ndjson, "{\"stars\":3}\n{\"stars\":0}\n");
+    }
+
+    #[test]
+    fn test_template_takes_precedence() {
+        let template = "{{name}}={{stars}}".parse().ok();
+        let output = Output::new(OutputFormat::Csv, vec![]).with_template(template);
+        assert_eq!(output.render(&rows(), &["name"]).unwrap(), "api=3\nweb, legacy=0\n");
+        assert!(output.is_machine_readable());
+    }
+
+    #[test]
+    fn test_unknown_column_is_rejected() {
+        let output = Output::new(OutputFormat::Csv, vec!["owner".into()]);
+        let err = output.render(&rows(), &["name"]).unwrap_err();
+        assert!(err.to_string().contains("unknown column 'owner'"));
+        assert!(err.to_string().contains("name, stars, language"));
+    }
+
+    #[test]
+    fn test_jq_filters_json_rows() {
+        let jq = ".[] | select(.stars > 0) | .name".parse().ok();
+        let output = Output::new(OutputFormat::Table, vec![]).with_jq(jq);
+        assert_eq!(output.render(&rows(), &["name"]).unwrap(), "api\n");
+        assert!(output.is_json());
+        assert!(!output.is_styled_table());
+
+        let jq = "map(keys)".parse().ok();
+        let output = Output::new(OutputFormat::Json, vec!["stars".into()]).with_jq(jq);
+        let json: Value = serde_json::from_str(&output.render(&rows(), &["name"]).unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!([["stars"], ["stars"]]));
+    }
+}
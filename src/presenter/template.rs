@@ -0,0 +1,382 @@
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use crate::domain::error::{GhTuiError, Result};
+use crate::domain::search::highlight_ranges;
+
+/// A `--template` such as `{{full_name}}\t{{size_in_bytes | size}}`, rendered
+/// once per item of a list command.
+///
+/// Expressions are a field name (optionally prefixed with `.` as in Go
+/// templates, nested with `a.b`) followed by helpers separated by `|`:
+/// `size`, `ago`, `truncate N`, `upper`, `lower` and `default TEXT`. A
+/// `TEXT` containing `|` must be double-quoted: `default "n | a"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Field { path: Vec<String>, helpers: Vec<Helper> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Helper {
+    /// Human-readable byte size
+    Size,
+    /// Relative time from an RFC 3339 date
+    Ago,
+    Truncate(usize),
+    Upper,
+    Lower,
+    Default(String),
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        let input = unescape(input);
+        let mut segments = Vec::new();
+        let mut rest = input.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_string()));
+            }
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| "unclosed '{{' in template".to_string())?;
+            segments.push(parse_expression(&after[..end])?);
+            rest = &after[end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl Template {
+    /// Render every row, one per line.
+    pub fn render_all(&self, rows: &[Map<String, Value>]) -> Result<String> {
+        if let Some(first) = rows.first() {
+            self.validate(first)?;
+        }
+
+        let mut out = String::new();
+        for row in rows {
+            let line = self.render(row);
+            out.push_str(&line);
+            if !line.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+        Ok(out)
+    }
+
+    fn render(&self, row: &Map<String, Value>) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Field { path, helpers } => {
+                    let value = lookup(row, path).cloned().unwrap_or(Value::Null);
+                    to_text(helpers.iter().fold(value, |value, helper| helper.apply(value, Utc::now())))
+                }
+            })
+            .collect()
+    }
+
+    /// Catch typos in field names instead of silently printing blanks.
+    fn validate(&self, row: &Map<String, Value>) -> Result<()> {
+        for segment in &self.segments {
+            if let Segment::Field { path, .. } = segment {
+                let top = &path[0];
+                if !row.contains_key(top) {
+                    let available: Vec<&str> = row.keys().map(|k| k.as_str()).collect();
+                    return Err(GhTuiError::InvalidInput(format!(
+                        "unknown template field '{}' (available: {})",
+                        path.join("."),
+                        available.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_expression(expr: &str) -> std::result::Result<Segment, String> {
+    let mut parts = split_helpers(expr)?.into_iter().map(str::trim);
+    let field = parts.next().unwrap_or_default().trim_start_matches('.');
+    if field.is_empty() {
+        return Err(format!("empty field in '{{{{{}}}}}'", expr));
+    }
+    let path = field.split('.').map(|p| p.to_string()).collect();
+
+    let helpers = parts
+        .map(|part| {
+            let (name, arg) = match part.split_once(char::is_whitespace) {
+                Some((name, arg)) => (name, Some(arg.trim())),
+                None => (part, None),
+            };
+            match (name, arg) {
+                ("size", None) => Ok(Helper::Size),
+                ("ago", None) => Ok(Helper::Ago),
+                ("upper", None) => Ok(Helper::Upper),
+                ("lower", None) => Ok(Helper::Lower),
+                ("truncate", Some(n)) => n
+                    .parse()
+                    .map(Helper::Truncate)
+                    .map_err(|_| format!("truncate expects a length, got '{}'", n)),
+                ("default", Some(text)) => {
+                    let unquoted = text.strip_prefix('"').and_then(|t| t.strip_suffix('"'));
+                    Ok(Helper::Default(unquoted.unwrap_or(text).to_string()))
+                }
+                _ => Err(format!(
+                    "unknown template helper '{}' (expected size, ago, truncate N, upper, lower or default TEXT)",
+                    part
+                )),
+            }
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(Segment::Field { path, helpers })
+}
+
+/// Split an expression on the `|` that are not inside double quotes.
+fn split_helpers(expr: &str) -> std::result::Result<Vec<&str>, String> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in expr.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '|' if !quoted => {
+                parts.push(&expr[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quoted {
+        return Err(format!("unclosed '\"' in '{{{{{}}}}}'", expr));
+    }
+    parts.push(&expr[start..]);
+    Ok(parts)
+}
+
+fn lookup<'a>(row: &'a Map<String, Value>, path: &[String]) -> Option<&'a Value> {
+    let (first, rest) = path.split_first()?;
+    rest.iter().try_fold(row.get(first)?, |value, key| value.get(key))
+}
+
+/// Interpret `\t`, `\n` and `\\` so templates can be written in single quotes.
+fn unescape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+impl Helper {
+    fn apply(&self, value: Value, now: DateTime<Utc>) -> Value {
+        match self {
+            Helper::Size => match value.as_f64() {
+                Some(bytes) => Value::String(human_size(bytes)),
+                None => value,
+            },
+            Helper::Ago => match value.as_str().and_then(|s| s.parse::<DateTime<Utc>>().ok()) {
+                Some(date) => Value::String(relative_time(date, now)),
+                None => value,
+            },
+            Helper::Truncate(max) => Value::String(truncate(&to_text(value), *max)),
+            Helper::Upper => Value::String(to_text(value).to_uppercase()),
+            Helper::Lower => Value::String(to_text(value).to_lowercase()),
+            Helper::Default(text) => match value {
+                Value::Null => Value::String(text.clone()),
+                Value::String(ref s) if s.is_empty() => Value::String(text.clone()),
+                other => other,
+            },
+        }
+    }
+}
+
+fn to_text(value: Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+pub fn human_size(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", size as u64)
+    } else {
+        format!("{:.2} {}", size, UNITS[unit])
+    }
+}
+
+/// At most `max` characters, ending with `...` when cut. Lengths too short
+/// for the ellipsis keep the first `max` characters.
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else if max <= 3 {
+        text.chars().take(max).collect()
+    } else {
+        let truncated: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    }
+}
+
+/// One line of a code fragment, around its first match and at most `width`
+/// characters long.
+pub fn snippet(fragment: &str, terms: &[String], width: usize) -> String {
+    let line = fragment.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut start = highlight_ranges(&line, terms)
+        .first()
+        .map(|r| r.start.saturating_sub(width / 4))
+        .unwrap_or(0);
+    while !line.is_char_boundary(start) {
+        start -= 1;
+    }
+    let excerpt = truncate(&line[start..], width);
+    if start > 0 { format!("...{}", excerpt) } else { excerpt }
+}
+
+pub fn relative_time(date: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - date).num_seconds();
+    let (amount, unit) = match seconds.abs() {
+        s if s < 60 => return "just now".to_string(),
+        s if s < 3_600 => (s / 60, "minute"),
+        s if s < 86_400 => (s / 3_600, "hour"),
+        s if s < 30 * 86_400 => (s / 86_400, "day"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+        s => (s / (365 * 86_400), "year"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    if seconds >= 0 {
+        format!("{} {}{} ago", amount, unit, plural)
+    } else {
+        format!("in {} {}{}", amount, unit, plural)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    fn row(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_render_fields_with_escapes() {
+        let template: Template = r"{{full_name}}\t{{ .stargazers_count }}".parse().unwrap();
+        let rows = vec![
+            row(json!({"full_name": "acme/api", "stargazers_count": 42})),
+            row(json!({"full_name": "acme/web", "stargazers_count": 0})),
+        ];
+        assert_eq!(template.render_all(&rows).unwrap(), "acme/api\t42\nacme/web\t0\n");
+    }
+
+    #[test]
+    fn test_helpers() {
+        let template: Template =
+            "{{name | upper}} {{size_in_bytes | size}} {{description | default none | truncate 8}} {{owner.login}}"
+                .parse()
+                .unwrap();
+        let rows = vec![row(json!({
+            "name": "dist",
+            "size_in_bytes": 1572864,
+            "description": null,
+            "owner": {"login": "octocat"}
+        }))];
+        assert_eq!(template.render_all(&rows).unwrap(), "DIST 1.50 MB none octocat\n");
+
+        let template: Template = "{{description | truncate 8}}".parse().unwrap();
+        let rows = vec![row(json!({"description": "A very long description"}))];
+        assert_eq!(template.render_all(&rows).unwrap(), "A ver...\n");
+    }
+
+    #[test]
+    fn test_default_text_with_pipe_is_quoted() {
+        let template: Template = r#"{{language | default "n | a" | upper}}"#.parse().unwrap();
+        let rows = vec![row(json!({"language": null}))];
+        assert_eq!(template.render_all(&rows).unwrap(), "N | A\n");
+        assert!(r#"{{language | default "n | a}}"#.parse::<Template>().unwrap_err().contains("unclosed"));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("description", 20), "description");
+        assert_eq!(truncate("description", 7), "desc...");
+        assert_eq!(truncate("description", 3), "des");
+        assert_eq!(truncate("description", 1), "d");
+        assert_eq!(truncate("description", 0), "");
+    }
+
+    #[test]
+    fn test_snippet_centers_first_match() {
+        let terms = vec!["unwrap".to_string()];
+        let fragment = "let config = load_configuration_from_disk(path)\n    .await\n    .unwrap();";
+        assert_eq!(snippet(fragment, &terms, 30), "...await .unwrap();");
+        assert_eq!(snippet("no match here", &terms, 30), "no match here");
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let now = Utc::now();
+        assert_eq!(relative_time(now - Duration::seconds(5), now), "just now");
+        assert_eq!(relative_time(now - Duration::hours(1), now), "1 hour ago");
+        assert_eq!(relative_time(now - Duration::days(3), now), "3 days ago");
+        assert_eq!(relative_time(now + Duration::days(45), now), "in 1 month");
+        assert_eq!(relative_time(now - Duration::days(800), now), "2 years ago");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("{{name".parse::<Template>().unwrap_err().contains("unclosed"));
+        assert!("{{name | bold}}".parse::<Template>().unwrap_err().contains("unknown template helper"));
+        assert!("{{name | truncate x}}".parse::<Template>().is_err());
+        assert!("{{ }}".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        let template: Template = "{{fullname}}".parse().unwrap();
+        let err = template.render_all(&[row(json!({"full_name": "acme/api"}))]).unwrap_err();
+        assert!(err.to_string().contains("unknown template field 'fullname'"));
+    }
+}
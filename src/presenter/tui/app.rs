@@ -1,10 +1,38 @@
 use crate::infra::github::auth::AuthManager;
 use crate::infra::github::client::GitHubClient;
-use crate::domain::repo::Repository;
+use crate::infra::config::ConfigManager;
+use crate::infra::config::storage::FileStorageHistory;
+use crate::app::storage_history_use_case::StorageHistoryUseCase;
+use crate::domain::history::UsagePoint;
+use crate::domain::repo::{RepoQuery, RepoRef, RepoSort, Repository};
 use crate::domain::artifact::Artifact;
+use crate::domain::branch::{Branch, BranchFilter};
+use crate::domain::lifecycle::RepoActionStatus;
+use crate::app::branch_use_case::BranchUseCase;
+use crate::app::search_use_case::SearchUseCase;
+use crate::app::pull_request_use_case::PullRequestUseCase;
+use crate::domain::pull_request::{MergeMethod, MergeOutcome, NewReview, PrListQuery, PullRequest, PullRequestDetails, ReviewEvent};
+use crate::infra::git::GitCli;
+use crate::infra::utils::browser;
+use crate::domain::search::{SearchKind, SearchQuery, SearchResults};
 use crate::domain::storage::{StorageItem, StorageUsageReport, StorageItemType};
 use crate::domain::error::Result;
 use crate::domain::github::GithubRepository;
+use chrono::Utc;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Number of snapshots shown in the Storage Manager history chart
+const HISTORY_CHART_POINTS: usize = 60;
+
+/// Results fetched by a TUI search
+const SEARCH_LIMIT: usize = 100;
+
+/// Branches without commits for this many days can be deleted from the branch view
+pub const BRANCH_STALE_DAYS: i64 = 90;
+
+/// Pull requests fetched by the pull request list
+const PULL_REQUEST_LIMIT: usize = 100;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum View {
@@ -12,10 +40,21 @@ pub enum View {
     RepoList,
     RepoDetail,
     ArtifactList,
+    BranchList,
+    Search,
+    PullRequestList,
+    PullRequestDetail,
     StorageManager,
     AuthPrompt,
 }
 
+/// An action on the open pull request waiting for confirmation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PendingPrAction {
+    Approve,
+    Merge,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputMode {
     Normal,
@@ -25,6 +64,8 @@ pub enum InputMode {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MenuItem {
     Repositories,
+    PullRequests,
+    Search,
     StorageManager,
     Quit,
 }
@@ -33,6 +74,8 @@ impl MenuItem {
     pub fn all() -> Vec<MenuItem> {
         vec![
             MenuItem::Repositories,
+            MenuItem::PullRequests,
+            MenuItem::Search,
             MenuItem::StorageManager,
             MenuItem::Quit,
         ]
@@ -41,6 +84,8 @@ impl MenuItem {
     pub fn label(&self) -> &'static str {
         match self {
             MenuItem::Repositories => "Repositories",
+            MenuItem::PullRequests => "Pull Requests",
+            MenuItem::Search => "Search",
             MenuItem::StorageManager => "Storage Manager",
             MenuItem::Quit => "Quit",
         }
@@ -49,6 +94,8 @@ impl MenuItem {
     pub fn description(&self) -> &'static str {
         match self {
             MenuItem::Repositories => "Browse and explore your GitHub repositories",
+            MenuItem::PullRequests => "Review, merge and check out pull requests",
+            MenuItem::Search => "Find issues, PRs, repos and code on GitHub",
             MenuItem::StorageManager => "Analyze and clean up storage usage",
             MenuItem::Quit => "Exit the application",
         }
@@ -63,6 +110,7 @@ pub struct App {
     pub artifacts: Vec<Artifact>,
     pub storage_items: Vec<StorageItem>,
     pub storage_report: Option<StorageUsageReport>,
+    pub storage_history: Vec<UsagePoint>,
     pub selected_index: usize,
     pub menu_index: usize,
     pub selected_storage_items: Vec<usize>,
@@ -71,6 +119,31 @@ pub struct App {
     pub error_message: Option<String>,
     pub success_message: Option<String>,
     pub filter_text: String,
+    /// Sorting and filters of the repository list; `filter_text` is applied on top
+    pub repo_query: RepoQuery,
+    /// Branches of `branch_repo`, shown in the branch view
+    pub branches: Vec<Branch>,
+    pub branch_repo: Option<RepoRef>,
+    /// Repository list index to restore when leaving the branch view
+    pub branch_return_index: usize,
+    /// Branch waiting for a second `d` to confirm its deletion
+    pub pending_branch_delete: Option<String>,
+    /// Query typed in the search view, in GitHub search syntax
+    pub search_query: String,
+    pub search_kind: SearchKind,
+    pub search_results: Option<SearchResults>,
+    /// Filters of the pull request list
+    pub pr_query: PrListQuery,
+    /// Author typed in the pull request list, applied on Enter
+    pub pr_author_input: String,
+    pub pull_requests: Vec<PullRequest>,
+    /// The pull request shown in the detail view
+    pub pr_details: Option<PullRequestDetails>,
+    /// Pull request list index to restore when leaving the detail view
+    pub pr_return_index: usize,
+    pub pending_pr_action: Option<PendingPrAction>,
+    /// Lines the description in the detail view is scrolled by
+    pub pr_scroll: u16,
     pub client: Option<GitHubClient>,
     pub tick: u64,
     pub pending_load: Option<MenuItem>,
@@ -90,6 +163,7 @@ impl App {
             artifacts: Vec::new(),
             storage_items: Vec::new(),
             storage_report: None,
+            storage_history: Vec::new(),
             selected_index: 0,
             menu_index: 0,
             selected_storage_items: Vec::new(),
@@ -98,6 +172,21 @@ impl App {
             error_message: None,
             success_message: None,
             filter_text: String::new(),
+            repo_query: RepoQuery { include_archived: true, ..Default::default() },
+            branches: Vec::new(),
+            branch_repo: None,
+            branch_return_index: 0,
+            pending_branch_delete: None,
+            search_query: String::new(),
+            search_kind: SearchKind::default(),
+            search_results: None,
+            pr_query: PrListQuery { limit: PULL_REQUEST_LIMIT, ..Default::default() },
+            pr_author_input: String::new(),
+            pull_requests: Vec::new(),
+            pr_details: None,
+            pr_return_index: 0,
+            pending_pr_action: None,
+            pr_scroll: 0,
             client,
             tick: 0,
             pending_load: None, // New field
@@ -173,19 +262,346 @@ impl App {
         Ok(())
     }
 
+    /// Open the branch view for the selected repository.
+    pub async fn open_branches(&mut self) -> Result<()> {
+        if let Some(repo) = self.selected_repo() {
+            self.branch_repo = Some(RepoRef { owner: repo.owner.clone(), name: repo.name.clone() });
+            self.branch_return_index = self.selected_index;
+            self.current_view = View::BranchList;
+            self.load_branches().await?;
+        }
+        Ok(())
+    }
+
+    /// Back to the repository the branches were opened from.
+    pub fn close_branches(&mut self) {
+        self.current_view = View::RepoDetail;
+        self.selected_index = self.branch_return_index;
+        self.pending_branch_delete = None;
+        self.error_message = None;
+    }
+
+    pub async fn load_branches(&mut self) -> Result<()> {
+        if let (Some(client), Some(target)) = (self.client.clone(), self.branch_repo.clone()) {
+            self.loading = true;
+            self.loading_message = format!("Loading branches for {}...", target);
+            self.error_message = None;
+            self.pending_branch_delete = None;
+
+            let use_case = BranchUseCase::new(Arc::new(client));
+            match use_case.list(&target, &BranchFilter::default()).await {
+                Ok(branches) => {
+                    self.branches = branches;
+                    self.selected_index = 0;
+                }
+                Err(e) => {
+                    self.error_message = Some(format!("Error loading branches: {}", e));
+                }
+            }
+
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    pub fn selected_branch(&self) -> Option<&Branch> {
+        self.branches.get(self.selected_index)
+    }
+
+    /// Delete the selected branch on the second request in a row. Only
+    /// branches `ghr branches prune` would delete as merged or stale are
+    /// accepted; the default branch, protected ones and branches with an open
+    /// pull request are refused.
+    pub async fn delete_selected_branch(&mut self) -> Result<()> {
+        let Some(branch) = self.selected_branch().cloned() else {
+            return Ok(());
+        };
+        if branch.prune_reason(true, Some(BRANCH_STALE_DAYS), Utc::now()).is_none() {
+            self.pending_branch_delete = None;
+            self.error_message = Some(format!("{} is not merged or stale and cannot be deleted here", branch.name));
+            return Ok(());
+        }
+        if self.pending_branch_delete.as_deref() != Some(branch.name.as_str()) {
+            self.error_message = None;
+            self.pending_branch_delete = Some(branch.name);
+            return Ok(());
+        }
+
+        self.pending_branch_delete = None;
+        if let (Some(client), Some(target)) = (self.client.clone(), self.branch_repo.clone()) {
+            let use_case = BranchUseCase::new(Arc::new(client));
+            let results = use_case.delete(&target, std::slice::from_ref(&branch.name)).await;
+            match results.into_iter().next().map(|(_, status)| status) {
+                Some(RepoActionStatus::Failed(e)) => {
+                    self.error_message = Some(format!("Error deleting {}: {}", branch.name, e));
+                }
+                _ => {
+                    self.branches.retain(|b| b.name != branch.name);
+                    self.selected_index = self.selected_index.min(self.branches.len().saturating_sub(1));
+                    self.success_message = Some(format!("✓ Deleted branch: {}", branch.name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn run_search(&mut self) -> Result<()> {
+        if let Some(client) = self.client.clone() {
+            let query = SearchQuery {
+                kind: self.search_kind,
+                query: self.search_query.clone(),
+                limit: SEARCH_LIMIT,
+            };
+            self.loading = true;
+            self.loading_message = format!("Searching {}...", query.kind);
+            self.error_message = None;
+
+            match SearchUseCase::new(Arc::new(client)).search(&query).await {
+                Ok(results) => {
+                    self.search_results = Some(results);
+                    self.selected_index = 0;
+                }
+                Err(e) => {
+                    self.search_results = None;
+                    self.error_message = Some(format!("Search failed: {}", e));
+                }
+            }
+
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    /// Search the next kind of results with the same query.
+    pub async fn cycle_search_kind(&mut self) -> Result<()> {
+        self.search_kind = self.search_kind.next();
+        self.search_results = None;
+        if !self.search_query.trim().is_empty() {
+            self.run_search().await?;
+        }
+        Ok(())
+    }
+
+    fn pull_request_use_case(&self) -> Option<PullRequestUseCase> {
+        let client = self.client.clone()?;
+        Some(PullRequestUseCase::new(Arc::new(client), Arc::new(GitCli::new())))
+    }
+
+    pub async fn load_pull_requests(&mut self) -> Result<()> {
+        if let Some(use_case) = self.pull_request_use_case() {
+            self.loading = true;
+            self.loading_message = "Loading pull requests...".into();
+            self.error_message = None;
+
+            match use_case.list(&self.pr_query).await {
+                Ok(pull_requests) => {
+                    self.pull_requests = pull_requests;
+                    self.selected_index = 0;
+                }
+                Err(e) => {
+                    self.pull_requests.clear();
+                    self.error_message = Some(format!("Error loading pull requests: {}", e));
+                }
+            }
+
+            self.loading = false;
+        }
+        Ok(())
+    }
+
+    pub async fn cycle_pr_state(&mut self) -> Result<()> {
+        self.pr_query.state = self.pr_query.state.next();
+        self.load_pull_requests().await
+    }
+
+    pub async fn toggle_pr_review_requested(&mut self) -> Result<()> {
+        self.pr_query.review_requested = !self.pr_query.review_requested;
+        self.load_pull_requests().await
+    }
+
+    /// Filter by the author typed in `pr_author_input`, or any author when empty.
+    pub async fn apply_pr_author(&mut self) -> Result<()> {
+        let author = self.pr_author_input.trim();
+        self.pr_query.author = (!author.is_empty()).then(|| author.to_string());
+        self.load_pull_requests().await
+    }
+
+    pub fn selected_pull_request(&self) -> Option<&PullRequest> {
+        self.pull_requests.get(self.selected_index)
+    }
+
+    /// Open the detail view for the selected pull request.
+    pub async fn open_pull_request(&mut self) -> Result<()> {
+        if self.selected_pull_request().is_some() {
+            self.pr_return_index = self.selected_index;
+            self.current_view = View::PullRequestDetail;
+            self.pr_details = None;
+            self.load_pull_request_details().await?;
+        }
+        Ok(())
+    }
+
+    /// Back to the list the pull request was opened from.
+    pub fn close_pull_request(&mut self) {
+        self.current_view = View::PullRequestList;
+        self.selected_index = self.pr_return_index;
+        self.pending_pr_action = None;
+        self.error_message = None;
+    }
+
+    pub async fn load_pull_request_details(&mut self) -> Result<()> {
+        let Some(pr) = self.pull_requests.get(self.pr_return_index).cloned() else {
+            return Ok(());
+        };
+        let (Some(use_case), Ok(target)) = (self.pull_request_use_case(), pr.repository.parse::<RepoRef>()) else {
+            return Ok(());
+        };
+        self.loading = true;
+        self.loading_message = format!("Loading #{} of {}...", pr.number, pr.repository);
+        self.error_message = None;
+        self.pending_pr_action = None;
+        self.pr_scroll = 0;
+
+        match use_case.details(&target, pr.number).await {
+            Ok(details) => {
+                // Keep the list in sync with what the detail view shows
+                if let Some(listed) = self.pull_requests.get_mut(self.pr_return_index) {
+                    *listed = details.pull_request.clone();
+                }
+                self.pr_details = Some(details);
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Error loading #{}: {}", pr.number, e));
+            }
+        }
+
+        self.loading = false;
+        Ok(())
+    }
+
+    fn open_pr_target(&self) -> Option<(RepoRef, PullRequest)> {
+        let pr = self.pr_details.as_ref()?.pull_request.clone();
+        Some((pr.repository.parse().ok()?, pr))
+    }
+
+    /// Approve the open pull request on the second request in a row.
+    pub async fn approve_pull_request(&mut self) -> Result<()> {
+        if self.pending_pr_action != Some(PendingPrAction::Approve) {
+            self.error_message = None;
+            self.pending_pr_action = Some(PendingPrAction::Approve);
+            return Ok(());
+        }
+        self.pending_pr_action = None;
+        let (Some(use_case), Some((target, pr))) = (self.pull_request_use_case(), self.open_pr_target()) else {
+            return Ok(());
+        };
+        let review = NewReview { event: ReviewEvent::Approve, body: None };
+        match use_case.review(&target, pr.number, &review).await {
+            Ok(_) => {
+                self.load_pull_request_details().await?;
+                self.success_message = Some(format!("✓ Approved #{}", pr.number));
+            }
+            Err(e) => self.error_message = Some(format!("Error approving #{}: {}", pr.number, e)),
+        }
+        Ok(())
+    }
+
+    /// Ask which method to merge the open pull request with.
+    pub fn request_merge(&mut self) {
+        self.error_message = None;
+        self.pending_pr_action = Some(PendingPrAction::Merge);
+    }
+
+    pub async fn merge_pull_request(&mut self, method: MergeMethod) -> Result<()> {
+        self.pending_pr_action = None;
+        let (Some(use_case), Some((target, pr))) = (self.pull_request_use_case(), self.open_pr_target()) else {
+            return Ok(());
+        };
+        match use_case.merge(&target, pr.number, method, false, false).await {
+            Ok((_, MergeOutcome::Merged { .. })) => {
+                self.load_pull_request_details().await?;
+                self.success_message = Some(format!("✓ Merged #{}: {}", pr.number, pr.title));
+            }
+            Ok((_, MergeOutcome::AutoMergeEnabled)) => {
+                self.success_message = Some(format!("✓ Auto-merge enabled for #{}", pr.number));
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Check out the open pull request, provided the current directory is a
+    /// clone of its repository.
+    pub async fn checkout_pull_request(&mut self) -> Result<()> {
+        let (Some(use_case), Some((target, pr))) = (self.pull_request_use_case(), self.open_pr_target()) else {
+            return Ok(());
+        };
+        let dir = Path::new(".");
+        match use_case.repo_from_remote(dir, "origin") {
+            Ok(here) if here == target => {}
+            Ok(here) => {
+                self.error_message = Some(format!("The current directory is a clone of {}, not {}", here, target));
+                return Ok(());
+            }
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return Ok(());
+            }
+        }
+        match use_case.checkout(dir, "origin", pr.number, None).await {
+            Ok((_, branch)) => self.success_message = Some(format!("✓ Checked out #{} on branch {}", pr.number, branch)),
+            Err(e) => self.error_message = Some(format!("Error checking out #{}: {}", pr.number, e)),
+        }
+        Ok(())
+    }
+
+    /// Open the pull request shown, or selected in the list, in the browser.
+    pub fn open_pull_request_in_browser(&mut self) {
+        let url = match self.current_view {
+            View::PullRequestDetail => self.pr_details.as_ref().map(|d| d.pull_request.html_url.clone()),
+            _ => self.selected_pull_request().map(|pr| pr.html_url.clone()),
+        };
+        if let Some(url) = url {
+            if let Err(e) = browser::open_url(&url) {
+                self.error_message = Some(e.to_string());
+            }
+        }
+    }
+
     pub async fn load_storage(&mut self) -> Result<()> {
         if let Some(ref client) = self.client {
             self.loading = true;
             self.loading_message = "Scanning storage usage...".into();
             self.error_message = None;
 
-            match client.get_storage_usage().await {
-                Ok(report) => {
+            let config = ConfigManager::new().and_then(|m| m.load()).ok();
+            let include_releases = config.as_ref().is_some_and(|c| c.storage.include_release_assets);
+            match client.get_storage_usage(include_releases).await {
+                Ok(mut report) => {
+                    if let Some(ref config) = config {
+                        report.apply_quota_overrides(&config.storage.quota_overrides());
+                    }
+                    let record = config.is_some_and(|c| c.storage.record_history);
+                    match FileStorageHistory::new() {
+                        Ok(history) => {
+                            let history = StorageHistoryUseCase::new(Arc::new(history));
+                            if record {
+                                if let Err(e) = history.record(&report) {
+                                    self.error_message = Some(format!("Error recording storage snapshot: {}", e));
+                                }
+                            }
+                            self.storage_history = history.series(None, Some(HISTORY_CHART_POINTS)).unwrap_or_default();
+                        }
+                        Err(e) if record => {
+                            self.error_message = Some(format!("Error recording storage snapshot: {}", e));
+                        }
+                        Err(_) => {}
+                    }
                     self.storage_items = report.items.clone()
                         .into_iter()
                         .filter(|i| i.size_in_bytes >= 1024)
                         .collect();
-                    self.storage_items.sort_by(|a, b| b.size_in_bytes.cmp(&a.size_in_bytes));
+                    self.storage_items.sort_by_key(|i| std::cmp::Reverse(i.size_in_bytes));
                     self.storage_report = Some(report);
                     self.selected_index = 0;
                     self.selected_storage_items.clear();
@@ -214,6 +630,9 @@ impl App {
                     StorageItemType::Cache => {
                         client.delete_cache(&item.owner, &item.repo, item.id).await?;
                     }
+                    StorageItemType::ReleaseAsset => {
+                        client.delete_release_asset(&item.owner, &item.repo, item.id).await?;
+                    }
                 }
             }
 
@@ -277,6 +696,9 @@ impl App {
             View::MainMenu => MenuItem::all().len(),
             View::RepoList => self.filtered_repos().len(),
             View::ArtifactList => self.artifacts.len(),
+            View::BranchList => self.branches.len(),
+            View::Search => self.search_results.as_ref().map(|r| r.hits.len()).unwrap_or(0),
+            View::PullRequestList => self.pull_requests.len(),
             View::StorageManager => self.storage_items.len(),
             _ => 0,
         }
@@ -292,20 +714,28 @@ impl App {
     }
 
     pub fn filtered_repos(&self) -> Vec<&Repository> {
-        if self.filter_text.is_empty() {
-            self.repos.iter().collect()
-        } else {
-            let filter = self.filter_text.to_lowercase();
-            self.repos
+        let query = RepoQuery {
+            text: (!self.filter_text.is_empty()).then(|| self.filter_text.clone()),
+            ..self.repo_query.clone()
+        };
+        query.apply(&self.repos)
+    }
+
+    /// Cycle the repository list through unsorted and each `RepoSort`.
+    pub fn cycle_repo_sort(&mut self) {
+        let next = match self.repo_query.sort {
+            None => Some(RepoSort::ALL[0]),
+            Some(current) => RepoSort::ALL
                 .iter()
-                .filter(|r| {
-                    r.name.to_lowercase().contains(&filter)
-                        || r.description
-                            .as_ref()
-                            .map(|d| d.to_lowercase().contains(&filter))
-                            .unwrap_or(false)
-                })
-                .collect()
-        }
+                .position(|s| *s == current)
+                .and_then(|i| RepoSort::ALL.get(i + 1).copied()),
+        };
+        self.repo_query.sort = next;
+        self.selected_index = 0;
+    }
+
+    pub fn toggle_repo_sort_order(&mut self) {
+        self.repo_query.descending = !self.repo_query.descending;
+        self.selected_index = 0;
     }
 }
@@ -1,4 +1,5 @@
-use crate::presenter::tui::app::{App, View, InputMode, MenuItem};
+use crate::presenter::tui::app::{App, View, InputMode, MenuItem, PendingPrAction};
+use crate::domain::pull_request::MergeMethod;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use std::time::Duration;
 
@@ -8,8 +9,9 @@ pub async fn handle_events(app: &mut App) -> crate::domain::error::Result<()> {
         app.pending_load = None; // clear it
         match item {
             MenuItem::Repositories => app.load_repos().await?,
+            MenuItem::PullRequests => app.load_pull_requests().await?,
             MenuItem::StorageManager => app.load_storage().await?,
-            MenuItem::Quit => {}
+            MenuItem::Search | MenuItem::Quit => {}
         }
         return Ok(());
     }
@@ -45,9 +47,15 @@ async fn handle_key_event(app: &mut App, key: KeyEvent) -> crate::domain::error:
             View::RepoList => handle_repo_list_keys(app, key).await?,
             View::RepoDetail => handle_repo_detail_keys(app, key).await?,
             View::ArtifactList => handle_artifact_list_keys(app, key).await?,
+            View::BranchList => handle_branch_list_keys(app, key).await?,
+            View::Search => handle_search_keys(app, key).await?,
+            View::PullRequestList => handle_pr_list_keys(app, key).await?,
+            View::PullRequestDetail => handle_pr_detail_keys(app, key).await?,
             View::StorageManager => handle_storage_manager_keys(app, key).await?,
             View::AuthPrompt => handle_auth_prompt_keys(app, key),
         },
+        InputMode::Editing if app.current_view == View::Search => handle_search_input_keys(app, key).await?,
+        InputMode::Editing if app.current_view == View::PullRequestList => handle_pr_author_input_keys(app, key).await?,
         InputMode::Editing => handle_editing_keys(app, key),
     }
     Ok(())
@@ -73,6 +81,19 @@ async fn handle_main_menu_keys(app: &mut App, key: KeyEvent) -> crate::domain::e
                     app.selected_index = 0;
                     app.pending_load = Some(MenuItem::Repositories);
                 }
+                MenuItem::PullRequests => {
+                    app.current_view = View::PullRequestList;
+                    app.loading = true;
+                    app.loading_message = "Loading pull requests...".into();
+                    app.selected_index = 0;
+                    app.pending_load = Some(MenuItem::PullRequests);
+                }
+                MenuItem::Search => {
+                    app.current_view = View::Search;
+                    app.input_mode = InputMode::Editing;
+                    app.selected_index = 0;
+                    app.error_message = None;
+                }
                 MenuItem::StorageManager => {
                     app.current_view = View::StorageManager;
                     app.loading = true;
@@ -105,10 +126,8 @@ async fn handle_repo_list_keys(app: &mut App, key: KeyEvent) -> crate::domain::e
         KeyCode::Up | KeyCode::Char('k') => {
             app.previous();
         }
-        KeyCode::Enter => {
-            if app.selected_repo().is_some() {
-                app.current_view = View::RepoDetail;
-            }
+        KeyCode::Enter if app.selected_repo().is_some() => {
+            app.current_view = View::RepoDetail;
         }
         KeyCode::Char('r') | KeyCode::Char('R') => {
             app.load_repos().await?;
@@ -116,6 +135,12 @@ async fn handle_repo_list_keys(app: &mut App, key: KeyEvent) -> crate::domain::e
         KeyCode::Char('/') => {
             app.input_mode = InputMode::Editing;
         }
+        KeyCode::Char('s') => {
+            app.cycle_repo_sort();
+        }
+        KeyCode::Char('o') => {
+            app.toggle_repo_sort_order();
+        }
         KeyCode::Char('a') | KeyCode::Char('A') => {
             if let Some(repo) = app.selected_repo() {
                 let owner = repo.owner.clone();
@@ -145,11 +170,202 @@ async fn handle_repo_detail_keys(app: &mut App, key: KeyEvent) -> crate::domain:
                 app.current_view = View::ArtifactList;
             }
         }
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            app.open_branches().await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn handle_branch_list_keys(app: &mut App, key: KeyEvent) -> crate::domain::error::Result<()> {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.should_quit = true;
+        }
+        KeyCode::Esc => {
+            app.close_branches();
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.pending_branch_delete = None;
+            app.next();
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.pending_branch_delete = None;
+            app.previous();
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            app.load_branches().await?;
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            app.delete_selected_branch().await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn handle_search_keys(app: &mut App, key: KeyEvent) -> crate::domain::error::Result<()> {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.should_quit = true;
+        }
+        KeyCode::Esc => {
+            app.current_view = View::MainMenu;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.next();
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.previous();
+        }
+        KeyCode::Char('/') => {
+            app.input_mode = InputMode::Editing;
+        }
+        KeyCode::Tab => {
+            app.cycle_search_kind().await?;
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') if !app.search_query.trim().is_empty() => {
+            app.run_search().await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn handle_search_input_keys(app: &mut App, key: KeyEvent) -> crate::domain::error::Result<()> {
+    match key.code {
+        KeyCode::Enter if !app.search_query.trim().is_empty() => {
+            app.input_mode = InputMode::Normal;
+            app.run_search().await?;
+        }
+        KeyCode::Esc => {
+            app.input_mode = InputMode::Normal;
+        }
+        KeyCode::Tab => {
+            app.search_kind = app.search_kind.next();
+        }
+        KeyCode::Char(c) => {
+            app.search_query.push(c);
+        }
+        KeyCode::Backspace => {
+            app.search_query.pop();
+        }
         _ => {}
     }
     Ok(())
 }
 
+async fn handle_pr_list_keys(app: &mut App, key: KeyEvent) -> crate::domain::error::Result<()> {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.should_quit = true;
+        }
+        KeyCode::Esc => {
+            app.current_view = View::MainMenu;
+            app.error_message = None;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.next();
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.previous();
+        }
+        KeyCode::Enter => {
+            app.open_pull_request().await?;
+        }
+        KeyCode::Char('s') => {
+            app.cycle_pr_state().await?;
+        }
+        KeyCode::Char('a') => {
+            app.pr_author_input = app.pr_query.author.clone().unwrap_or_default();
+            app.input_mode = InputMode::Editing;
+        }
+        KeyCode::Char('v') => {
+            app.toggle_pr_review_requested().await?;
+        }
+        KeyCode::Char('o') => {
+            app.open_pull_request_in_browser();
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            app.load_pull_requests().await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn handle_pr_author_input_keys(app: &mut App, key: KeyEvent) -> crate::domain::error::Result<()> {
+    match key.code {
+        KeyCode::Enter => {
+            app.input_mode = InputMode::Normal;
+            app.apply_pr_author().await?;
+        }
+        KeyCode::Esc => {
+            app.input_mode = InputMode::Normal;
+        }
+        KeyCode::Char(c) => {
+            app.pr_author_input.push(c);
+        }
+        KeyCode::Backspace => {
+            app.pr_author_input.pop();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn handle_pr_detail_keys(app: &mut App, key: KeyEvent) -> crate::domain::error::Result<()> {
+    // Choosing the merge method, any other key cancels
+    if app.pending_pr_action == Some(PendingPrAction::Merge) {
+        match key.code {
+            KeyCode::Char('m') => app.merge_pull_request(MergeMethod::Merge).await?,
+            KeyCode::Char('s') => app.merge_pull_request(MergeMethod::Squash).await?,
+            KeyCode::Char('r') => app.merge_pull_request(MergeMethod::Rebase).await?,
+            _ => app.pending_pr_action = None,
+        }
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.should_quit = true;
+        }
+        KeyCode::Esc => {
+            app.close_pull_request();
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.pending_pr_action = None;
+            app.pr_scroll = app.pr_scroll.saturating_add(1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.pending_pr_action = None;
+            app.pr_scroll = app.pr_scroll.saturating_sub(1);
+        }
+        KeyCode::Char('a') => {
+            app.approve_pull_request().await?;
+        }
+        KeyCode::Char('m') => {
+            app.request_merge();
+        }
+        KeyCode::Char('c') => {
+            app.pending_pr_action = None;
+            app.checkout_pull_request().await?;
+        }
+        KeyCode::Char('o') => {
+            app.pending_pr_action = None;
+            app.open_pull_request_in_browser();
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            app.load_pull_request_details().await?;
+        }
+        _ => {
+            app.pending_pr_action = None;
+        }
+    }
+    Ok(())
+}
+
 async fn handle_artifact_list_keys(app: &mut App, key: KeyEvent) -> crate::domain::error::Result<()> {
     match key.code {
         KeyCode::Char('q') | KeyCode::Char('Q') => {
@@ -204,10 +420,8 @@ async fn handle_storage_manager_keys(app: &mut App, key: KeyEvent) -> crate::dom
         KeyCode::Char('r') | KeyCode::Char('R') => {
             app.load_storage().await?;
         }
-        KeyCode::Char('d') | KeyCode::Char('D') => {
-            if !app.selected_storage_items.is_empty() {
-                app.delete_selected_storage_items().await?;
-            }
+        KeyCode::Char('d') | KeyCode::Char('D') if !app.selected_storage_items.is_empty() => {
+            app.delete_selected_storage_items().await?;
         }
         _ => {}
     }
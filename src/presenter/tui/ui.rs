@@ -1,10 +1,19 @@
-use crate::presenter::tui::app::{App, View, InputMode, MenuItem};
+use crate::presenter::tui::app::{App, View, InputMode, MenuItem, PendingPrAction};
+use crate::domain::pull_request::{CheckStatus, MergeableState, PullRequest, ReviewDecision, ReviewState};
+use crate::presenter::markdown;
 use crate::domain::storage::StorageItemType;
+use crate::domain::branch::PullRequestState;
+use crate::presenter::template::relative_time;
+use crate::presenter::tui::app::BRANCH_STALE_DAYS;
+use crate::presenter::template::{snippet, truncate};
+use crate::domain::search::{highlight_ranges, SearchHits};
+use crate::domain::issue::IssueState;
+use chrono::Utc;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Sparkline, Wrap},
     Frame,
 };
 
@@ -282,6 +291,10 @@ fn render_feature_view(f: &mut Frame, app: &App) {
         View::RepoList => render_repo_list(f, chunks[1], app),
         View::RepoDetail => render_repo_detail(f, chunks[1], app),
         View::ArtifactList => render_artifact_list(f, chunks[1], app),
+        View::BranchList => render_branch_list(f, chunks[1], app),
+        View::Search => render_search(f, chunks[1], app),
+        View::PullRequestList => render_pr_list(f, chunks[1], app),
+        View::PullRequestDetail => render_pr_detail(f, chunks[1], app),
         View::StorageManager => render_storage_manager(f, chunks[1], app),
         _ => {}
     }
@@ -346,7 +359,16 @@ fn render_repo_list(f: &mut Frame, area: Rect, app: &App) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::DarkGray))
-                .title(format!(" {}/{} ", repos.len(), app.repos.len())),
+                .title(match app.repo_query.sort {
+                    Some(sort) => format!(
+                        " {}/{} · sorted by {} {} ",
+                        repos.len(),
+                        app.repos.len(),
+                        sort.label(),
+                        if app.repo_query.descending { "↓" } else { "↑" }
+                    ),
+                    None => format!(" {}/{} ", repos.len(), app.repos.len()),
+                }),
         );
 
     f.render_widget(list, list_chunks[0]);
@@ -455,19 +477,495 @@ fn render_artifact_list(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(list, area);
 }
 
+fn render_branch_list(f: &mut Frame, area: Rect, app: &App) {
+    if let Some(ref error) = app.error_message {
+        if app.branches.is_empty() {
+            let error_widget = Paragraph::new(format!("❌ {}", error))
+                .style(Style::default().fg(Color::Red))
+                .block(Block::default().borders(Borders::ALL).title(" Error "));
+            f.render_widget(error_widget, area);
+            return;
+        }
+    }
+
+    let repo = app.branch_repo.as_ref().map(|r| r.to_string()).unwrap_or_default();
+    if app.branches.is_empty() {
+        let empty = Paragraph::new("No branches found.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", repo)));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+
+    let now = Utc::now();
+    let items: Vec<ListItem> = app.branches
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let style = if i == app.selected_index {
+                Style::default()
+                    .bg(Color::Rgb(60, 60, 100))
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let marker = if b.is_default { "★" } else if b.protected { "🔒" } else { " " };
+            let age = b.last_commit_at.map(|at| relative_time(at, now)).unwrap_or_else(|| "—".into());
+            let prunable = b.prune_reason(true, Some(BRANCH_STALE_DAYS), now);
+            let pr = match b.pull_request {
+                Some(ref pr) => {
+                    let color = match pr.state {
+                        PullRequestState::Open => Color::Green,
+                        PullRequestState::Draft => Color::DarkGray,
+                        PullRequestState::Closed => Color::Red,
+                        PullRequestState::Merged => Color::Magenta,
+                    };
+                    Span::styled(format!("#{} {:<6}", pr.number, pr.state), Style::default().fg(color))
+                }
+                None => Span::styled(format!("{:<13}", "—"), Style::default().fg(Color::DarkGray)),
+            };
+
+            let content = Line::from(vec![
+                Span::raw(format!("{:<2}", marker)),
+                Span::raw(" "),
+                Span::styled(format!("{:<35}", b.name), style),
+                Span::raw(" "),
+                Span::styled(format!("{:<16}", age), Style::default().fg(Color::Blue)),
+                Span::raw(" "),
+                Span::raw(format!("↑{:<4} ↓{:<4}", b.ahead, b.behind)),
+                Span::raw(" "),
+                pr,
+                Span::raw(" "),
+                Span::styled(
+                    prunable.map(|r| r.to_string()).unwrap_or_default(),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]);
+
+            ListItem::new(content).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!(" {} · {} branches ", repo, app.branches.len())));
+    f.render_widget(list, chunks[0]);
+
+    let (text, style) = if let Some(ref name) = app.pending_branch_delete {
+        (format!(" Press d again to delete {}", name), Style::default().fg(Color::Yellow))
+    } else if let Some(ref error) = app.error_message {
+        (format!(" ❌ {}", error), Style::default().fg(Color::Red))
+    } else {
+        (
+            format!(" Merged branches and branches idle for {} days can be deleted", BRANCH_STALE_DAYS),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    let footer = Paragraph::new(text)
+        .style(style)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[1]);
+}
+
+fn render_search(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let editing = app.input_mode == InputMode::Editing;
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(format!(" [{}] ", app.search_kind), Style::default().fg(Color::Cyan)),
+        Span::raw("🔍 "),
+        Span::raw(app.search_query.clone()),
+        Span::styled(if editing { "▏" } else { "" }, Style::default().fg(Color::Yellow)),
+    ]))
+    .style(if editing { Style::default().fg(Color::Yellow) } else { Style::default() })
+    .block(Block::default().borders(Borders::ALL).title(" Search (Tab: issues · pull requests · repositories · code) "));
+    f.render_widget(input, chunks[0]);
+
+    if let Some(ref error) = app.error_message {
+        let error_widget = Paragraph::new(format!("❌ {}", error))
+            .style(Style::default().fg(Color::Red))
+            .block(Block::default().borders(Borders::ALL).title(" Error "));
+        f.render_widget(error_widget, chunks[1]);
+        return;
+    }
+
+    let Some(ref results) = app.search_results else {
+        let hint = Paragraph::new("Type a query such as 'author:@me state:open' and press Enter.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(hint, chunks[1]);
+        return;
+    };
+
+    if results.hits.is_empty() {
+        let empty = Paragraph::new(format!("No {} found.", app.search_kind))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    let terms = &results.terms;
+    let dim = Style::default().fg(Color::DarkGray);
+    let lines: Vec<Vec<Span>> = match results.hits {
+        SearchHits::Issues(ref hits) => hits
+            .iter()
+            .map(|hit| {
+                let issue = &hit.issue;
+                let state_color = match issue.state {
+                    IssueState::Open => Color::Green,
+                    IssueState::Closed => Color::Magenta,
+                };
+                let mut spans = vec![
+                    Span::styled(format!("{:<28}", truncate(&hit.repository, 28)), dim),
+                    Span::styled(format!(" #{:<6} ", issue.number), Style::default().fg(Color::Blue)),
+                    Span::styled(format!("{:<7}", issue.state), Style::default().fg(state_color)),
+                ];
+                spans.extend(highlighted(truncate(&issue.title, 70), terms));
+                spans
+            })
+            .collect(),
+        SearchHits::Repositories(ref repos) => repos
+            .iter()
+            .map(|repo| {
+                let mut spans = highlighted(format!("{:<35}", repo.full_name), terms);
+                spans.push(Span::styled(format!(" ⭐ {:<7}", repo.stargazers_count), Style::default().fg(Color::Yellow)));
+                spans.push(Span::styled(
+                    format!("[{:<10}] ", repo.language.as_deref().unwrap_or("—")),
+                    Style::default().fg(Color::Blue),
+                ));
+                spans.extend(highlighted(truncate(repo.description.as_deref().unwrap_or(""), 60), terms));
+                spans
+            })
+            .collect(),
+        SearchHits::Code(ref hits) => hits
+            .iter()
+            .map(|hit| {
+                let mut spans = vec![
+                    Span::styled(format!("{:<28}", truncate(&hit.repository, 28)), dim),
+                    Span::raw(" "),
+                    Span::styled(format!("{:<40} ", truncate(&hit.path, 40)), Style::default().fg(Color::Blue)),
+                ];
+                let fragment = hit.fragment.as_deref().map(|f| snippet(f, terms, 60)).unwrap_or_default();
+                spans.extend(highlighted(fragment, terms));
+                spans
+            })
+            .collect(),
+    };
+
+    let items: Vec<ListItem> = lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, spans)| {
+            let style = if i == app.selected_index {
+                Style::default()
+                    .bg(Color::Rgb(60, 60, 100))
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+
+    let mut title = format!(" Showing {} of {} {} ", results.hits.len(), results.total_count, app.search_kind);
+    if results.incomplete_results {
+        title.push_str("· incomplete ");
+    }
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(list, chunks[1]);
+}
+
+fn pr_state_span(pr: &PullRequest) -> Span<'static> {
+    let (label, color) = match pr.state {
+        PullRequestState::Open if pr.draft => ("draft", Color::DarkGray),
+        PullRequestState::Open | PullRequestState::Draft => ("open", Color::Green),
+        PullRequestState::Closed => ("closed", Color::Red),
+        PullRequestState::Merged => ("merged", Color::Magenta),
+    };
+    Span::styled(format!("{:<7}", label), Style::default().fg(color))
+}
+
+fn check_span(status: CheckStatus) -> Span<'static> {
+    match status {
+        CheckStatus::Success => Span::styled("✓", Style::default().fg(Color::Green)),
+        CheckStatus::Failure => Span::styled("✗", Style::default().fg(Color::Red)),
+        CheckStatus::Pending => Span::styled("●", Style::default().fg(Color::Yellow)),
+        CheckStatus::None => Span::styled("—", Style::default().fg(Color::DarkGray)),
+    }
+}
+
+fn review_span(decision: Option<ReviewDecision>) -> Span<'static> {
+    let color = match decision {
+        Some(ReviewDecision::Approved) => Color::Green,
+        Some(ReviewDecision::ChangesRequested) => Color::Red,
+        Some(ReviewDecision::ReviewRequired) => Color::Yellow,
+        None => Color::DarkGray,
+    };
+    let label = decision.map(|d| d.to_string()).unwrap_or_else(|| "—".into());
+    Span::styled(format!("{:<18}", label), Style::default().fg(color))
+}
+
+fn render_pr_list(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let editing = app.input_mode == InputMode::Editing;
+    let author = if editing {
+        format!("{}▏", app.pr_author_input)
+    } else {
+        app.pr_query.author.clone().unwrap_or_else(|| "any".into())
+    };
+    let filters = Paragraph::new(Line::from(vec![
+        Span::raw(" State: "),
+        Span::styled(app.pr_query.state.to_string(), Style::default().fg(Color::Cyan)),
+        Span::raw("  Author: "),
+        Span::styled(author, Style::default().fg(if editing { Color::Yellow } else { Color::Cyan })),
+        Span::raw("  Review requested from me: "),
+        Span::styled(if app.pr_query.review_requested { "yes" } else { "no" }, Style::default().fg(Color::Cyan)),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(" Filters (s: state · a: author · v: review requested) "));
+    f.render_widget(filters, chunks[0]);
+
+    if let Some(ref error) = app.error_message {
+        let error_widget = Paragraph::new(format!("❌ {}", error))
+            .style(Style::default().fg(Color::Red))
+            .block(Block::default().borders(Borders::ALL).title(" Error "));
+        f.render_widget(error_widget, chunks[1]);
+        return;
+    }
+
+    if app.pull_requests.is_empty() {
+        let empty = Paragraph::new("No pull requests match the filters.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    let now = Utc::now();
+    let dim = Style::default().fg(Color::DarkGray);
+    let items: Vec<ListItem> = app.pull_requests
+        .iter()
+        .enumerate()
+        .map(|(i, pr)| {
+            let style = if i == app.selected_index {
+                Style::default()
+                    .bg(Color::Rgb(60, 60, 100))
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let content = Line::from(vec![
+                pr_state_span(pr),
+                Span::styled(format!("{:<28}", truncate(&pr.repository, 28)), dim),
+                Span::styled(format!(" #{:<6} ", pr.number), Style::default().fg(Color::Blue)),
+                Span::styled(format!("{:<55}", truncate(&pr.title, 55)), style),
+                Span::raw(" "),
+                check_span(pr.checks),
+                Span::raw(" "),
+                review_span(pr.review_decision),
+                Span::styled(format!("{:<14}", truncate(&pr.author, 14)), dim),
+                Span::styled(relative_time(pr.updated_at, now), Style::default().fg(Color::Blue)),
+            ]);
+
+            ListItem::new(content).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!(" {} pull requests ", app.pull_requests.len())));
+    f.render_widget(list, chunks[1]);
+}
+
+fn render_pr_detail(f: &mut Frame, area: Rect, app: &App) {
+    let Some(ref details) = app.pr_details else {
+        let text = app.error_message.clone().map(|e| format!("❌ {}", e)).unwrap_or_default();
+        let error_widget = Paragraph::new(text)
+            .style(Style::default().fg(Color::Red))
+            .block(Block::default().borders(Borders::ALL).title(" Error "));
+        f.render_widget(error_widget, area);
+        return;
+    };
+    let pr = &details.pull_request;
+    let now = Utc::now();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+
+    let mergeable = match pr.mergeable {
+        MergeableState::Mergeable => Span::styled("✓ no conflicts", Style::default().fg(Color::Green)),
+        MergeableState::Conflicting => Span::styled("✗ conflicts", Style::default().fg(Color::Red)),
+        MergeableState::Unknown => Span::styled("? not checked yet", Style::default().fg(Color::DarkGray)),
+    };
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(pr.title.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" #{}", pr.number), Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(vec![
+            pr_state_span(pr),
+            Span::raw(format!("{} wants to merge ", pr.author)),
+            Span::styled(pr.head_ref.clone(), Style::default().fg(Color::Blue)),
+            Span::raw(" into "),
+            Span::styled(pr.base_ref.clone(), Style::default().fg(Color::Blue)),
+            Span::styled(format!(" · opened {}", relative_time(pr.created_at, now)), Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(vec![
+            Span::raw("Checks "),
+            check_span(pr.checks),
+            Span::raw(format!(" {}  Review ", pr.checks)),
+            review_span(pr.review_decision),
+            mergeable,
+        ]),
+    ])
+    .block(Block::default().borders(Borders::ALL).title(format!(" {} ", pr.repository)));
+    f.render_widget(header, chunks[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    let description = match details.body {
+        Some(ref body) => Paragraph::new(markdown::to_plain_text(body)),
+        None => Paragraph::new("No description provided.").style(Style::default().fg(Color::DarkGray)),
+    };
+    f.render_widget(
+        description
+            .wrap(Wrap { trim: false })
+            .scroll((app.pr_scroll, 0))
+            .block(Block::default().borders(Borders::ALL).title(" Description ")),
+        body[0],
+    );
+
+    let side = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(25), Constraint::Percentage(40)])
+        .split(body[1]);
+
+    let checks: Vec<ListItem> = details.checks
+        .iter()
+        .map(|c| ListItem::new(Line::from(vec![check_span(c.status), Span::raw(format!(" {}", c.name))])))
+        .collect();
+    f.render_widget(
+        List::new(checks).block(Block::default().borders(Borders::ALL).title(format!(" Checks ({}) ", details.checks.len()))),
+        side[0],
+    );
+
+    let reviews: Vec<ListItem> = details.reviews
+        .iter()
+        .map(|r| {
+            let color = match r.state {
+                ReviewState::Approved => Color::Green,
+                ReviewState::ChangesRequested => Color::Red,
+                ReviewState::Commented => Color::Blue,
+                ReviewState::Dismissed | ReviewState::Pending => Color::DarkGray,
+            };
+            let when = r.submitted_at.map(|at| relative_time(at, now)).unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", r.author)),
+                Span::styled(r.state.to_string(), Style::default().fg(color)),
+                Span::styled(format!(" {}", when), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+    f.render_widget(
+        List::new(reviews).block(Block::default().borders(Borders::ALL).title(format!(" Reviews ({}) ", details.reviews.len()))),
+        side[1],
+    );
+
+    let files: Vec<ListItem> = details.files
+        .iter()
+        .map(|file| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("+{:<5}", file.additions), Style::default().fg(Color::Green)),
+                Span::styled(format!("-{:<5}", file.deletions), Style::default().fg(Color::Red)),
+                Span::raw(file.path.clone()),
+            ]))
+        })
+        .collect();
+    let files_title = if details.files.len() < details.changed_files as usize {
+        format!(" Files changed ({}, first {} shown) ", details.changed_files, details.files.len())
+    } else {
+        format!(" Files changed ({}) ", details.changed_files)
+    };
+    f.render_widget(List::new(files).block(Block::default().borders(Borders::ALL).title(files_title)), side[2]);
+
+    let (text, style) = match app.pending_pr_action {
+        Some(PendingPrAction::Approve) => (format!(" Press a again to approve #{}", pr.number), Style::default().fg(Color::Yellow)),
+        Some(PendingPrAction::Merge) => (
+            format!(" Merge #{} into {}? m: merge commit · s: squash · r: rebase · any other key: cancel", pr.number, pr.base_ref),
+            Style::default().fg(Color::Yellow),
+        ),
+        None => match app.error_message {
+            Some(ref error) => (format!(" ❌ {}", error), Style::default().fg(Color::Red)),
+            None => (format!(" {}", pr.html_url), Style::default().fg(Color::DarkGray)),
+        },
+    };
+    let footer = Paragraph::new(text)
+        .style(style)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+/// `text` as spans, with the parts matching `terms` in bold yellow.
+fn highlighted(text: String, terms: &[String]) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for range in highlight_ranges(&text, terms) {
+        spans.push(Span::raw(text[last..range.start].to_string()));
+        spans.push(Span::styled(
+            text[range.clone()].to_string(),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ));
+        last = range.end;
+    }
+    spans.push(Span::raw(text[last..].to_string()));
+    spans
+}
+
 fn render_storage_manager(f: &mut Frame, area: Rect, app: &App) {
+    let history_height = if app.storage_history.len() >= 2 { 6 } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),  // Usage gauge
-            Constraint::Min(0),     // Item list
+            Constraint::Length(5),               // Usage gauge
+            Constraint::Length(history_height),  // History chart
+            Constraint::Min(0),                  // Item list
         ])
         .split(area);
 
+    if history_height > 0 {
+        render_storage_history(f, chunks[1], app);
+    }
+
     // Storage gauge
     if let Some(ref report) = app.storage_report {
-        let percentage = if report.max_allowed > 0 {
-            (report.total_used as f64 / report.max_allowed as f64 * 100.0) as u16
+        let total_used = report.used_with_known_quota();
+        let max_allowed = report.max_allowed();
+        let percentage = if max_allowed > 0 {
+            (total_used as f64 / max_allowed as f64 * 100.0) as u16
         } else {
             0
         };
@@ -481,11 +979,13 @@ fn render_storage_manager(f: &mut Frame, area: Rect, app: &App) {
             Color::Green
         };
 
-        let used_mb = report.total_used as f64 / 1_048_576.0;
-        let max_mb = report.max_allowed as f64 / 1_048_576.0;
+        let used_mb = total_used as f64 / 1_048_576.0;
+        let max_mb = max_allowed as f64 / 1_048_576.0;
         
         // Display in GB if > 1024 MB
-        let label = if max_mb >= 1024.0 {
+        let label = if max_allowed == 0 {
+            format!("{:.2} MB / unknown quota", report.total_used() as f64 / 1_048_576.0)
+        } else if max_mb >= 1024.0 {
             format!("{:.2} MB / {:.2} GB ({:.1}%)", used_mb, max_mb / 1024.0, percentage)
         } else {
             format!("{:.2} MB / {:.2} MB ({:.1}%)", used_mb, max_mb, percentage)
@@ -513,7 +1013,7 @@ fn render_storage_manager(f: &mut Frame, area: Rect, app: &App) {
             .style(Style::default().fg(Color::Green))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
-        f.render_widget(empty, chunks[1]);
+        f.render_widget(empty, chunks[2]);
         return;
     }
 
@@ -543,6 +1043,7 @@ fn render_storage_manager(f: &mut Frame, area: Rect, app: &App) {
             let type_icon = match item.item_type {
                 StorageItemType::Artifact => "📦",
                 StorageItemType::Cache => "🗄️ ",
+                StorageItemType::ReleaseAsset => "🏷️ ",
             };
             let size = format!("{:.2} MB", item.size_in_bytes as f64 / 1_048_576.0);
 
@@ -571,34 +1072,65 @@ fn render_storage_manager(f: &mut Frame, area: Rect, app: &App) {
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title(title));
 
-    f.render_widget(list, chunks[1]);
+    f.render_widget(list, chunks[2]);
+}
+
+fn render_storage_history(f: &mut Frame, area: Rect, app: &App) {
+    let values: Vec<u64> = app.storage_history.iter().map(|p| p.used).collect();
+    let first = values.first().copied().unwrap_or(0);
+    let last = values.last().copied().unwrap_or(0);
+    let delta = last as i64 - first as i64;
+    let (trend, color) = if delta > 0 {
+        (format!("+{:.2} MB", delta as f64 / 1_048_576.0), Color::Red)
+    } else {
+        (format!("{:.2} MB", delta as f64 / 1_048_576.0), Color::Green)
+    };
+
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Usage history ({} snapshots, {}) ", values.len(), trend)),
+        )
+        .style(Style::default().fg(color))
+        .data(&values);
+
+    f.render_widget(sparkline, area);
 }
 
 fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
     let help = match app.current_view {
         View::MainMenu => "↑/↓: Navigate | Enter: Select | q: Quit",
         View::RepoList => if app.input_mode == InputMode::Normal {
-            "↑/↓: Navigate | Enter: Details | a: Artifacts | /: Filter | r: Refresh | Esc: Menu | q: Quit"
+            "↑/↓: Navigate | Enter: Details | a: Artifacts | /: Filter | s: Sort | o: Order | r: Refresh | Esc: Menu | q: Quit"
         } else {
             "Type to filter | Enter/Esc: Stop"
         },
-        View::RepoDetail => "a: Artifacts | Esc: Back | q: Quit",
+        View::RepoDetail => "a: Artifacts | b: Branches | Esc: Back | q: Quit",
+        View::BranchList => "↑/↓: Navigate | d: Delete (twice) | r: Refresh | Esc: Back | q: Quit",
+        View::Search => if app.input_mode == InputMode::Normal {
+            "↑/↓: Navigate | /: Edit query | Tab: Next kind | r: Refresh | Esc: Menu | q: Quit"
+        } else {
+            "Type a query | Tab: Kind | Enter: Search | Esc: Stop editing"
+        },
+        View::PullRequestList => if app.input_mode == InputMode::Normal {
+            "↑/↓: Navigate | Enter: Details | s: State | a: Author | v: Review requested | o: Browser | r: Refresh | Esc: Menu | q: Quit"
+        } else {
+            "Type a login (@me for you, empty for anyone) | Enter: Apply | Esc: Cancel"
+        },
+        View::PullRequestDetail => "↑/↓: Scroll | a: Approve | m: Merge | c: Checkout | o: Browser | r: Refresh | Esc: Back | q: Quit",
         View::ArtifactList => "↑/↓: Navigate | d: Delete | r: Refresh | Esc: Back | q: Quit",
         View::StorageManager => "↑/↓: Navigate | Space: Toggle | d: Delete selected | r: Refresh | Esc: Menu | q: Quit",
         View::AuthPrompt => "q: Quit",
     };
 
-    // Show success message if present
-    let text = if let Some(ref msg) = app.success_message {
-        msg.as_str()
-    } else {
-        help
-    };
-
-    let style = if app.success_message.is_some() {
-        Style::default().fg(Color::Green)
+    // Show an error or success message if present
+    let (text, style) = if let Some(ref msg) = app.error_message {
+        (msg.as_str(), Style::default().fg(Color::Red))
+    } else if let Some(ref msg) = app.success_message {
+        (msg.as_str(), Style::default().fg(Color::Green))
     } else {
-        Style::default().fg(Color::DarkGray)
+        (help, Style::default().fg(Color::DarkGray))
     };
 
     let status = Paragraph::new(text)
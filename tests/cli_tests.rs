@@ -19,6 +19,15 @@ fn test_auth_help() {
         .stdout(predicate::str::contains("Authenticate with GitHub"));
 }
 
+#[test]
+fn test_auth_status_rejects_output_options() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["auth", "status", "--format", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not support --format"));
+}
+
 #[test]
 fn test_ls_help() {
     let mut cmd = Command::cargo_bin("ghr").unwrap();
@@ -36,3 +45,561 @@ fn test_artifacts_help() {
         .success()
         .stdout(predicate::str::contains("Manage artifacts"));
 }
+
+#[test]
+fn test_df_help() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.arg("df").arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--group-by"))
+        .stdout(predicate::str::contains("--min-size"));
+}
+
+#[test]
+fn test_df_rejects_invalid_grouping() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["df", "--group-by", "size"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid grouping"));
+}
+
+#[test]
+fn test_df_rejects_invalid_threshold() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["df", "--fail-above", "120%"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be between 0 and 100"));
+}
+
+#[test]
+fn test_df_rejects_columns() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["df", "--columns", "repo,size_in_bytes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not support --columns"));
+}
+
+#[test]
+fn test_df_json_conflicts_with_csv() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["df", "--json", "--csv"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_df_history_help() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["df", "history", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Show recorded storage usage over time"));
+}
+
+#[test]
+fn test_retention_help() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["retention", "set", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--all-repos"))
+        .stdout(predicate::str::contains("--dry-run"));
+}
+
+#[test]
+fn test_retention_requires_a_target() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["retention", "set", "--days", "30"])
+        .assert()
+        .failure();
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["retention", "get", "--repo", "acme/api", "--org", "acme"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_retention_rejects_invalid_repo() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["retention", "get", "--repo", "not-a-repo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected owner/repo"));
+}
+
+#[test]
+fn test_global_format_option() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--format"))
+        .stdout(predicate::str::contains("--columns"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["artifacts", "list", "--format", "xml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'xml'"));
+}
+
+#[test]
+fn test_invalid_template_is_rejected() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--template", "{{name | bold}}"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown template helper"));
+}
+
+#[test]
+fn test_invalid_jq_expression_is_rejected() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["artifacts", "list", "--jq", ".[] | select(.size_in_bytes >"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid jq expression"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--jq", ".[].name", "--template", "{{name}}"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_ls_sort_and_filters() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--sort"))
+        .stdout(predicate::str::contains("--pushed-before"))
+        .stdout(predicate::str::contains("--min-stars"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--sort", "popularity"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid sort 'popularity'"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--pushed-after", "last week"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected YYYY-MM-DD"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--fork", "--source"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_ls_repo_type_requires_owner() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--type", "member"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--owner"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["ls", "--owner", "acme", "--type", "forks"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected all, member or sources"));
+}
+
+#[test]
+fn test_repo_view_help_and_validation() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "view", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("owner/repo"))
+        .stdout(predicate::str::contains("--json"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "view", "just-a-name"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected owner/repo"));
+}
+
+#[test]
+fn test_repo_clone_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "clone", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--org"))
+        .stdout(predicate::str::contains("--into"))
+        .stdout(predicate::str::contains("--parallel"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "clone"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("required"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "clone", "acme/api", "--org", "acme"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "clone", "--org", "acme", "--protocol", "ftp"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected ssh or https"));
+}
+
+#[test]
+fn test_repo_lifecycle_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "archive", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--dry-run"))
+        .stdout(predicate::str::contains("--pushed-before"))
+        .stdout(predicate::str::contains("--yes"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "unarchive"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("required"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "archive", "acme/api", "--filter", "svc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--org"));
+
+    // Deletion always asks for the repository name
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "delete", "acme/api", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected argument '--yes'"));
+}
+
+#[test]
+fn test_repo_create_and_fork_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "create", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--template"))
+        .stdout(predicate::str::contains("--gitignore"))
+        .stdout(predicate::str::contains("--license"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "create", "svc", "--template", "acme/tpl", "--license", "MIT"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "create", "svc", "--template", "tpl"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected owner/repo"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "fork"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("required"));
+}
+
+#[test]
+fn test_repo_edit_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "edit", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--settings"))
+        .stdout(predicate::str::contains("--delete-branch-on-merge"))
+        .stdout(predicate::str::contains("--dry-run"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "edit", "acme/api", "--wiki", "maybe"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values: true, false"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "edit", "acme/api", "--visibility", "secret"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected public, private or internal"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["repo", "edit", "acme/api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no settings to change"));
+}
+
+#[test]
+fn test_audit_repos_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["audit", "repos", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--rules"))
+        .stdout(predicate::str::contains("--json"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["audit", "repos"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--org <ORG>"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["audit", "repos", "--org", "acme", "--rules", "/nonexistent/rules.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot read /nonexistent/rules.toml"));
+}
+
+#[test]
+fn test_protect_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["protect", "apply", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--from"))
+        .stdout(predicate::str::contains("--ruleset"))
+        .stdout(predicate::str::contains("--dry-run"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["protect", "apply", "acme/api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--from <FILE>"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["protect", "show"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<REPO>"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["protect", "apply", "acme/api", "--from", "/nonexistent/protection.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot read /nonexistent/protection.toml"));
+}
+
+#[test]
+fn test_branches_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["branches", "list", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--merged"))
+        .stdout(predicate::str::contains("--stale-days"));
+
+    // JSON goes through the global --format like every other list
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["branches", "list", "acme/api", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected argument '--json'"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["branches", "prune", "acme/api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--merged"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["branches", "prune", "acme/api", "--stale-days", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn test_issue_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["issue", "list", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--label"))
+        .stdout(predicate::str::contains("--milestone"))
+        .stdout(predicate::str::contains("--search"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["issue", "list", "acme/api", "--state", "merged"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected open, closed or all"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["issue", "create", "acme/api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--title <TITLE>"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["issue", "close", "acme/api", "7", "--reason", "wontfix"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected completed or not-planned"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["issue", "view", "acme/api", "seven"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn test_search_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["search", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("issues"))
+        .stdout(predicate::str::contains("prs"))
+        .stdout(predicate::str::contains("repos"))
+        .stdout(predicate::str::contains("code"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["search", "prs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<QUERY>"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["search", "issues", "crash", "--limit", "5000"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1..=1000"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["search", "code", "--limit", "0", "--", "-path:tests"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1..=1000"));
+}
+
+#[test]
+fn test_pr_status_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "status", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--repo"))
+        .stdout(predicate::str::contains("--org"))
+        .stdout(predicate::str::contains("--json"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "status", "--repo", "acme/api", "--org", "acme"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "status", "--limit", "500"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1..=100"));
+}
+
+#[test]
+fn test_pr_action_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "merge", "12", "--repo", "acme/api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--merge"))
+        .stderr(predicate::str::contains("--squash"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "merge", "12", "--squash", "--rebase"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "merge", "12", "--squash", "--auto", "--delete-branch"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "review", "12", "--approve", "--comment"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "checkout", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--remote"))
+        .stdout(predicate::str::contains("--branch"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["pr", "diff", "abc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn test_release_arguments() {
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["release", "delete", "acme/cli"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--prereleases"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["release", "delete", "acme/cli", "v1.0.0", "--prereleases"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["release", "delete", "acme/cli", "v1.0.0", "--keep", "3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["release", "upload", "acme/cli", "v1.0.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<FILES>"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["release", "create", "acme/cli", "v1.0.0", "--notes", "x", "--notes-file", "NOTES.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["release", "download", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--pattern"))
+        .stdout(predicate::str::contains("--clobber"));
+
+    let mut cmd = Command::cargo_bin("ghr").unwrap();
+    cmd.args(["df", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--include-releases"));
+}